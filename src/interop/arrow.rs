@@ -0,0 +1,341 @@
+/// Arrow互通：点云与 Arrow RecordBatch 互转，经 Arrow C 数据接口（PyCapsule）与 pyarrow/polars/DuckDB 交换
+use crate::io::table::{self, TableColumnNames};
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::utils::error::{PointCloudError, Result};
+use arrow::array::{
+    Array, ArrayRef, ArrowPrimitiveType, AsArray, BooleanArray, ListArray, PrimitiveArray,
+    StructArray,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer, ScalarBuffer};
+use arrow::compute::cast;
+use arrow::datatypes::{self as arrow_types, DataType as ArrowType, Field, Schema};
+use arrow::error::ArrowError;
+use arrow::ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use arrow::record_batch::{RecordBatch, RecordBatchIterator};
+use polars::prelude::{
+    ChunkExplode, ChunkedArray, Column, DataFrame, DataType, Float32Type, IntoSeries,
+    ListBuilderTrait, ListChunked, ListPrimitiveChunkedBuilder, NamedFromOwned, PlSmallStr,
+    PolarsError, PolarsNumericType, Series,
+};
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use std::ffi::CStr;
+use std::sync::Arc;
+
+/// Arrow PyCapsule 接口的胶囊名称
+const STREAM_CAPSULE: &CStr = c"arrow_array_stream";
+const SCHEMA_CAPSULE: &CStr = c"arrow_schema";
+const ARRAY_CAPSULE: &CStr = c"arrow_array";
+
+impl HighPerformancePointCloud {
+    /// 转换为 Arrow RecordBatch，列布局同 to_table_parquet（多维属性为 float32 列表列）
+    pub fn to_record_batch(&self, columns: TableColumnNames) -> Result<RecordBatch> {
+        let df = table::to_dataframe(self, columns, false)?;
+        let mut fields = Vec::with_capacity(df.width());
+        let mut arrays = Vec::with_capacity(df.width());
+        for column in df.take_columns() {
+            let name = column.name().clone();
+            let array = column_to_arrow(column)?;
+            fields.push(Field::new(
+                name.as_str(),
+                array.data_type().clone(),
+                array.null_count() > 0,
+            ));
+            arrays.push(array);
+        }
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).map_err(arrow_err)
+    }
+
+    /// 从 Arrow RecordBatch 序列构建点云，列参数同 from_table_parquet
+    /// 字符串、时间等无法作为坐标或属性的列被忽略
+    pub fn from_record_batches(batches: &[RecordBatch], columns: TableColumnNames) -> Result<Self> {
+        let mut frames = batches.iter().map(batch_to_dataframe);
+        let Some(mut df) = frames.next().transpose()? else {
+            return Ok(Self::new());
+        };
+        // 各批次作为 polars 的块依次追加，不拼接复制
+        for frame in frames {
+            df.vstack_mut(&frame?).map_err(polars_err)?;
+        }
+        table::from_dataframe(df, columns)
+    }
+}
+
+/// 将 RecordBatch 导出为 arrow_array_stream 胶囊，由消费方读取并释放
+pub fn export_stream(py: Python<'_>, batch: RecordBatch) -> PyResult<Bound<'_, PyCapsule>> {
+    let schema = batch.schema();
+    let reader = RecordBatchIterator::new(vec![Ok(batch)], schema);
+    let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+    PyCapsule::new(py, stream, Some(STREAM_CAPSULE.to_owned()))
+}
+
+/// 从实现 Arrow PyCapsule 接口的对象导入 RecordBatch
+/// 支持 __arrow_c_stream__（pyarrow.Table、polars.DataFrame、DuckDB 结果等）与 __arrow_c_array__
+pub fn import_batches(obj: &Bound<'_, PyAny>) -> PyResult<Vec<RecordBatch>> {
+    if obj.hasattr("__arrow_c_stream__")? {
+        let capsule = obj
+            .call_method0("__arrow_c_stream__")?
+            .cast_into::<PyCapsule>()?;
+        let raw = capsule.pointer_checked(Some(STREAM_CAPSULE))?;
+        // from_raw 移出流结构体并将胶囊内的 release 置空，胶囊析构时不再重复释放
+        let stream = unsafe { FFI_ArrowArrayStream::from_raw(raw.cast().as_ptr()) };
+        let reader = ArrowArrayStreamReader::try_new(stream).map_err(arrow_err)?;
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(arrow_err)?;
+        return Ok(batches);
+    }
+    if obj.hasattr("__arrow_c_array__")? {
+        let (schema, array): (Bound<'_, PyCapsule>, Bound<'_, PyCapsule>) =
+            obj.call_method0("__arrow_c_array__")?.extract()?;
+        let schema = schema.pointer_checked(Some(SCHEMA_CAPSULE))?;
+        let array = array.pointer_checked(Some(ARRAY_CAPSULE))?;
+        let data = unsafe {
+            let array = FFI_ArrowArray::from_raw(array.cast().as_ptr());
+            from_ffi(array, schema.cast::<FFI_ArrowSchema>().as_ref())
+        }
+        .map_err(arrow_err)?;
+        if !matches!(data.data_type(), ArrowType::Struct(_)) {
+            return Err(PyTypeError::new_err(format!(
+                "__arrow_c_array__ 返回的是{}数组，需要结构体数组（RecordBatch）",
+                data.data_type()
+            )));
+        }
+        return Ok(vec![RecordBatch::from(StructArray::from(data))]);
+    }
+    Err(PyTypeError::new_err(
+        "对象未实现 Arrow PyCapsule 接口（__arrow_c_stream__ 或 __arrow_c_array__）",
+    ))
+}
+
+/// RecordBatch -> polars DataFrame，跳过不支持的列
+fn batch_to_dataframe(batch: &RecordBatch) -> Result<DataFrame> {
+    let mut frame = Vec::with_capacity(batch.num_columns());
+    for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+        if let Some(column) = arrow_to_column(field.name(), array)? {
+            frame.push(column);
+        }
+    }
+    DataFrame::new(frame).map_err(polars_err)
+}
+
+/// polars 列 -> Arrow 数组（布尔、整数、浮点与 float32 列表列）
+fn column_to_arrow(column: Column) -> Result<ArrayRef> {
+    let series = column.take_materialized_series();
+    let array: ArrayRef = match series.dtype().clone() {
+        DataType::Boolean => Arc::new(
+            series
+                .bool()
+                .map_err(polars_err)?
+                .into_iter()
+                .collect::<BooleanArray>(),
+        ),
+        DataType::UInt8 => primitive_to_arrow::<_, arrow_types::UInt8Type>(series)?,
+        DataType::Int8 => primitive_to_arrow::<_, arrow_types::Int8Type>(series)?,
+        DataType::UInt16 => primitive_to_arrow::<_, arrow_types::UInt16Type>(series)?,
+        DataType::Int16 => primitive_to_arrow::<_, arrow_types::Int16Type>(series)?,
+        DataType::UInt32 => primitive_to_arrow::<_, arrow_types::UInt32Type>(series)?,
+        DataType::Int32 => primitive_to_arrow::<_, arrow_types::Int32Type>(series)?,
+        DataType::UInt64 => primitive_to_arrow::<_, arrow_types::UInt64Type>(series)?,
+        DataType::Int64 => primitive_to_arrow::<_, arrow_types::Int64Type>(series)?,
+        DataType::Float32 => primitive_to_arrow::<_, arrow_types::Float32Type>(series)?,
+        DataType::Float64 => primitive_to_arrow::<_, arrow_types::Float64Type>(series)?,
+        DataType::List(_) => {
+            let list = series
+                .cast(&DataType::List(Box::new(DataType::Float32)))
+                .map_err(polars_err)?;
+            drop(series);
+            let chunked = list.list().map_err(polars_err)?.clone();
+            drop(list);
+            list_to_arrow(chunked)?
+        }
+        dtype => {
+            return Err(PointCloudError::ConversionError(format!(
+                "列{}的类型{}不能转换为Arrow数组",
+                series.name(),
+                dtype
+            )))
+        }
+    };
+    Ok(array)
+}
+
+/// polars 数值列 -> Arrow 数组：缓冲区为该列独占时（如 to_dataframe 新建的列）直接移交，不复制
+fn primitive_to_arrow<T, A>(series: Series) -> Result<ArrayRef>
+where
+    T: PolarsNumericType,
+    A: ArrowPrimitiveType<Native = T::Native>,
+{
+    let values: ChunkedArray<T> = series.unpack::<T>().map_err(polars_err)?.clone();
+    drop(series);
+    let values = if values.chunks().len() > 1 {
+        values.rechunk().into_owned()
+    } else {
+        values
+    };
+    let Some(chunk) = values.downcast_into_iter().next() else {
+        return Ok(Arc::new(PrimitiveArray::<A>::new(
+            ScalarBuffer::from(Vec::new()),
+            None,
+        )));
+    };
+    let (_, buffer, validity) = chunk.into_inner();
+    let nulls = validity.map(|bitmap| bitmap.iter().collect::<NullBuffer>());
+    Ok(Arc::new(PrimitiveArray::<A>::new(
+        ScalarBuffer::from(buffer.make_mut()),
+        nulls,
+    )))
+}
+
+/// polars float32 列表列 -> Arrow List 数组，内层值缓冲区同 primitive_to_arrow 移交
+fn list_to_arrow(list: ListChunked) -> Result<ArrayRef> {
+    if list.null_count() > 0 {
+        return Err(PointCloudError::ConversionError(format!(
+            "列{}包含空值",
+            list.name()
+        )));
+    }
+    let list = if list.chunks().len() > 1 {
+        list.rechunk().into_owned()
+    } else {
+        list
+    };
+    let offsets = list
+        .offsets()
+        .map_err(polars_err)?
+        .iter()
+        .map(|&offset| i32::try_from(offset))
+        .collect::<std::result::Result<Vec<i32>, _>>()
+        .map_err(|_| {
+            PointCloudError::ConversionError(format!("列{}的元素数超过Arrow List上限", list.name()))
+        })?;
+    let values = list.get_inner();
+    drop(list);
+    let values = primitive_to_arrow::<Float32Type, arrow_types::Float32Type>(values)?;
+    let array = ListArray::try_new(
+        Arc::new(Field::new_list_field(ArrowType::Float32, true)),
+        OffsetBuffer::new(ScalarBuffer::from(offsets)),
+        values,
+        None,
+    )
+    .map_err(arrow_err)?;
+    Ok(Arc::new(array))
+}
+
+/// Arrow 数组 -> polars 列，数值列表（含定长列表）转为 float32 列表列；不支持的类型返回 None
+/// 无空值的数值列只复制一次值缓冲区；空值保留，由读取坐标与属性时报错
+fn arrow_to_column(name: &str, array: &ArrayRef) -> Result<Option<Column>> {
+    let name = PlSmallStr::from_str(name);
+    macro_rules! primitive {
+        ($ty:ty) => {{
+            let array = array.as_primitive::<$ty>();
+            if array.null_count() > 0 {
+                Column::new(name, array.iter().collect::<Vec<_>>())
+            } else {
+                Series::from_vec(name, array.values().to_vec()).into()
+            }
+        }};
+    }
+    let column = match array.data_type() {
+        ArrowType::Boolean => Column::new(name, array.as_boolean().iter().collect::<Vec<_>>()),
+        ArrowType::UInt8 => primitive!(arrow_types::UInt8Type),
+        ArrowType::Int8 => primitive!(arrow_types::Int8Type),
+        ArrowType::UInt16 => primitive!(arrow_types::UInt16Type),
+        ArrowType::Int16 => primitive!(arrow_types::Int16Type),
+        ArrowType::UInt32 => primitive!(arrow_types::UInt32Type),
+        ArrowType::Int32 => primitive!(arrow_types::Int32Type),
+        ArrowType::UInt64 => primitive!(arrow_types::UInt64Type),
+        ArrowType::Int64 => primitive!(arrow_types::Int64Type),
+        ArrowType::Float32 => primitive!(arrow_types::Float32Type),
+        ArrowType::Float64 => primitive!(arrow_types::Float64Type),
+        ArrowType::List(inner)
+        | ArrowType::LargeList(inner)
+        | ArrowType::FixedSizeList(inner, _)
+            if inner.data_type().is_numeric() =>
+        {
+            let list =
+                cast(array, &ArrowType::new_list(ArrowType::Float32, true)).map_err(arrow_err)?;
+            let list = list.as_list::<i32>();
+            if list.values().null_count() > 0 {
+                return Err(PointCloudError::ConversionError(format!(
+                    "列{}的列表元素包含空值",
+                    name
+                )));
+            }
+            let mut builder = ListPrimitiveChunkedBuilder::<Float32Type>::new(
+                name,
+                list.len(),
+                list.values().len(),
+                DataType::Float32,
+            );
+            for row in list.iter() {
+                match row {
+                    Some(row) => builder
+                        .append_slice(row.as_primitive::<arrow_types::Float32Type>().values()),
+                    None => builder.append_null(),
+                }
+            }
+            builder.finish().into_series().into()
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(column))
+}
+
+fn arrow_err(e: ArrowError) -> PointCloudError {
+    PointCloudError::ConversionError(e.to_string())
+}
+
+fn polars_err(e: PolarsError) -> PointCloudError {
+    PointCloudError::ConversionError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point_cloud::attribute_data::AttributeData;
+    use crate::traits::PointCloudCore;
+    use crate::utils::tensor;
+
+    #[test]
+    fn test_record_batch_roundtrip() {
+        // 原点与读回时自动选择的原点不同，按全局坐标比较
+        let points = [[500_000.5, 4_000_001.0, 2.0], [500_003.0, 4_000_004.0, 5.0]];
+        let mut pc = HighPerformancePointCloud::from_global_points(
+            &points,
+            Some([499_000.0, 3_999_000.0, 0.0]),
+        )
+        .unwrap();
+        pc.set_attribute_data("label".to_string(), AttributeData::U16(vec![7, 9]))
+            .unwrap();
+        let normal = tensor::tensor2_from_slice(&[0.0, 0.0, 1.0, 1.0, 0.0, 0.0], 2, 3).unwrap();
+        pc.set_attribute_data("normal".to_string(), AttributeData::Vector(normal))
+            .unwrap();
+
+        let batch = pc.to_record_batch(TableColumnNames::default()).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(
+            batch.schema().field_with_name("label").unwrap().data_type(),
+            &ArrowType::UInt16
+        );
+        assert!(matches!(
+            batch
+                .schema()
+                .field_with_name("normal")
+                .unwrap()
+                .data_type(),
+            ArrowType::List(_)
+        ));
+
+        let columns = TableColumnNames::default().with_all_attributes(true, None);
+        let loaded = HighPerformancePointCloud::from_record_batches(&[batch], columns).unwrap();
+        assert_eq!(loaded.get_xyz(), pc.get_xyz());
+        assert!(matches!(loaded.attribute("label"), Some(AttributeData::U16(v)) if v == &[7, 9]));
+        assert_eq!(
+            loaded.attribute("normal").unwrap().to_f32_vec(),
+            vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]
+        );
+    }
+}
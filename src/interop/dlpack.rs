@@ -0,0 +1,485 @@
+/// DLPack互通：主机张量经 __dlpack__ / __array_interface__ 暴露给 PyTorch/NumPy
+/// NdArray 后端上的 float32 张量直接共享点云的存储，其余数据取出时复制一次
+use crate::point_cloud::attribute_data::match_attribute;
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor::{self, Backend};
+use burn::backend::ndarray::NdArrayTensor;
+use burn::tensor::{DType, Tensor, TensorData};
+use pyo3::ffi;
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use std::ffi::{c_void, CStr};
+use std::ptr;
+use std::sync::Arc;
+
+const DLTENSOR: &CStr = c"dltensor";
+const USED_DLTENSOR: &CStr = c"used_dltensor";
+/// DLPack 设备类型：CPU
+pub const DL_CPU: i32 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DLDevice {
+    device_type: i32,
+    device_id: i32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct DLDataType {
+    code: u8,
+    bits: u8,
+    lanes: u16,
+}
+
+#[repr(C)]
+struct DLTensor {
+    data: *mut c_void,
+    device: DLDevice,
+    ndim: i32,
+    dtype: DLDataType,
+    shape: *mut i64,
+    strides: *mut i64,
+    byte_offset: u64,
+}
+
+#[repr(C)]
+struct DLManagedTensor {
+    dl_tensor: DLTensor,
+    manager_ctx: *mut c_void,
+    deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+/// 导出的 DLManagedTensor 及其形状与缓冲区的所有权，deleter 释放整个结构
+#[repr(C)]
+struct ExportedTensor {
+    managed: DLManagedTensor,
+    shape: Vec<i64>,
+    _owner: Arc<HostTensor>,
+}
+
+/// 主机张量的缓冲区
+enum Buffer {
+    /// NdArray 后端张量的存储，与点云共享（只增加引用计数）
+    Shared(NdArrayTensor),
+    /// 复制得到的独立缓冲区
+    Owned(TensorData),
+}
+
+/// 主机内存中的行优先稠密张量，导出时直接暴露该缓冲区
+pub struct HostTensor {
+    buffer: Buffer,
+    shape: Vec<usize>,
+    dtype: DType,
+}
+
+impl HostTensor {
+    pub fn new(data: TensorData) -> Self {
+        Self {
+            shape: data.shape.clone(),
+            dtype: data.dtype,
+            buffer: Buffer::Owned(data),
+        }
+    }
+
+    /// 取出浮点张量：NdArray 后端上共享其连续存储，其他后端或非连续存储时回读复制一次
+    pub fn from_float<const D: usize>(tensor: &Tensor<Backend, D>) -> Self {
+        match tensor::ndarray_storage(tensor) {
+            Some(storage) if f32_slice(&storage).is_some() => Self {
+                shape: tensor.dims().to_vec(),
+                dtype: DType::F32,
+                buffer: Buffer::Shared(storage),
+            },
+            _ => Self::new(tensor.clone().into_data()),
+        }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// 是否与点云共享存储（未复制）
+    pub fn is_shared(&self) -> bool {
+        matches!(self.buffer, Buffer::Shared(_))
+    }
+
+    /// NumPy 数组接口的类型字符串（如 "<f4"）
+    pub fn typestr(&self) -> Result<&'static str> {
+        Ok(element_type(self.dtype)?.1)
+    }
+
+    /// 缓冲区首地址
+    pub fn data_ptr(&self) -> usize {
+        match &self.buffer {
+            Buffer::Shared(storage) => f32_slice(storage).map_or(0, |s| s.as_ptr() as usize),
+            Buffer::Owned(data) => data.as_bytes().as_ptr() as usize,
+        }
+    }
+
+    /// float32 张量的数据，其他类型为 None
+    fn as_f32(&self) -> Option<&[f32]> {
+        match &self.buffer {
+            Buffer::Shared(storage) => f32_slice(storage),
+            Buffer::Owned(data) => data.as_slice::<f32>().ok(),
+        }
+    }
+
+    /// 转为独立的 TensorData：共享存储时复制一次
+    fn into_data(self) -> TensorData {
+        match self.buffer {
+            Buffer::Shared(storage) => {
+                TensorData::new(f32_slice(&storage).unwrap_or_default().to_vec(), self.shape)
+            }
+            Buffer::Owned(data) => data,
+        }
+    }
+
+    /// 复制缓冲区得到独立的张量（__dlpack__ 的 copy=True）
+    pub fn deep_copy(&self) -> Self {
+        let data = match &self.buffer {
+            Buffer::Shared(storage) => TensorData::new(
+                f32_slice(storage).unwrap_or_default().to_vec(),
+                self.shape.clone(),
+            ),
+            Buffer::Owned(data) => data.clone(),
+        };
+        Self::new(data)
+    }
+}
+
+/// NdArray float32 存储的行优先连续切片，非连续或其他类型时为 None
+fn f32_slice(storage: &NdArrayTensor) -> Option<&[f32]> {
+    match storage {
+        NdArrayTensor::F32(storage) => storage.view().to_slice(),
+        _ => None,
+    }
+}
+
+impl HighPerformancePointCloud {
+    /// 按名称取出主机张量：xyz（同 get_xyz）、local_xyz（float32 局部坐标）、rgb（[M,3] uint8）、
+    /// intensity、gps_time 或自定义属性
+    /// NdArray 后端上的 local_xyz、intensity、float32 与多维属性共享点云的存储，不复制；
+    /// float64 全局坐标与主机端的 rgb、gps_time、其他类型属性复制一次
+    pub fn host_tensor(&self, name: &str) -> Result<HostTensor> {
+        let n = self.point_count();
+        let host = match name {
+            "xyz" => HostTensor::new(TensorData::new(self.global_xyz_flat(), [n, 3])),
+            "local_xyz" => HostTensor::from_float(self.xyz_ref()),
+            "rgb" => {
+                let (r, g, b) = self
+                    .get_rgb()
+                    .ok_or_else(|| PointCloudError::InvalidParameter("点云没有RGB".to_string()))?;
+                let flat: Vec<u8> = (0..n).flat_map(|i| [r[i], g[i], b[i]]).collect();
+                HostTensor::new(TensorData::new(flat, [n, 3]))
+            }
+            "intensity" => HostTensor::from_float(
+                self.intensity_ref()
+                    .ok_or_else(|| PointCloudError::InvalidParameter("点云没有强度".to_string()))?,
+            ),
+            "gps_time" => {
+                let times = self.gps_time_ref().ok_or_else(|| {
+                    PointCloudError::InvalidParameter("点云没有GPS时间".to_string())
+                })?;
+                HostTensor::new(TensorData::new(times.to_vec(), [n]))
+            }
+            _ => {
+                let data = self.attribute(name).ok_or_else(|| {
+                    PointCloudError::InvalidParameter(format!("属性'{}'不存在", name))
+                })?;
+                match_attribute!(
+                    data,
+                    t => HostTensor::from_float(t),
+                    m => HostTensor::from_float(m),
+                    values => HostTensor::new(TensorData::new(values.clone(), [values.len()]))
+                )
+            }
+        };
+        Ok(host)
+    }
+
+    /// 按行展开的 float64 全局坐标：在局部坐标缓冲区上一遍加上原点，NdArray 后端上只复制这一次
+    pub fn global_xyz_flat(&self) -> Vec<f64> {
+        let local = HostTensor::from_float(self.xyz_ref());
+        let origin = self.origin();
+        local
+            .as_f32()
+            .unwrap_or_default()
+            .iter()
+            .zip(origin.iter().cycle())
+            .map(|(&v, &o)| v as f64 + o)
+            .collect()
+    }
+
+    /// 从 [M,3] 主机张量创建点云：float64 为全局坐标并自动设置原点，其他类型按 float32 读取
+    pub fn from_host_xyz(xyz: HostTensor) -> Result<Self> {
+        let shape = xyz.shape().to_vec();
+        if shape.len() != 2 || shape[1] != 3 {
+            return Err(PointCloudError::TensorShapeError(format!(
+                "XYZ必须是[N,3]的形状，实际为{:?}",
+                shape
+            )));
+        }
+        if xyz.dtype == DType::F64 {
+            let flat = xyz
+                .into_data()
+                .into_vec::<f64>()
+                .map_err(|e| PointCloudError::ConversionError(format!("{:?}", e)))?;
+            let points: Vec<[f64; 3]> = flat.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect();
+            if points.is_empty() {
+                return Ok(Self::new());
+            }
+            return Self::from_global_points(&points, None);
+        }
+        Self::from_tensor_xyz(tensor::tensor2_from_data(xyz.into_data())?)
+    }
+}
+
+/// DType -> (DLPack 类型, NumPy 类型字符串)
+fn element_type(dtype: DType) -> Result<(DLDataType, &'static str)> {
+    let (code, bits, typestr) = match dtype {
+        DType::F64 => (2, 64, "<f8"),
+        DType::F32 => (2, 32, "<f4"),
+        DType::I64 => (0, 64, "<i8"),
+        DType::I32 => (0, 32, "<i4"),
+        DType::I16 => (0, 16, "<i2"),
+        DType::I8 => (0, 8, "|i1"),
+        DType::U64 => (1, 64, "<u8"),
+        DType::U32 => (1, 32, "<u4"),
+        DType::U16 => (1, 16, "<u2"),
+        DType::U8 => (1, 8, "|u1"),
+        DType::Bool => (6, 8, "|b1"),
+        dtype => {
+            return Err(PointCloudError::ConversionError(format!(
+                "不支持导出{:?}类型的张量",
+                dtype
+            )))
+        }
+    };
+    Ok((
+        DLDataType {
+            code,
+            bits,
+            lanes: 1,
+        },
+        typestr,
+    ))
+}
+
+/// DLPack 类型 -> DType
+fn dtype_of(dl: DLDataType) -> Result<DType> {
+    let dtype = match (dl.code, dl.bits, dl.lanes) {
+        (2, 64, 1) => DType::F64,
+        (2, 32, 1) => DType::F32,
+        (0, 64, 1) => DType::I64,
+        (0, 32, 1) => DType::I32,
+        (0, 16, 1) => DType::I16,
+        (0, 8, 1) => DType::I8,
+        (1, 64, 1) => DType::U64,
+        (1, 32, 1) => DType::U32,
+        (1, 16, 1) => DType::U16,
+        (1, 8, 1) => DType::U8,
+        (6, 8, 1) => DType::Bool,
+        (code, bits, lanes) => {
+            return Err(PointCloudError::ConversionError(format!(
+                "不支持的DLPack类型: code={} bits={} lanes={}",
+                code, bits, lanes
+            )))
+        }
+    };
+    Ok(dtype)
+}
+
+/// 将主机张量导出为 dltensor 胶囊（strides 为空，表示行优先连续）
+pub fn export_dlpack<'py>(
+    py: Python<'py>,
+    tensor: &Arc<HostTensor>,
+) -> PyResult<Bound<'py, PyCapsule>> {
+    let (dtype, _) = element_type(tensor.dtype)?;
+    let mut exported = Box::new(ExportedTensor {
+        managed: DLManagedTensor {
+            dl_tensor: DLTensor {
+                data: tensor.data_ptr() as *mut c_void,
+                device: DLDevice {
+                    device_type: DL_CPU,
+                    device_id: 0,
+                },
+                ndim: tensor.shape().len() as i32,
+                dtype,
+                shape: ptr::null_mut(),
+                strides: ptr::null_mut(),
+                byte_offset: 0,
+            },
+            manager_ctx: ptr::null_mut(),
+            deleter: Some(delete_exported),
+        },
+        shape: tensor.shape().iter().map(|&d| d as i64).collect(),
+        _owner: Arc::clone(tensor),
+    });
+    exported.managed.dl_tensor.shape = exported.shape.as_mut_ptr();
+    let raw = Box::into_raw(exported);
+    let capsule =
+        unsafe { ffi::PyCapsule_New(raw.cast(), DLTENSOR.as_ptr(), Some(capsule_destructor)) };
+    if capsule.is_null() {
+        unsafe { drop(Box::from_raw(raw)) };
+        return Err(PyErr::fetch(py));
+    }
+    Ok(unsafe { Bound::from_owned_ptr(py, capsule).cast_into_unchecked() })
+}
+
+/// 从实现 __dlpack__ 的对象（torch.Tensor、numpy 数组等）复制出行优先主机张量，仅支持 CPU 设备
+pub fn import_dlpack(obj: &Bound<'_, PyAny>) -> PyResult<HostTensor> {
+    if obj.hasattr("__dlpack_device__")? {
+        let (device_type, _): (i32, i32) = obj.call_method0("__dlpack_device__")?.extract()?;
+        if device_type != DL_CPU {
+            return Err(PointCloudError::InvalidParameter(
+                "仅支持CPU上的DLPack张量，GPU张量请先调用 .cpu()".to_string(),
+            )
+            .into());
+        }
+    }
+    let capsule = obj.call_method0("__dlpack__")?.cast_into::<PyCapsule>()?;
+    let managed = capsule
+        .pointer_checked(Some(DLTENSOR))?
+        .cast::<DLManagedTensor>();
+    let result = unsafe { copy_dl_tensor(&managed.as_ref().dl_tensor) };
+    // 改名表示已消费，随后由消费方调用 deleter，生产方的胶囊析构时不再释放
+    unsafe {
+        ffi::PyCapsule_SetName(capsule.as_ptr(), USED_DLTENSOR.as_ptr());
+        if let Some(deleter) = managed.as_ref().deleter {
+            deleter(managed.as_ptr());
+        }
+    }
+    result.map_err(PyErr::from)
+}
+
+/// 按 shape/strides 把 DLTensor 复制为行优先的 TensorData
+unsafe fn copy_dl_tensor(tensor: &DLTensor) -> Result<HostTensor> {
+    let dtype = dtype_of(tensor.dtype)?;
+    let size = usize::from(tensor.dtype.bits / 8);
+    let ndim = usize::try_from(tensor.ndim).unwrap_or(0);
+    let shape: Vec<usize> = (0..ndim).map(|d| *tensor.shape.add(d) as usize).collect();
+    let count: usize = shape.iter().product();
+    let base = tensor.data.cast::<u8>().add(tensor.byte_offset as usize);
+
+    let bytes = if count == 0 {
+        Vec::new()
+    } else if tensor.strides.is_null() {
+        std::slice::from_raw_parts(base, count * size).to_vec()
+    } else {
+        let strides: Vec<isize> = (0..ndim).map(|d| *tensor.strides.add(d) as isize).collect();
+        let mut bytes = Vec::with_capacity(count * size);
+        for linear in 0..count {
+            let (mut rest, mut offset) = (linear, 0isize);
+            for d in (0..ndim).rev() {
+                offset += (rest % shape[d]) as isize * strides[d];
+                rest /= shape[d];
+            }
+            let element = base.offset(offset * size as isize);
+            bytes.extend_from_slice(std::slice::from_raw_parts(element, size));
+        }
+        bytes
+    };
+    Ok(HostTensor::new(TensorData::from_bytes_vec(
+        bytes, shape, dtype,
+    )))
+}
+
+/// 释放导出的张量（消费方用完后调用）
+unsafe extern "C" fn delete_exported(managed: *mut DLManagedTensor) {
+    drop(Box::from_raw(managed.cast::<ExportedTensor>()));
+}
+
+/// 胶囊析构：未被消费（名称仍为 dltensor）时由生产方调用 deleter
+unsafe extern "C" fn capsule_destructor(capsule: *mut ffi::PyObject) {
+    if ffi::PyCapsule_IsValid(capsule, DLTENSOR.as_ptr()) == 1 {
+        let managed =
+            ffi::PyCapsule_GetPointer(capsule, DLTENSOR.as_ptr()).cast::<DLManagedTensor>();
+        if let Some(deleter) = (*managed).deleter {
+            deleter(managed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point_cloud::attribute_data::AttributeData;
+
+    #[test]
+    fn test_host_tensor_roundtrip() {
+        let mut pc =
+            HighPerformancePointCloud::from_xyz(vec![vec![0.0, 1.0, 2.0], vec![3.0, 4.0, 5.0]])
+                .unwrap();
+        pc.set_attribute_data("label".to_string(), AttributeData::U16(vec![7, 9]))
+            .unwrap();
+
+        let xyz = pc.host_tensor("xyz").unwrap();
+        assert_eq!(xyz.shape(), &[2, 3]);
+        assert_eq!(xyz.typestr().unwrap(), "<f8");
+        let label = pc.host_tensor("label").unwrap();
+        assert_eq!(label.typestr().unwrap(), "<u2");
+        assert!(pc.host_tensor("missing").is_err());
+
+        let loaded = HighPerformancePointCloud::from_host_xyz(xyz).unwrap();
+        assert_eq!(loaded.get_xyz(), pc.get_xyz());
+    }
+
+    #[test]
+    fn test_host_tensor_shares_storage() {
+        let mut pc =
+            HighPerformancePointCloud::from_xyz(vec![vec![0.0, 1.0, 2.0], vec![3.0, 4.0, 5.0]])
+                .unwrap();
+        let feat = tensor::tensor2_from_slice(&[1.0, 2.0, 3.0, 4.0], 2, 2).unwrap();
+        pc.set_attribute_data("feat".to_string(), AttributeData::Vector(feat))
+            .unwrap();
+
+        let local = pc.host_tensor("local_xyz").unwrap();
+        let again = pc.host_tensor("local_xyz").unwrap();
+        assert_eq!(local.typestr().unwrap(), "<f4");
+        // NdArray 后端上两次取出指向同一份存储
+        if local.is_shared() {
+            assert!(again.is_shared());
+            assert_eq!(local.data_ptr(), again.data_ptr());
+        }
+        let feat = pc.host_tensor("feat").unwrap();
+        assert_eq!(feat.shape(), &[2, 2]);
+        assert!(!pc.host_tensor("xyz").unwrap().is_shared());
+
+        // 主机张量持有存储的引用，点云释放后仍可读取
+        drop(pc);
+        assert_eq!(local.as_f32().unwrap(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(feat.as_f32().unwrap(), &[1.0, 2.0, 3.0, 4.0]);
+        let copied = local.deep_copy();
+        assert!(!copied.is_shared());
+        assert_ne!(copied.data_ptr(), local.data_ptr());
+        assert_eq!(copied.as_f32(), local.as_f32());
+    }
+
+    #[test]
+    fn test_copy_strided_tensor() {
+        // 2x3 行优先缓冲区按转置的 [3,2] 视图读取
+        let mut values = [0.0f64, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut shape = [3i64, 2];
+        let mut strides = [1i64, 3];
+        let tensor = DLTensor {
+            data: values.as_mut_ptr().cast(),
+            device: DLDevice {
+                device_type: DL_CPU,
+                device_id: 0,
+            },
+            ndim: 2,
+            dtype: element_type(DType::F64).unwrap().0,
+            shape: shape.as_mut_ptr(),
+            strides: strides.as_mut_ptr(),
+            byte_offset: 0,
+        };
+        let host = unsafe { copy_dl_tensor(&tensor) }.unwrap();
+        assert_eq!(host.shape(), &[3, 2]);
+        assert_eq!(
+            host.into_data().into_vec::<f64>().unwrap(),
+            vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]
+        );
+    }
+}
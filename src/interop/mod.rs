@@ -1,2 +1,4 @@
 /// 跨生态互通模块入口
+pub mod arrow;
+pub mod dlpack;
 pub mod numpy;
@@ -1,10 +1,12 @@
 /// NumPy互通：点云与numpy数组批量互转，零/低拷贝优化
+use crate::point_cloud::attribute_data::{match_attribute, AttributeData, AttributeValue};
+use crate::point_cloud::camera::{Distortion, Extrinsic, PinholeIntrinsics};
 use crate::point_cloud::core::HighPerformancePointCloud;
 use crate::traits::PointCloudCore;
 use crate::utils::error::Result;
 use crate::utils::tensor;
-use numpy::ndarray::{Array1, Array2};
-use numpy::{IntoPyArray, PyArrayMethods, PyUntypedArrayMethods};
+use numpy::ndarray::{Array1, Array2, Array3};
+use numpy::{Element, IntoPyArray, PyArrayMethods, PyUntypedArrayMethods};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyDictMethods};
 
@@ -15,16 +17,7 @@ pub fn to_numpy<'py>(&self, py: Python<'py>) -> Result<Py<PyAny>> {
         let dict = PyDict::new(py);
 
         // 转换XYZ
-        let xyz_array = tensor::tensor2_to_vec(self.xyz_ref());
-        let n = xyz_array.len();
-        let mut xyz_flat: Vec<f32> = Vec::with_capacity(n * 3);
-        for row in &xyz_array {
-            xyz_flat.extend_from_slice(row);
-        }
-
-        let xyz_nd = Array2::from_shape_vec((n, 3), xyz_flat)
-            .map_err(|e| format!("XYZ shape error: {}", e))?;
-        let xyz_np = IntoPyArray::into_pyarray(xyz_nd, py);
+        let xyz_np = self.xyz_to_pyarray(py)?;
         dict.set_item("xyz", xyz_np)
             .map_err(|e: PyErr| e.to_string())?;
 
@@ -57,21 +50,30 @@ pub fn to_numpy<'py>(&self, py: Python<'py>) -> Result<Py<PyAny>> {
             dict.set_item("b", b_np).map_err(|e: PyErr| e.to_string())?;
         }
 
-        // 转换自定义属性
+        // 转换自定义属性（保持 dtype）
         for (name, attr) in self.attributes_ref() {
-            let attr_vec = tensor::tensor1_to_vec(attr);
-            let attr_nd = Array1::from_vec(attr_vec);
-            let attr_np = IntoPyArray::into_pyarray(attr_nd, py);
-            dict.set_item(name.as_str(), attr_np)
+            dict.set_item(name.as_str(), attribute_to_pyarray(py, attr)?)
                 .map_err(|e: PyErr| e.to_string())?;
         }
 
         Ok(dict.into())
     }
 
-    /// 从 PyAny（numpy array）读取 XYZ 数据，支持多种 dtype
-    /// 返回创建的 HighPerformancePointCloud
+    /// XYZ 转 numpy 数组：始终为 float64 全局坐标（局部坐标 + 原点），只复制一次
+    pub fn xyz_to_pyarray(&self, py: Python<'_>) -> Result<Py<PyAny>> {
+        let n = self.point_count();
+        let flat = self.global_xyz_flat();
+        let nd =
+            Array2::from_shape_vec((n, 3), flat).map_err(|e| format!("XYZ shape error: {}", e))?;
+        Ok(IntoPyArray::into_pyarray(nd, py).into())
+    }
+
+    /// 从 PyAny（numpy array）读取 XYZ 数据，支持 float32/float64
+    /// float64 坐标按包围盒自动设置原点，保持大坐标精度
     pub fn from_xyz_array(xyz_obj: &Bound<'_, pyo3::PyAny>) -> Result<Self> {
+        if let Some(points) = read_global_xyz_from_pyany(xyz_obj)? {
+            return Self::from_global_points(&points, None);
+        }
         let xyz = read_xyz_from_pyany(xyz_obj)?;
         Self::from_tensor_xyz(xyz)
     }
@@ -84,8 +86,7 @@ pub fn from_numpy(_py: Python, data: &Bound<'_, PyDict>) -> Result<Self> {
             .map_err(|_| "获取xyz失败".to_string())?
             .ok_or("xyz字段缺失".to_string())?;
 
-        let xyz = read_xyz_from_pyany(&xyz_obj)?;
-        let mut result = Self::from_tensor_xyz(xyz)?;
+        let mut result = Self::from_xyz_array(&xyz_obj)?;
 
         // 可选：intensity（支持多种 dtype）
         if let Ok(Some(intensity_obj)) = data.get_item("intensity") {
@@ -165,18 +166,127 @@ pub fn set_rgb_from_arrays(
         *b_mut = Some(b);
         Ok(())
     }
+
+    /// 从深度图 numpy 数组反投影创建点云（支持 uint16/float32，形状[H,W]）
+    pub fn from_depth_array(
+        depth_obj: &Bound<'_, pyo3::PyAny>,
+        intrinsics: PinholeIntrinsics,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<&Extrinsic>,
+    ) -> Result<Self> {
+        let depth = read_depth_from_pyany(depth_obj)?;
+        Self::from_depth_image(depth, intrinsics, depth_scale, depth_trunc, extrinsic)
+    }
+
+    /// 从 RGB-D numpy 数组反投影创建点云
+    /// color: uint8 数组，形状[H,W,3]；depth: uint16/float32 数组，形状[H,W]
+    pub fn from_rgbd_arrays(
+        color_obj: &Bound<'_, pyo3::PyAny>,
+        depth_obj: &Bound<'_, pyo3::PyAny>,
+        intrinsics: PinholeIntrinsics,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<&Extrinsic>,
+    ) -> Result<Self> {
+        let (color, _, _) = read_color_from_pyany(color_obj)?;
+        let depth = read_depth_from_pyany(depth_obj)?;
+        Self::from_rgbd(
+            color,
+            depth,
+            intrinsics,
+            depth_scale,
+            depth_trunc,
+            extrinsic,
+        )
+    }
+
+    /// 从相机图像 numpy 数组（uint8，形状[H,W,3]）双线性采样着色
+    /// 返回每个点的可见性
+    pub fn colorize_from_array(
+        &mut self,
+        image_obj: &Bound<'_, pyo3::PyAny>,
+        intrinsics: PinholeIntrinsics,
+        extrinsic: Option<&Extrinsic>,
+        distortion: Option<&Distortion>,
+    ) -> Result<Vec<bool>> {
+        let (image, height, width) = read_color_from_pyany(image_obj)?;
+        self.colorize_from_image(image, width, height, intrinsics, extrinsic, distortion)
+    }
+}
+
+// ============ 辅助函数：Tensor 转 numpy ============
+
+/// Tensor2 -> numpy float32 数组（形状[rows, cols]）
+pub fn tensor2_to_pyarray(py: Python<'_>, t: &Tensor2) -> Result<Py<PyAny>> {
+    let [rows, cols] = t.dims();
+    let flat = tensor::tensor2_to_flat(t);
+    let nd =
+        Array2::from_shape_vec((rows, cols), flat).map_err(|e| format!("shape error: {}", e))?;
+    Ok(IntoPyArray::into_pyarray(nd, py).into())
+}
+
+/// Tensor3 -> numpy float32 数组（形状[d0, d1, d2]）
+pub fn tensor3_to_pyarray(py: Python<'_>, t: &Tensor3) -> Result<Py<PyAny>> {
+    let [d0, d1, d2] = t.dims();
+    let flat = tensor::tensor1_to_vec(&t.clone().reshape([d0 * d1 * d2]));
+    let nd =
+        Array3::from_shape_vec((d0, d1, d2), flat).map_err(|e| format!("shape error: {}", e))?;
+    Ok(IntoPyArray::into_pyarray(nd, py).into())
+}
+
+/// 自定义属性 -> numpy 数组，保持 dtype（多维属性为 float32 [M,D]）
+pub fn attribute_to_pyarray(py: Python<'_>, data: &AttributeData) -> Result<Py<PyAny>> {
+    match_attribute!(
+        data,
+        t => Ok(IntoPyArray::into_pyarray(tensor::tensor1_to_vec(t), py).into()),
+        m => tensor2_to_pyarray(py, m),
+        values => Ok(IntoPyArray::into_pyarray(values.clone(), py).into())
+    )
+}
+
+/// 从 PyAny 读取自定义属性数组，1D 支持 bool/整数/float32/float64 并保持 dtype，
+/// 2D 支持 float32 [M,D] 多维属性；dtype 或维数不受支持时返回 None
+pub fn read_attribute_array(obj: &Bound<'_, pyo3::PyAny>) -> Option<AttributeData> {
+    if let Ok(arr) = obj.cast::<PyArray2<f32>>() {
+        let shape = arr.shape();
+        let (rows, cols) = (shape[0], shape[1]);
+        if cols == 0 {
+            return None;
+        }
+        let flat: Vec<f32> = arr.readonly().as_array().iter().copied().collect();
+        return tensor::tensor2_from_slice(&flat, rows, cols)
+            .ok()
+            .map(AttributeData::Vector);
+    }
+    fn read<T: AttributeValue + Element>(obj: &Bound<'_, pyo3::PyAny>) -> Option<AttributeData> {
+        let arr = obj.cast::<PyArray1<T>>().ok()?;
+        let values = arr.readonly().as_array().iter().copied().collect();
+        Some(AttributeData::from_vec::<T>(values))
+    }
+    read::<f32>(obj)
+        .or_else(|| read::<f64>(obj))
+        .or_else(|| read::<i8>(obj))
+        .or_else(|| read::<i16>(obj))
+        .or_else(|| read::<i32>(obj))
+        .or_else(|| read::<i64>(obj))
+        .or_else(|| read::<u8>(obj))
+        .or_else(|| read::<u16>(obj))
+        .or_else(|| read::<u32>(obj))
+        .or_else(|| read::<u64>(obj))
+        .or_else(|| read::<bool>(obj))
 }
 
 // ============ 内部辅助函数：从 PyAny 读取数据 ============
 
-use crate::utils::tensor::{Tensor1, Tensor2};
-use numpy::{PyArray1, PyArray2};
+use crate::utils::tensor::{Tensor1, Tensor2, Tensor3};
+use numpy::{PyArray1, PyArray2, PyArray3};
 
 /// 从 PyAny 读取 2D XYZ 数组，仅支持 f32 dtype
 fn read_xyz_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Tensor2> {
     let arr = obj
         .cast::<PyArray2<f32>>()
-        .map_err(|_| "xyz必须是dtype=float32的2D numpy数组，请使用 arr.astype(np.float32) 转换")?;
+        .map_err(|_| "xyz必须是dtype=float32或float64的2D numpy数组")?;
 
     let shape = arr.shape();
     if shape[1] != 3 {
@@ -191,6 +301,36 @@ fn read_xyz_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Tensor2> {
     tensor::tensor2_from_slice(slice, shape[0], shape[1])
 }
 
+/// 从 PyAny 读取 float64 的 [M,3] 全局坐标，dtype 不是 float64 时返回 None
+fn read_global_xyz_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Option<Vec<[f64; 3]>>> {
+    let Ok(arr) = obj.cast::<PyArray2<f64>>() else {
+        return Ok(None);
+    };
+    let shape = arr.shape();
+    if shape[1] != 3 {
+        return Err(format!("XYZ必须是[N,3]的形状，实际为[{},{}]", shape[0], shape[1]).into());
+    }
+    let readonly = arr.readonly();
+    let slice = readonly
+        .as_slice()
+        .map_err(|_| "无法读取xyz数据，数组可能不连续")?;
+    Ok(Some(
+        slice.chunks_exact(3).map(|p| [p[0], p[1], p[2]]).collect(),
+    ))
+}
+
+/// 从 PyAny 读取 [M,3] 全局坐标数组（用于查询等非点云输入），支持 float32/float64
+pub fn read_points_array(obj: &Bound<'_, pyo3::PyAny>) -> Result<Vec<[f64; 3]>> {
+    if let Some(points) = read_global_xyz_from_pyany(obj)? {
+        return Ok(points);
+    }
+    let xyz = read_xyz_from_pyany(obj)?;
+    Ok(tensor::tensor2_to_points(&xyz)
+        .into_iter()
+        .map(|p| p.map(f64::from))
+        .collect())
+}
+
 /// 从 PyAny 读取 1D 数组，仅支持 f32 dtype
 fn read_1d_array_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Tensor1> {
     let arr = obj
@@ -203,3 +343,51 @@ fn read_1d_array_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Tensor1> {
         .map_err(|_| "无法读取数据，数组可能不连续")?;
     Ok(tensor::tensor1_from_slice(slice))
 }
+
+/// 从 PyAny 读取深度图，支持 uint16/float32 dtype，形状[H,W]
+fn read_depth_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<Tensor2> {
+    if let Ok(arr) = obj.cast::<PyArray2<f32>>() {
+        let shape = arr.shape();
+        let readonly = arr.readonly();
+        let slice = readonly
+            .as_slice()
+            .map_err(|_| "无法读取深度图数据，数组可能不连续")?;
+        return tensor::tensor2_from_slice(slice, shape[0], shape[1]);
+    }
+
+    let arr = obj
+        .cast::<PyArray2<u16>>()
+        .map_err(|_| "depth必须是dtype=uint16或float32的2D numpy数组")?;
+    let shape = arr.shape();
+    let readonly = arr.readonly();
+    let slice = readonly
+        .as_slice()
+        .map_err(|_| "无法读取深度图数据，数组可能不连续")?;
+    let data: Vec<f32> = slice.iter().map(|&v| v as f32).collect();
+    tensor::tensor2_from_slice(&data, shape[0], shape[1])
+}
+
+/// 从 PyAny 读取颜色图，仅支持 uint8 dtype，形状[H,W,3]
+/// 返回 ([H*W,3] 张量, H, W)
+fn read_color_from_pyany(obj: &Bound<'_, pyo3::PyAny>) -> Result<(Tensor2, usize, usize)> {
+    let arr = obj
+        .cast::<PyArray3<u8>>()
+        .map_err(|_| "color必须是dtype=uint8的3D numpy数组")?;
+
+    let shape = arr.shape();
+    if shape[2] != 3 {
+        return Err(format!(
+            "color必须是[H,W,3]的形状，实际为[{},{},{}]",
+            shape[0], shape[1], shape[2]
+        )
+        .into());
+    }
+
+    let readonly = arr.readonly();
+    let slice = readonly
+        .as_slice()
+        .map_err(|_| "无法读取颜色数据，数组可能不连续")?;
+    let data: Vec<f32> = slice.iter().map(|&v| v as f32).collect();
+    let color = tensor::tensor2_from_slice(&data, shape[0] * shape[1], 3)?;
+    Ok((color, shape[0], shape[1]))
+}
@@ -0,0 +1,346 @@
+/// 分区 Parquet 数据集：按 XY 网格切分瓦片，Hive 目录分区，瓦片内按空间填充曲线排序
+use crate::io::table::{self, TableColumnNames};
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+use polars::prelude::ParquetWriter;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+
+/// 数据集索引文件名（记录瓦片路径、点数与包围盒，供扫描时裁剪瓦片）
+pub const DATASET_INDEX: &str = "_dataset.json";
+/// Hive 分区目录对应的列名
+pub const PARTITION_COLUMNS: [&str; 3] = ["tile_x", "tile_y", "tile"];
+/// 瓦片内排序键每轴的量化位数
+const SORT_BITS: u32 = 16;
+/// 瓦片编号曲线每轴的位数
+const TILE_BITS: u32 = 32;
+
+/// 瓦片分区方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileScheme {
+    /// 按网格行列分区：tile_x=<i>/tile_y=<j>，瓦片内按 Morton 序排列
+    Grid,
+    /// 按瓦片行列的 Morton 键分区：tile=<key>
+    Morton,
+    /// 按瓦片行列的 Hilbert 键分区：tile=<key>，瓦片内按 Hilbert 序排列
+    Hilbert,
+}
+
+impl TileScheme {
+    /// 解析分区方式名称（grid/morton/hilbert）
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "grid" => Ok(TileScheme::Grid),
+            "morton" => Ok(TileScheme::Morton),
+            "hilbert" => Ok(TileScheme::Hilbert),
+            _ => Err(PointCloudError::InvalidParameter(format!(
+                "不支持的分区方式: {}（可选 grid/morton/hilbert）",
+                name
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            TileScheme::Grid => "grid",
+            TileScheme::Morton => "morton",
+            TileScheme::Hilbert => "hilbert",
+        }
+    }
+
+    /// 二维空间填充曲线键
+    fn curve_key(self, order: u32, x: u32, y: u32) -> u64 {
+        match self {
+            TileScheme::Hilbert => hilbert_2d(order, x, y),
+            TileScheme::Grid | TileScheme::Morton => morton_2d(x, y),
+        }
+    }
+}
+
+/// 分区数据集写出参数
+#[derive(Clone, Debug)]
+pub struct DatasetOptions {
+    /// 瓦片边长（全局坐标单位）
+    pub tile_size: f64,
+    pub scheme: TileScheme,
+    /// 每个行组的行数，None 时使用 polars 默认值
+    pub row_group_size: Option<usize>,
+    /// 根目录非空时是否先删除已有的索引与瓦片分区，false 时报错
+    pub overwrite: bool,
+}
+
+impl HighPerformancePointCloud {
+    /// 写出分区 Parquet 数据集
+    /// 每个瓦片一个 Parquet 文件，行按空间填充曲线排序使行组的 min/max 统计紧凑，
+    /// 根目录的 _dataset.json 记录每个瓦片的路径、点数与包围盒，坐标系同时写入每个瓦片的元数据
+    pub fn to_parquet_dataset(
+        &self,
+        root: &str,
+        options: &DatasetOptions,
+        columns: TableColumnNames,
+    ) -> Result<()> {
+        if !options.tile_size.is_finite() || options.tile_size <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "tile_size必须大于0".to_string(),
+            ));
+        }
+        let root = Path::new(root);
+        clear_dataset(root, options.overwrite)?;
+        fs::create_dir_all(root).map_err(PointCloudError::IoError)?;
+
+        let points = self.global_points();
+        let tile_size = options.tile_size;
+        let tile_of = |p: &[f64; 3]| {
+            [
+                (p[0] / tile_size).floor() as i64,
+                (p[1] / tile_size).floor() as i64,
+            ]
+        };
+        let tile_origin = points
+            .iter()
+            .map(tile_of)
+            .fold([i64::MAX; 2], |min, t| [min[0].min(t[0]), min[1].min(t[1])]);
+
+        // 分区键：网格按 (tile_x, tile_y)，曲线分区按相对瓦片行列的曲线键
+        let mut tiles: BTreeMap<(u64, i64, i64), Vec<usize>> = BTreeMap::new();
+        for (idx, p) in points.iter().enumerate() {
+            let [tx, ty] = tile_of(p);
+            let key = match options.scheme {
+                TileScheme::Grid => 0,
+                scheme => scheme.curve_key(
+                    TILE_BITS,
+                    (tx - tile_origin[0]) as u32,
+                    (ty - tile_origin[1]) as u32,
+                ),
+            };
+            tiles.entry((key, tx, ty)).or_default().push(idx);
+        }
+
+        // 瓦片内按量化坐标的曲线键排序
+        let cells = f64::from((1u32 << SORT_BITS) - 1);
+        let cell_of =
+            |v: f64, tile: i64| ((v / tile_size - tile as f64) * cells).clamp(0.0, cells) as u32;
+
+        let mut entries = Vec::with_capacity(tiles.len());
+        for ((key, tx, ty), mut indices) in tiles {
+            indices.sort_by_key(|&idx| {
+                let p = &points[idx];
+                options
+                    .scheme
+                    .curve_key(SORT_BITS, cell_of(p[0], tx), cell_of(p[1], ty))
+            });
+
+            let partition = match options.scheme {
+                TileScheme::Grid => format!("tile_x={}/tile_y={}", tx, ty),
+                _ => format!("tile={}", key),
+            };
+            let dir = root.join(&partition);
+            fs::create_dir_all(&dir).map_err(PointCloudError::IoError)?;
+            let tile = self.select_points(tensor::indices_to_tensor(&indices));
+            let mut df = table::to_dataframe(&tile, columns.clone(), false)?;
+            let file =
+                File::create(dir.join("part-0.parquet")).map_err(PointCloudError::IoError)?;
+            // 默认写出各行组的 min/max 统计，排序后统计范围紧凑，便于谓词下推跳过行组
+            ParquetWriter::new(file)
+                .with_row_group_size(options.row_group_size)
+                .with_key_value_metadata(table::crs_metadata(self))
+                .finish(&mut df)
+                .map_err(|e| PointCloudError::ParseError(e.to_string()))?;
+
+            let (min, max) = bounds(indices.iter().map(|&idx| &points[idx]));
+            entries.push(json!({
+                "path": format!("{}/part-0.parquet", partition),
+                "tile_x": tx,
+                "tile_y": ty,
+                "key": key,
+                "points": indices.len(),
+                "min": min,
+                "max": max,
+            }));
+        }
+
+        let (min, max) = bounds(points.iter());
+        let index = json!({
+            "tile_size": tile_size,
+            "scheme": options.scheme.name(),
+            "tile_origin": tile_origin,
+            "points": self.point_count(),
+            "crs": self.crs().map(ToString::to_string),
+            "bounds": { "min": min, "max": max },
+            "tiles": entries,
+        });
+        let text = serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?;
+        fs::write(root.join(DATASET_INDEX), text).map_err(PointCloudError::IoError)?;
+        Ok(())
+    }
+}
+
+/// 检查数据集根目录：非空时按 overwrite 删除旧的索引与瓦片分区目录，否则报错
+/// 只删除 _dataset.json 与 tile_x=/tile=/… 分区目录，其他文件保留
+fn clear_dataset(root: &Path, overwrite: bool) -> Result<()> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Ok(());
+    };
+    let entries = entries
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(PointCloudError::IoError)?;
+    if entries.is_empty() {
+        return Ok(());
+    }
+    if !overwrite {
+        return Err(PointCloudError::InvalidParameter(format!(
+            "数据集目录{}非空，设置overwrite=True覆盖",
+            root.display()
+        )));
+    }
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = entry.path();
+        if name == DATASET_INDEX {
+            fs::remove_file(&path).map_err(PointCloudError::IoError)?;
+        } else if path.is_dir()
+            && PARTITION_COLUMNS
+                .iter()
+                .any(|column| name.starts_with(&format!("{}=", column)))
+        {
+            fs::remove_dir_all(&path).map_err(PointCloudError::IoError)?;
+        }
+    }
+    Ok(())
+}
+
+/// 读取数据集索引，返回与包围盒相交的瓦片文件路径及坐标系
+/// bbox 同 ParquetScan::bbox（4 个值只比较 XY），其他长度时不裁剪
+pub fn dataset_tiles(root: &Path, bbox: Option<&[f64]>) -> Result<(Vec<String>, Option<String>)> {
+    let text = fs::read_to_string(root.join(DATASET_INDEX)).map_err(PointCloudError::IoError)?;
+    let index: Value =
+        serde_json::from_str(&text).map_err(|e| PointCloudError::ParseError(e.to_string()))?;
+    let tiles = index
+        .get("tiles")
+        .and_then(Value::as_array)
+        .ok_or_else(|| PointCloudError::ParseError(format!("{}缺少tiles", DATASET_INDEX)))?;
+
+    let coords = |tile: &Value, key: &str| -> Option<Vec<f64>> {
+        tile.get(key)?
+            .as_array()?
+            .iter()
+            .map(Value::as_f64)
+            .collect()
+    };
+    let overlaps = |tile: &Value| {
+        let dims = match bbox.map(<[f64]>::len) {
+            Some(4) => 2,
+            Some(6) => 3,
+            _ => return true,
+        };
+        let bbox = bbox.unwrap_or_default();
+        match (coords(tile, "min"), coords(tile, "max")) {
+            (Some(min), Some(max)) => {
+                (0..dims).all(|k| min[k] <= bbox[dims + k] && max[k] >= bbox[k])
+            }
+            _ => true,
+        }
+    };
+
+    let mut paths = Vec::new();
+    for tile in tiles.iter().filter(|tile| overlaps(tile)) {
+        let path = tile.get("path").and_then(Value::as_str).ok_or_else(|| {
+            PointCloudError::ParseError(format!("{}的瓦片缺少path", DATASET_INDEX))
+        })?;
+        paths.push(root.join(path).to_string_lossy().into_owned());
+    }
+    let crs = index.get("crs").and_then(Value::as_str).map(str::to_string);
+    Ok((paths, crs))
+}
+
+/// 点集的三维包围盒
+fn bounds<'a>(points: impl Iterator<Item = &'a [f64; 3]>) -> ([f64; 3], [f64; 3]) {
+    points.fold(
+        ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]),
+        |(min, max), p| {
+            (
+                std::array::from_fn(|k| min[k].min(p[k])),
+                std::array::from_fn(|k| max[k].max(p[k])),
+            )
+        },
+    )
+}
+
+/// 将 32 位整数的各位间隔展开到 64 位的偶数位
+fn spread_bits(v: u32) -> u64 {
+    let mut x = u64::from(v);
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// 二维 Morton（Z 序）键：x 占偶数位，y 占奇数位
+pub fn morton_2d(x: u32, y: u32) -> u64 {
+    spread_bits(x) | (spread_bits(y) << 1)
+}
+
+/// 二维 Hilbert 键，网格边长为 2^order（order ≤ 32）
+pub fn hilbert_2d(order: u32, x: u32, y: u32) -> u64 {
+    let n = 1u64 << order;
+    let (mut x, mut y) = (u64::from(x), u64::from(y));
+    let mut d = 0u64;
+    let mut s = n >> 1;
+    while s > 0 {
+        let rx = u64::from((x & s) > 0);
+        let ry = u64::from((y & s) > 0);
+        d += s * s * ((3 * rx) ^ ry);
+        // 旋转象限
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morton_2d() {
+        assert_eq!(morton_2d(0, 0), 0);
+        assert_eq!(morton_2d(1, 0), 1);
+        assert_eq!(morton_2d(0, 1), 2);
+        assert_eq!(morton_2d(1, 1), 3);
+        assert_eq!(morton_2d(2, 0), 4);
+        assert_eq!(morton_2d(u32::MAX, u32::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_hilbert_2d() {
+        let order1: Vec<u64> = [(0, 0), (0, 1), (1, 1), (1, 0)]
+            .iter()
+            .map(|&(x, y)| hilbert_2d(1, x, y))
+            .collect();
+        assert_eq!(order1, vec![0, 1, 2, 3]);
+
+        // 相邻键在网格上相邻
+        let mut cells: Vec<(u64, u32, u32)> = (0..8)
+            .flat_map(|x| (0..8).map(move |y| (hilbert_2d(3, x, y), x, y)))
+            .collect();
+        cells.sort();
+        for pair in cells.windows(2) {
+            assert_eq!(pair[1].0, pair[0].0 + 1);
+            assert_eq!(
+                pair[0].1.abs_diff(pair[1].1) + pair[0].2.abs_diff(pair[1].2),
+                1
+            );
+        }
+    }
+}
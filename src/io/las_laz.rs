@@ -1,118 +1,1435 @@
 /// LAZ/LAS格式读写/删，处理压缩/解压缩、格式兼容
+use crate::io::las_meta::{self, ExtraBytes, LasMetadata};
+use crate::point_cloud::attribute_data::{AttributeData, AttributeType};
 use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::crs::Crs;
+use crate::point_cloud::origin;
 use crate::traits::{PointCloudCore, PointCloudProperties};
 use crate::utils::error::{PointCloudError, Result};
-use las::point::Format;
-use las::{Builder, Color, Point, Reader, Writer};
-use std::fs;
+use crate::utils::tensor;
+use las::point::{Classification, Format, ScanDirection};
+use las::{Builder, Color, Header, Point, Reader, Transform, Vector, Writer};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
 
 impl HighPerformancePointCloud {
     /// 从LAS/LAZ文件读取（自动检测压缩）
     pub fn from_las_laz(path: &str) -> Result<Self> {
         let mut reader = Reader::from_path(path).map_err(|e| format!("无法读取LAS文件: {}", e))?;
 
-        let mut xyz = Vec::new();
-        let mut intensity = Vec::new();
-        let mut rgb_r = Vec::new();
-        let mut rgb_g = Vec::new();
-        let mut rgb_b = Vec::new();
-        let has_color = reader.header().point_format().has_color;
+        let schema = LasSchema::from_header(reader.header())?;
+        let capacity = reader.header().number_of_points() as usize;
+        let mut buffer = LasPointBuffer::new(&schema, capacity);
 
         for point_result in reader.points() {
             let point = point_result.map_err(|_| "读取LAS点失败".to_string())?;
+            buffer.push(&point);
+        }
+
+        buffer.into_cloud()
+    }
 
-            xyz.push(vec![point.x as f32, point.y as f32, point.z as f32]);
+    /// 写入LAS文件
+    /// compress: 是否压缩为LAZ格式
+    pub fn to_las(&self, path: &str, compress: bool) -> Result<()> {
+        self.to_las_with_options(
+            path,
+            LasWriteOptions {
+                compress,
+                ..Default::default()
+            },
+        )
+    }
 
-            // LAS always has intensity
-            intensity.push(point.intensity as f32 / 65535.0);
+    /// 按指定参数写入LAS文件
+    /// 未指定的版本、缩放与偏移沿用读取时保留的头部，VLR/EVLR 原样写回
+    pub fn to_las_with_options(&self, path: &str, options: LasWriteOptions) -> Result<()> {
+        if self.point_count() == 0 {
+            return Err("点云为空".into());
+        }
 
-            if has_color {
-                if let Some(color) = point.color {
-                    rgb_r.push((color.red >> 8) as u8);
-                    rgb_g.push((color.green >> 8) as u8);
-                    rgb_b.push((color.blue >> 8) as u8);
+        let mut writer = LasStreamWriter::new(path, options)?;
+        writer.write_chunk(self)?;
+        writer.close()
+    }
+
+    /// 按块读取LAS/LAZ文件，每块最多 chunk_size 个点
+    pub fn iter_las(path: &str, chunk_size: usize) -> Result<LasChunkReader> {
+        LasChunkReader::new(path, chunk_size)
+    }
+
+    /// 按点格式转换为LAS点记录
+    /// 标准字段从同名属性写回，格式要求但点云缺失的字段（颜色、GPS时间、NIR）填 0
+    /// 其余自定义属性按 Extra Bytes 描述符编码为附加字节
+    /// extra_bytes: 写入附加字节的自定义属性描述符，缺失的属性写为无效值
+    fn to_las_points(&self, format: &Format, extra_bytes: &[ExtraBytes]) -> Result<Vec<Point>> {
+        let n = self.point_count();
+        let xyz = self.global_points();
+        let intensity = self.get_intensity();
+        let rgb = self.get_rgb();
+        let gps_time = self.gps_time_ref();
+        let fields: Vec<(LasField, Vec<f32>)> = LasField::ALL
+            .into_iter()
+            .filter(|field| field.applies_to(format))
+            .filter_map(|field| {
+                self.get_attribute(field.name())
+                    .map(|values| (field, values))
+            })
+            .collect();
+        let scalars = self.scalar_attributes();
+        let extra: Vec<(usize, Vec<u8>)> = extra_bytes
+            .iter()
+            .map(|descriptor| {
+                let data = scalars.get(&descriptor.name);
+                (descriptor.size(), descriptor.encode_column(data, n))
+            })
+            .collect();
+
+        xyz.iter()
+            .enumerate()
+            .map(|(idx, point_xyz)| {
+                let mut point = Point {
+                    x: point_xyz[0],
+                    y: point_xyz[1],
+                    z: point_xyz[2],
+                    ..Default::default()
+                };
+
+                if let Some(intensity_vec) = &intensity {
+                    let raw = (intensity_vec[idx] * 65535.0).clamp(0.0, 65535.0);
+                    point.intensity = raw as u16;
                 }
+
+                if let Some((ref r_vec, ref g_vec, ref b_vec)) = rgb {
+                    point.color = Some(Color {
+                        red: (r_vec[idx] as u16) << 8,
+                        green: (g_vec[idx] as u16) << 8,
+                        blue: (b_vec[idx] as u16) << 8,
+                    });
+                }
+
+                if format.has_color && point.color.is_none() {
+                    point.color = Some(Color {
+                        red: 0,
+                        green: 0,
+                        blue: 0,
+                    });
+                }
+                if format.has_gps_time {
+                    point.gps_time = Some(gps_time.map_or(0.0, |times| times[idx]));
+                }
+                if format.has_nir {
+                    point.nir = Some(0);
+                }
+                for (field, values) in &fields {
+                    field.write(&mut point, values[idx])?;
+                }
+                for (size, bytes) in &extra {
+                    point
+                        .extra_bytes
+                        .extend_from_slice(&bytes[idx * size..(idx + 1) * size]);
+                }
+
+                Ok(point)
+            })
+            .collect()
+    }
+
+    /// 自定义属性（非标准字段）对应的 Extra Bytes 描述符
+    /// 按 LAS 元数据中的顺序排列，未声明的属性按名称排序追加，类型与属性一致
+    /// 多维属性拆分为 name_0..name_{D-1} 的 float32 维度
+    fn las_extra_bytes(&self) -> Vec<ExtraBytes> {
+        let scalars = self.scalar_attributes();
+        let mut names: Vec<String> = scalars
+            .keys()
+            .filter(|name| !LasField::is_standard(name))
+            .cloned()
+            .collect();
+        names.sort();
+
+        let mut descriptors: Vec<ExtraBytes> = self
+            .las_metadata()
+            .map(|meta| {
+                meta.extra_bytes
+                    .iter()
+                    .filter(|d| names.contains(&d.name))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        for name in names {
+            if !descriptors.iter().any(|d| d.name == name) {
+                let dtype = scalars[&name].dtype();
+                descriptors.push(ExtraBytes::for_attribute(&name, dtype));
+            }
+        }
+        descriptors
+    }
+
+    /// 删除LAS/LAZ文件
+    pub fn delete_file(path: &str) -> Result<()> {
+        if fs::metadata(path).is_err() {
+            return Err(format!("文件不存在: {}", path).into());
+        }
+
+        fs::remove_file(path).map_err(PointCloudError::IoError)?;
+        Ok(())
+    }
+}
+
+/// LAS 标准点字段，读取时映射为同名自定义属性（整数字段为 uint8/uint16，标志位为 bool）
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LasField {
+    Classification,
+    ReturnNumber,
+    NumberOfReturns,
+    ScanAngle,
+    UserData,
+    PointSourceId,
+    ScanDirection,
+    EdgeOfFlightLine,
+    Synthetic,
+    KeyPoint,
+    Withheld,
+    Overlap,
+    ScannerChannel,
+    Nir,
+}
+
+impl LasField {
+    /// 全部标准字段
+    pub const ALL: [LasField; 14] = [
+        LasField::Classification,
+        LasField::ReturnNumber,
+        LasField::NumberOfReturns,
+        LasField::ScanAngle,
+        LasField::UserData,
+        LasField::PointSourceId,
+        LasField::ScanDirection,
+        LasField::EdgeOfFlightLine,
+        LasField::Synthetic,
+        LasField::KeyPoint,
+        LasField::Withheld,
+        LasField::Overlap,
+        LasField::ScannerChannel,
+        LasField::Nir,
+    ];
+
+    /// 对应的属性名
+    pub fn name(self) -> &'static str {
+        match self {
+            LasField::Classification => "classification",
+            LasField::ReturnNumber => "return_number",
+            LasField::NumberOfReturns => "number_of_returns",
+            LasField::ScanAngle => "scan_angle",
+            LasField::UserData => "user_data",
+            LasField::PointSourceId => "point_source_id",
+            LasField::ScanDirection => "scan_direction",
+            LasField::EdgeOfFlightLine => "edge_of_flight_line",
+            LasField::Synthetic => "synthetic",
+            LasField::KeyPoint => "key_point",
+            LasField::Withheld => "withheld",
+            LasField::Overlap => "overlap",
+            LasField::ScannerChannel => "scanner_channel",
+            LasField::Nir => "nir",
+        }
+    }
+
+    /// 属性名是否为标准字段
+    pub fn is_standard(name: &str) -> bool {
+        LasField::ALL.iter().any(|field| field.name() == name)
+    }
+
+    /// 点格式是否包含该字段（scanner_channel 仅扩展格式 6-10，nir 仅格式 8/10）
+    fn applies_to(self, format: &Format) -> bool {
+        match self {
+            LasField::ScannerChannel => format.is_extended,
+            LasField::Nir => format.has_nir,
+            _ => true,
+        }
+    }
+
+    /// 读取后的属性类型（标志位为 bool）
+    fn dtype(self) -> AttributeType {
+        match self {
+            LasField::Classification
+            | LasField::ReturnNumber
+            | LasField::NumberOfReturns
+            | LasField::UserData
+            | LasField::ScannerChannel => AttributeType::U8,
+            LasField::PointSourceId | LasField::Nir => AttributeType::U16,
+            LasField::ScanAngle => AttributeType::F32,
+            LasField::ScanDirection
+            | LasField::EdgeOfFlightLine
+            | LasField::Synthetic
+            | LasField::KeyPoint
+            | LasField::Withheld
+            | LasField::Overlap => AttributeType::Bool,
+        }
+    }
+
+    fn read(self, point: &Point) -> f32 {
+        match self {
+            LasField::Classification => u8::from(point.classification) as f32,
+            LasField::ReturnNumber => point.return_number as f32,
+            LasField::NumberOfReturns => point.number_of_returns as f32,
+            LasField::ScanAngle => point.scan_angle,
+            LasField::UserData => point.user_data as f32,
+            LasField::PointSourceId => point.point_source_id as f32,
+            LasField::ScanDirection => {
+                u8::from(point.scan_direction == ScanDirection::LeftToRight) as f32
+            }
+            LasField::EdgeOfFlightLine => u8::from(point.is_edge_of_flight_line) as f32,
+            LasField::Synthetic => u8::from(point.is_synthetic) as f32,
+            LasField::KeyPoint => u8::from(point.is_key_point) as f32,
+            LasField::Withheld => u8::from(point.is_withheld) as f32,
+            LasField::Overlap => u8::from(point.is_overlap) as f32,
+            LasField::ScannerChannel => point.scanner_channel as f32,
+            LasField::Nir => point.nir.unwrap_or(0) as f32,
+        }
+    }
+
+    fn write(self, point: &mut Point, value: f32) -> Result<()> {
+        match self {
+            LasField::Classification => {
+                point.classification =
+                    Classification::new(value as u8).map_err(|e| e.to_string())?;
+            }
+            LasField::ReturnNumber => point.return_number = value as u8,
+            LasField::NumberOfReturns => point.number_of_returns = value as u8,
+            LasField::ScanAngle => point.scan_angle = value,
+            LasField::UserData => point.user_data = value as u8,
+            LasField::PointSourceId => point.point_source_id = value as u16,
+            LasField::ScanDirection => {
+                point.scan_direction = if value != 0.0 {
+                    ScanDirection::LeftToRight
+                } else {
+                    ScanDirection::RightToLeft
+                };
+            }
+            LasField::EdgeOfFlightLine => point.is_edge_of_flight_line = value != 0.0,
+            LasField::Synthetic => point.is_synthetic = value != 0.0,
+            LasField::KeyPoint => point.is_key_point = value != 0.0,
+            LasField::Withheld => point.is_withheld = value != 0.0,
+            LasField::Overlap => point.is_overlap = value != 0.0,
+            LasField::ScannerChannel => point.scanner_channel = value as u8,
+            LasField::Nir => point.nir = Some(value as u16),
+        }
+        Ok(())
+    }
+}
+
+/// 点记录格式需求：GPS时间、颜色、NIR 与扩展格式（6-10）
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct LasLayout {
+    gps_time: bool,
+    color: bool,
+    nir: bool,
+    extended: bool,
+}
+
+impl LasLayout {
+    fn from_format(format: &Format) -> Self {
+        Self {
+            gps_time: format.has_gps_time,
+            color: format.has_color,
+            nir: format.has_nir,
+            extended: format.is_extended,
+        }
+    }
+
+    /// 根据点云内容确定最小格式需求
+    /// 回波数超过 7、分类超过 31、扫描角超出 ±90°、存在扫描通道或重叠标志时需要扩展格式
+    fn of(cloud: &HighPerformancePointCloud) -> Self {
+        let max_of = |field: LasField| {
+            cloud.get_attribute(field.name()).map_or(0.0, |values| {
+                values.into_iter().fold(0.0f32, |max, v| max.max(v.abs()))
+            })
+        };
+        let extended = max_of(LasField::ReturnNumber) > 7.0
+            || max_of(LasField::NumberOfReturns) > 7.0
+            || max_of(LasField::Classification) > 31.0
+            || max_of(LasField::ScanAngle) > 90.0
+            || max_of(LasField::ScannerChannel) > 0.0
+            || max_of(LasField::Overlap) > 0.0;
+        // NIR 只存在于含颜色的格式 8/10，缺失的颜色写为 0
+        let nir = cloud.attributes_ref().contains_key(LasField::Nir.name());
+        Self {
+            gps_time: cloud.gps_time_ref().is_some(),
+            color: cloud.has_rgb() || nir,
+            nir,
+            extended,
+        }
+    }
+
+    /// 满足需求的最小点格式（不含波形，即 0/1/2/3/6/7/8）
+    fn format_id(&self) -> u8 {
+        if self.nir {
+            8
+        } else if self.extended {
+            if self.color {
+                7
+            } else {
+                6
+            }
+        } else {
+            match (self.gps_time, self.color) {
+                (false, false) => 0,
+                (true, false) => 1,
+                (false, true) => 2,
+                (true, true) => 3,
             }
         }
+    }
 
-        let mut result = Self::from_xyz(xyz)?;
+    /// 本格式无法无损容纳另一需求的原因（颜色必须一致），为空时可以容纳
+    fn conflicts(&self, other: &Self) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if self.color && !other.color {
+            reasons.push("块缺少格式要求的RGB");
+        }
+        if !self.color && other.color {
+            reasons.push("块含RGB而格式不含颜色");
+        }
+        if other.gps_time && !self.gps_time {
+            reasons.push("块含GPS时间而格式不含");
+        }
+        if other.nir && !self.nir {
+            reasons.push("块含NIR而格式不含");
+        }
+        if other.extended && !self.extended {
+            reasons.push("块需要扩展点格式（回波数>7、分类>31等）");
+        }
+        reasons
+    }
+}
 
-        if !intensity.is_empty() && intensity.len() == result.point_count() {
-            result.set_intensity(intensity)?;
+/// 从文件头解析出的读取方案：点格式、全部 Extra Bytes 描述符、需保留的元数据、局部坐标原点与坐标系
+struct LasSchema {
+    layout: LasLayout,
+    extra_bytes: Vec<ExtraBytes>,
+    metadata: LasMetadata,
+    origin: [f64; 3],
+    crs: Option<Crs>,
+}
+
+impl LasSchema {
+    fn from_header(header: &Header) -> Result<Self> {
+        let extra_bytes = las_meta::parse_extra_bytes(header.vlrs().iter().chain(header.evlrs()))?;
+        let scalar = extra_bytes
+            .iter()
+            .filter(|descriptor| descriptor.is_scalar())
+            .cloned()
+            .collect();
+        // 原点取头部偏移，偏移为 0 时按头部包围盒自动选择
+        let metadata = LasMetadata::from_header(header, scalar);
+        let origin = if metadata.offset != [0.0; 3] {
+            metadata.offset
+        } else {
+            let bounds = header.bounds();
+            origin::auto_origin(
+                [bounds.min.x, bounds.min.y, bounds.min.z],
+                [bounds.max.x, bounds.max.y, bounds.max.z],
+            )
+        };
+        Ok(Self {
+            layout: LasLayout::from_format(header.point_format()),
+            extra_bytes,
+            metadata,
+            origin,
+            crs: las_meta::crs_from_vlrs(header.vlrs().iter().chain(header.evlrs())),
+        })
+    }
+}
+
+/// LAS点累加器：按列收集坐标、强度、颜色、GPS时间与标准字段，避免 Vec<Vec<f32>> 中间结构
+struct LasPointBuffer {
+    xyz: Vec<f32>,
+    intensity: Vec<f32>,
+    rgb: Option<[Vec<u8>; 3]>,
+    gps_time: Option<Vec<f64>>,
+    fields: Vec<(LasField, Vec<f32>)>,
+    /// 标量 Extra Bytes 维度：描述符、在点记录附加字节中的偏移、原始字节
+    extra: Vec<(ExtraBytes, usize, Vec<u8>)>,
+    metadata: LasMetadata,
+    origin: [f64; 3],
+    crs: Option<Crs>,
+}
+
+impl LasPointBuffer {
+    fn new(schema: &LasSchema, capacity: usize) -> Self {
+        let layout = schema.layout;
+        let mut extra = Vec::new();
+        let mut offset = 0;
+        for descriptor in &schema.extra_bytes {
+            if descriptor.is_scalar() {
+                let raw = Vec::with_capacity(capacity * descriptor.size());
+                extra.push((descriptor.clone(), offset, raw));
+            }
+            offset += descriptor.size();
+        }
+        let fields = LasField::ALL
+            .into_iter()
+            .filter(|field| match field {
+                LasField::ScannerChannel => layout.extended,
+                LasField::Nir => layout.nir,
+                _ => true,
+            })
+            .map(|field| (field, Vec::with_capacity(capacity)))
+            .collect();
+        Self {
+            xyz: Vec::with_capacity(capacity * 3),
+            intensity: Vec::with_capacity(capacity),
+            rgb: layout
+                .color
+                .then(|| std::array::from_fn(|_| Vec::with_capacity(capacity))),
+            gps_time: layout.gps_time.then(|| Vec::with_capacity(capacity)),
+            fields,
+            extra,
+            metadata: schema.metadata.clone(),
+            origin: schema.origin,
+            crs: schema.crs.clone(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.intensity.len()
+    }
+
+    fn push(&mut self, point: &Point) {
+        let [ox, oy, oz] = self.origin;
+        self.xyz.extend_from_slice(&[
+            (point.x - ox) as f32,
+            (point.y - oy) as f32,
+            (point.z - oz) as f32,
+        ]);
+
+        // LAS always has intensity
+        self.intensity.push(point.intensity as f32 / 65535.0);
+
+        if let Some([r, g, b]) = self.rgb.as_mut() {
+            let color = point.color.unwrap_or(Color {
+                red: 0,
+                green: 0,
+                blue: 0,
+            });
+            r.push((color.red >> 8) as u8);
+            g.push((color.green >> 8) as u8);
+            b.push((color.blue >> 8) as u8);
+        }
+
+        if let Some(gps_time) = self.gps_time.as_mut() {
+            gps_time.push(point.gps_time.unwrap_or(0.0));
         }
 
-        if !rgb_r.is_empty() && rgb_r.len() == result.point_count() {
-            result.set_rgb(rgb_r, rgb_g, rgb_b)?;
+        for (field, values) in self.fields.iter_mut() {
+            values.push(field.read(point));
         }
 
+        for (descriptor, offset, raw) in self.extra.iter_mut() {
+            match point.extra_bytes.get(*offset..*offset + descriptor.size()) {
+                Some(bytes) => raw.extend_from_slice(bytes),
+                None => descriptor.encode(f64::NAN, raw),
+            }
+        }
+    }
+
+    fn into_cloud(self) -> Result<HighPerformancePointCloud> {
+        let n = self.len();
+        if n == 0 {
+            return Err("XYZ数据为空".into());
+        }
+
+        let xyz = tensor::tensor2_from_slice(&self.xyz, n, 3)?;
+        let mut result = HighPerformancePointCloud::from_tensor_xyz(xyz)?;
+        *result.origin_mut() = self.origin;
+        result.set_crs(self.crs);
+        result.set_intensity(self.intensity)?;
+        if let Some([r, g, b]) = self.rgb {
+            result.set_rgb(r, g, b)?;
+        }
+        if let Some(gps_time) = self.gps_time {
+            result.set_gps_time(gps_time)?;
+        }
+        for (field, values) in self.fields {
+            let data = AttributeData::from_values(field.dtype(), &values);
+            result.set_attribute_data(field.name().to_string(), data)?;
+        }
+        for (descriptor, _, raw) in self.extra {
+            let data = descriptor.decode_column(&raw);
+            result.set_attribute_data(descriptor.name, data)?;
+        }
+        result.set_las_metadata(Some(self.metadata));
         Ok(result)
     }
+}
 
-    /// 写入LAS文件
-    /// compress: 是否压缩为LAZ格式
-    pub fn to_las(&self, path: &str, _compress: bool) -> Result<()> {
-        if self.point_count() == 0 {
-            return Err("点云为空".into());
+// ============ 流式读写 ============
+
+/// 分块读取LAS/LAZ文件的迭代器，内存占用只与 chunk_size 相关
+pub struct LasChunkReader {
+    reader: Reader,
+    chunk_size: usize,
+    schema: LasSchema,
+    remaining: u64,
+}
+
+impl LasChunkReader {
+    /// 打开文件，chunk_size 为每块的最大点数
+    pub fn new(path: &str, chunk_size: usize) -> Result<Self> {
+        if chunk_size == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "chunk_size必须大于0".to_string(),
+            ));
+        }
+        let reader = Reader::from_path(path).map_err(|e| format!("无法读取LAS文件: {}", e))?;
+        let schema = LasSchema::from_header(reader.header())?;
+        let remaining = reader.header().number_of_points();
+        Ok(Self {
+            reader,
+            chunk_size,
+            schema,
+            remaining,
+        })
+    }
+
+    /// 文件总点数
+    pub fn total_points(&self) -> u64 {
+        self.reader.header().number_of_points()
+    }
+
+    /// 尚未读取的点数
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// 读取下一块，文件读完时返回 None
+    pub fn next_chunk(&mut self) -> Result<Option<HighPerformancePointCloud>> {
+        let take = (self.chunk_size as u64).min(self.remaining) as usize;
+        let mut buffer = LasPointBuffer::new(&self.schema, take);
+        for point_result in self.reader.points().take(take) {
+            let point = point_result.map_err(|_| "读取LAS点失败".to_string())?;
+            buffer.push(&point);
+        }
+        self.remaining -= buffer.len() as u64;
+        if buffer.len() == 0 {
+            self.remaining = 0;
+            return Ok(None);
         }
+        buffer.into_cloud().map(Some)
+    }
+}
+
+impl Iterator for LasChunkReader {
+    type Item = Result<HighPerformancePointCloud>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_chunk().transpose()
+    }
+}
 
+/// LAS 写出参数，None 表示沿用点云保留的头部（无则使用默认值）
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LasWriteOptions {
+    /// 是否压缩为LAZ格式（扩展名为 .laz 时自动压缩）
+    pub compress: bool,
+    /// LAS 版本（1, 0..=4）
+    pub version: Option<(u8, u8)>,
+    /// 坐标量化比例，默认 0.001
+    pub scale: Option<[f64; 3]>,
+    /// 坐标量化偏移，默认 0
+    pub offset: Option<[f64; 3]>,
+    /// 点格式（0/1/2/3/6/7/8），None 时按首个块的内容自动选择
+    pub point_format: Option<u8>,
+}
+
+impl LasWriteOptions {
+    fn validate(&self) -> Result<()> {
+        if let Some((major, minor)) = self.version {
+            if major != 1 || minor > 4 {
+                return Err(PointCloudError::InvalidParameter(format!(
+                    "不支持的LAS版本: {}.{}",
+                    major, minor
+                )));
+            }
+        }
+        if let Some(scale) = self.scale {
+            if scale.iter().any(|s| !s.is_finite() || *s <= 0.0) {
+                return Err(PointCloudError::InvalidParameter(
+                    "scale必须为正数".to_string(),
+                ));
+            }
+        }
+        if let Some(offset) = self.offset {
+            if offset.iter().any(|o| !o.is_finite()) {
+                return Err(PointCloudError::InvalidParameter(
+                    "offset必须为有限值".to_string(),
+                ));
+            }
+        }
+        if let Some(id) = self.point_format {
+            if !matches!(id, 0..=3 | 6..=8) {
+                return Err(PointCloudError::InvalidParameter(format!(
+                    "不支持的点格式: {}（可选 0/1/2/3/6/7/8）",
+                    id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 流式写入LAS/LAZ文件：逐块追加点，关闭时回写头部的包围盒与点数
+/// 点格式取自写出参数，未指定时在写入第一块时按其内容自动确定（0/1/2/3/6/7/8），
+/// 后续块必须能被该格式容纳；后续块需要 GPS 时间、NIR 或扩展格式时应预先指定点格式
+/// 头部的版本、缩放偏移与 VLR/EVLR 取自写出参数或首个块保留的 LAS 元数据
+pub struct LasStreamWriter {
+    path: String,
+    options: LasWriteOptions,
+    writer: Option<Writer<BufWriter<File>>>,
+    format: Format,
+    extra_bytes: Vec<ExtraBytes>,
+    point_count: u64,
+}
+
+impl LasStreamWriter {
+    /// 创建写入器，文件在写入第一块时创建
+    pub fn new(path: &str, options: LasWriteOptions) -> Result<Self> {
+        options.validate()?;
+        Ok(Self {
+            path: path.to_string(),
+            options,
+            writer: None,
+            format: Format::default(),
+            extra_bytes: Vec::new(),
+            point_count: 0,
+        })
+    }
+
+    /// 已写入的点数
+    pub fn point_count(&self) -> u64 {
+        self.point_count
+    }
+
+    /// 文件是否处于打开状态
+    pub fn is_open(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    fn open(&mut self, layout: LasLayout, first: &HighPerformancePointCloud) -> Result<()> {
         // 验证路径可写
-        if let Some(parent) = std::path::Path::new(path).parent() {
+        if let Some(parent) = std::path::Path::new(&self.path).parent() {
             if !parent.exists() {
                 fs::create_dir_all(parent).map_err(PointCloudError::IoError)?;
             }
         }
 
-        let has_rgb = self.has_rgb();
-        let format_id = if has_rgb { 2 } else { 0 };
+        self.extra_bytes = first.las_extra_bytes();
+        for descriptor in &self.extra_bytes {
+            descriptor.check_name()?;
+        }
+        let default_metadata = LasMetadata::default();
+        let metadata = first.las_metadata().unwrap_or(&default_metadata);
+        // 沿用的旧版本无法容纳扩展点格式时升级到 1.4，显式指定的版本不做调整
+        let (major, minor) = match self.options.version {
+            Some(version) => version,
+            None if layout.format_id() >= 6 => metadata.version.max((1, 4)),
+            None => metadata.version,
+        };
+        let scale = self.options.scale.unwrap_or(metadata.scale);
+        // 无保留头部时以点云原点为偏移，避免大坐标量化后溢出 i32
+        let offset = self.options.offset.unwrap_or(match first.las_metadata() {
+            Some(metadata) => metadata.offset,
+            None => first.origin(),
+        });
 
-        let mut builder = Builder::from((1, 4));
-        let mut format = Format::new(format_id).map_err(|e| e.to_string())?;
-        format.is_compressed = _compress || path.to_lowercase().ends_with(".laz");
-        builder.point_format = format;
+        let mut builder = Builder::from((major, minor));
+        builder.point_format = self.point_format(layout)?;
+        builder.transforms = Vector {
+            x: Transform {
+                scale: scale[0],
+                offset: offset[0],
+            },
+            y: Transform {
+                scale: scale[1],
+                offset: offset[1],
+            },
+            z: Transform {
+                scale: scale[2],
+                offset: offset[2],
+            },
+        };
+        if !metadata.system_identifier.is_empty() {
+            builder.system_identifier = metadata.system_identifier.clone();
+        }
+        if !metadata.generating_software.is_empty() {
+            builder.generating_software = metadata.generating_software.clone();
+        }
+        builder.file_source_id = metadata.file_source_id;
+        builder.gps_time_type = metadata.gps_time_type();
+        builder.vlrs = metadata.vlrs.clone();
+        builder.evlrs = metadata.evlrs.clone();
+        // 点云坐标系与保留的坐标系 VLR 不一致（重投影或手动设置）时替换为新的坐标系 VLR
+        let retained_crs = las_meta::crs_from_vlrs(metadata.vlrs.iter().chain(&metadata.evlrs));
+        if first.crs() != retained_crs.as_ref() {
+            builder.vlrs.retain(|vlr| !las_meta::is_crs_vlr(vlr));
+            builder.evlrs.retain(|vlr| !las_meta::is_crs_vlr(vlr));
+            builder.vlrs.extend(first.crs().and_then(las_meta::crs_vlr));
+        }
+        // 全局编码中的 WKT 标志位自 1.4 起才有效
+        builder.has_wkt_crs = (major, minor) >= (1, 4)
+            && builder
+                .vlrs
+                .iter()
+                .chain(&builder.evlrs)
+                .any(las_meta::is_wkt_vlr);
+        if !self.extra_bytes.is_empty() {
+            builder
+                .vlrs
+                .push(las_meta::extra_bytes_vlr(&self.extra_bytes));
+        }
         let header = builder.into_header().map_err(|e| e.to_string())?;
 
-        let mut writer = Writer::from_path(path, header).map_err(|e| e.to_string())?;
+        let writer = Writer::from_path(&self.path, header).map_err(|e| e.to_string())?;
+        self.writer = Some(writer);
+        self.format = self.point_format(layout)?;
+        Ok(())
+    }
 
-        let xyz = self.get_xyz();
-        let intensity = self.get_intensity();
-        let rgb = self.get_rgb();
+    fn point_format(&self, layout: LasLayout) -> Result<Format> {
+        let mut format = Format::new(layout.format_id()).map_err(|e| e.to_string())?;
+        format.is_compressed = self.options.compress || self.path.to_lowercase().ends_with(".laz");
+        let extra_size: usize = self.extra_bytes.iter().map(ExtraBytes::size).sum();
+        format.extra_bytes = u16::try_from(extra_size)
+            .map_err(|_| format!("Extra Bytes总字节数{}超出点记录限制", extra_size))?;
+        Ok(format)
+    }
 
-        for (idx, point_xyz) in xyz.iter().enumerate() {
-            let mut point = Point {
-                x: point_xyz[0] as f64,
-                y: point_xyz[1] as f64,
-                z: point_xyz[2] as f64,
-                ..Default::default()
+    /// 追加一块点云
+    pub fn write_chunk(&mut self, chunk: &HighPerformancePointCloud) -> Result<()> {
+        if chunk.point_count() == 0 {
+            return Ok(());
+        }
+        let layout = LasLayout::of(chunk);
+        if self.writer.is_none() {
+            if self.point_count > 0 {
+                return Err("写入器已关闭".into());
+            }
+            let format_layout = match self.options.point_format {
+                Some(id) => LasLayout::from_format(&Format::new(id).map_err(|e| e.to_string())?),
+                None => layout,
             };
+            // 在创建文件之前检查首个块，避免留下只有头部的文件
+            Self::check_layout(&format_layout, &layout)?;
+            self.open(format_layout, chunk)?;
+        }
+        // 检查失败时不写入任何点，已写入的块仍可通过 close 正常收尾
+        Self::check_layout(&LasLayout::from_format(&self.format), &layout)?;
 
-            if let Some(intensity_vec) = &intensity {
-                let raw = (intensity_vec[idx] * 65535.0).clamp(0.0, 65535.0);
-                point.intensity = raw as u16;
-            } else {
-                point.intensity = 0;
+        if let Some(name) = chunk.scalar_attributes().into_keys().find(|name| {
+            !LasField::is_standard(name) && !self.extra_bytes.iter().any(|d| &d.name == name)
+        }) {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "块包含首个块没有的自定义属性'{}'",
+                name
+            )));
+        }
+
+        let writer = self.writer.as_mut().expect("写入器已打开");
+        for point in chunk.to_las_points(&self.format, &self.extra_bytes)? {
+            writer.write_point(point).map_err(|e| e.to_string())?;
+        }
+        self.point_count += chunk.point_count() as u64;
+        Ok(())
+    }
+
+    fn check_layout(format: &LasLayout, layout: &LasLayout) -> Result<()> {
+        let reasons = format.conflicts(layout);
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        Err(PointCloudError::InvalidParameter(format!(
+            "点格式{}无法写入该块：{}；可通过 point_format 预先指定点格式",
+            format.format_id(),
+            reasons.join("，")
+        )))
+    }
+
+    /// 关闭文件并回写头部（包围盒、点数）
+    pub fn close(&mut self) -> Result<()> {
+        match self.writer.take() {
+            Some(mut writer) => writer.close().map_err(|e| e.to_string().into()),
+            None if self.point_count > 0 => Ok(()),
+            None => Err("未写入任何点".into()),
+        }
+    }
+}
+
+impl Drop for LasStreamWriter {
+    /// 未显式关闭时回写头部，避免留下点数与包围盒未更新的文件
+    fn drop(&mut self) {
+        if let Some(mut writer) = self.writer.take() {
+            let _ = writer.close();
+        }
+    }
+}
+
+// ============ COPC（Cloud Optimized Point Cloud）============
+
+/// COPC 信息 VLR 的 user_id 与 record_id
+const COPC_USER_ID: &str = "copc";
+const COPC_INFO_RECORD_ID: u16 = 1;
+/// 层级页中每个条目的字节数
+const COPC_ENTRY_SIZE: usize = 32;
+/// 单个层级页的字节数上限
+const COPC_MAX_PAGE_SIZE: u64 = 1 << 24;
+/// 节点键的最大层级（更深的节点边长已低于 f64 精度）
+const COPC_MAX_LEVEL: i32 = 52;
+
+/// COPC 信息 VLR
+#[derive(Clone, Copy, Debug)]
+pub struct CopcInfo {
+    /// 根节点立方体中心
+    pub center: [f64; 3],
+    /// 根节点立方体半边长
+    pub halfsize: f64,
+    /// 根节点点间距
+    pub spacing: f64,
+    /// 根层级页在文件中的偏移
+    pub root_hier_offset: u64,
+    /// 根层级页字节数
+    pub root_hier_size: u64,
+    /// GPS 时间范围
+    pub gpstime_range: [f64; 2],
+}
+
+impl CopcInfo {
+    fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < 72 {
+            return Err(format!("COPC信息VLR长度不足: {}", data.len()).into());
+        }
+        let f64_at = |pos: usize| f64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        let u64_at = |pos: usize| u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        Ok(Self {
+            center: [f64_at(0), f64_at(8), f64_at(16)],
+            halfsize: f64_at(24),
+            spacing: f64_at(32),
+            root_hier_offset: u64_at(40),
+            root_hier_size: u64_at(48),
+            gpstime_range: [f64_at(56), f64_at(64)],
+        })
+    }
+
+    /// 节点立方体的包围盒
+    fn node_bounds(&self, key: &CopcKey) -> ([f64; 3], [f64; 3]) {
+        let size = 2.0 * self.halfsize / (1u64 << key.level) as f64;
+        let min: [f64; 3] =
+            std::array::from_fn(|k| self.center[k] - self.halfsize + key.xyz[k] as f64 * size);
+        (min, min.map(|v| v + size))
+    }
+}
+
+/// COPC 八叉树节点键
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CopcKey {
+    level: i32,
+    xyz: [i32; 3],
+}
+
+impl CopcKey {
+    /// 层级非负且坐标位于该层级的网格内
+    fn is_valid(&self) -> bool {
+        (0..=COPC_MAX_LEVEL).contains(&self.level)
+            && self
+                .xyz
+                .iter()
+                .all(|&v| v >= 0 && (v as u64) < (1u64 << self.level))
+    }
+}
+
+/// 层级页条目
+/// point_count 为 -1 时指向子层级页，为 0 时节点为空
+#[derive(Clone, Copy, Debug)]
+struct CopcEntry {
+    key: CopcKey,
+    offset: u64,
+    byte_size: i32,
+    point_count: i32,
+}
+
+/// 解析层级页字节
+fn parse_copc_page(data: &[u8]) -> Result<Vec<CopcEntry>> {
+    if data.len() % COPC_ENTRY_SIZE != 0 {
+        return Err(format!("COPC层级页长度不是{}的整数倍", COPC_ENTRY_SIZE).into());
+    }
+    let entries = data
+        .chunks_exact(COPC_ENTRY_SIZE)
+        .map(|entry| {
+            let i32_at = |pos: usize| i32::from_le_bytes(entry[pos..pos + 4].try_into().unwrap());
+            CopcEntry {
+                key: CopcKey {
+                    level: i32_at(0),
+                    xyz: [i32_at(4), i32_at(8), i32_at(12)],
+                },
+                offset: u64::from_le_bytes(entry[16..24].try_into().unwrap()),
+                byte_size: i32_at(24),
+                point_count: i32_at(28),
             }
+        })
+        .collect();
+    Ok(entries)
+}
 
-            if let Some((ref r_vec, ref g_vec, ref b_vec)) = rgb {
-                point.color = Some(Color {
-                    red: (r_vec[idx] as u16) << 8,
-                    green: (g_vec[idx] as u16) << 8,
-                    blue: (b_vec[idx] as u16) << 8,
+impl CopcEntry {
+    /// 转换为 las 的层级条目，用于按偏移解码数据块
+    fn to_las(self) -> las::copc::Entry {
+        las::copc::Entry {
+            key: las::copc::VoxelKey {
+                l: self.key.level,
+                x: self.key.xyz[0],
+                y: self.key.xyz[1],
+                z: self.key.xyz[2],
+            },
+            offset: self.offset,
+            byte_size: self.byte_size,
+            point_count: self.point_count,
+        }
+    }
+}
+
+/// 检查 [offset, offset + size) 位于文件内且不超过 max_size
+fn check_copc_range(
+    offset: u64,
+    size: i64,
+    max_size: u64,
+    file_len: u64,
+    what: &str,
+) -> Result<u64> {
+    let size = u64::try_from(size).map_err(|_| format!("COPC{}字节数无效: {}", what, size))?;
+    let in_file = offset.checked_add(size).is_some_and(|end| end <= file_len);
+    if size > max_size || !in_file {
+        return Err(format!("COPC{}超出文件范围: 偏移{}，{}字节", what, offset, size).into());
+    }
+    Ok(size)
+}
+
+/// 从根层级页开始遍历，返回按文件偏移排序的数据节点
+/// keep 判断节点是否需要读取；子层级页的键为其子树的根，不需要时整页跳过，不读取页内容
+fn read_copc_hierarchy(
+    file: &mut File,
+    info: &CopcInfo,
+    keep: impl Fn(&CopcKey) -> bool,
+) -> Result<Vec<CopcEntry>> {
+    let file_len = file.metadata().map_err(PointCloudError::IoError)?.len();
+    let mut pages = vec![(info.root_hier_offset, info.root_hier_size as i64)];
+    let mut visited = HashSet::new();
+    let mut nodes = Vec::new();
+    while let Some((offset, size)) = pages.pop() {
+        // 层级页互相引用时终止，防止死循环
+        if !visited.insert(offset) {
+            return Err(format!("COPC层级页被重复引用: 偏移{}", offset).into());
+        }
+        let size = check_copc_range(offset, size, COPC_MAX_PAGE_SIZE, file_len, "层级页")?;
+        let mut data = vec![0u8; size as usize];
+        file.seek(SeekFrom::Start(offset))
+            .map_err(PointCloudError::IoError)?;
+        file.read_exact(&mut data)
+            .map_err(PointCloudError::IoError)?;
+        for entry in parse_copc_page(&data)? {
+            if !entry.key.is_valid() {
+                return Err(format!("COPC节点键无效: {:?}", entry.key).into());
+            }
+            if !keep(&entry.key) {
+                continue;
+            }
+            match entry.point_count {
+                -1 => pages.push((entry.offset, entry.byte_size as i64)),
+                count if count > 0 => {
+                    check_copc_range(
+                        entry.offset,
+                        entry.byte_size as i64,
+                        u64::MAX,
+                        file_len,
+                        "数据块",
+                    )?;
+                    nodes.push(entry);
+                }
+                _ => {}
+            }
+        }
+    }
+    nodes.sort_by_key(|entry| entry.offset);
+    Ok(nodes)
+}
+
+fn boxes_intersect(a: &([f64; 3], [f64; 3]), b: &([f64; 3], [f64; 3])) -> bool {
+    (0..3).all(|k| a.0[k] <= b.1[k] && b.0[k] <= a.1[k])
+}
+
+impl HighPerformancePointCloud {
+    /// 读取文件的 COPC 信息 VLR，非 COPC 文件返回 None
+    pub fn copc_info(path: &str) -> Result<Option<CopcInfo>> {
+        let reader = Reader::from_path(path).map_err(|e| format!("无法读取LAS文件: {}", e))?;
+        reader
+            .header()
+            .vlrs()
+            .iter()
+            .find(|vlr| vlr.user_id == COPC_USER_ID && vlr.record_id == COPC_INFO_RECORD_ID)
+            .map(|vlr| CopcInfo::parse(&vlr.data))
+            .transpose()
+    }
+
+    /// 判断文件是否为 COPC
+    pub fn is_copc(path: &str) -> Result<bool> {
+        Ok(Self::copc_info(path)?.is_some())
+    }
+
+    /// 按空间范围与层级读取 COPC 文件
+    /// bounds: (min, max) 包围盒，None 时读取全部范围
+    /// max_level: 最大八叉树层级（根为 0），None 时读取全部层级
+    /// 只读取与查询相交的层级页，数据块按条目中的偏移独立解码
+    pub fn from_copc(
+        path: &str,
+        bounds: Option<([f64; 3], [f64; 3])>,
+        max_level: Option<i32>,
+    ) -> Result<Self> {
+        let info = Self::copc_info(path)?
+            .ok_or_else(|| PointCloudError::from(format!("不是COPC文件: {}", path)))?;
+        if let Some((min, max)) = &bounds {
+            if (0..3).any(|k| min[k] > max[k]) {
+                return Err(PointCloudError::InvalidParameter(
+                    "bounds的最小值不能大于最大值".to_string(),
+                ));
+            }
+        }
+
+        let mut file = File::open(path).map_err(PointCloudError::IoError)?;
+        let nodes = read_copc_hierarchy(&mut file, &info, |key| {
+            max_level.is_none_or(|level| key.level <= level)
+                && bounds
+                    .as_ref()
+                    .is_none_or(|query| boxes_intersect(&info.node_bounds(key), query))
+        })?;
+
+        let mut reader = las::CopcEntryReader::new(BufReader::new(file))
+            .map_err(|e| format!("无法读取COPC文件: {}", e))?;
+        let selected: u64 = nodes.iter().map(|node| node.point_count as u64).sum();
+        if selected > reader.header().number_of_points() {
+            return Err(format!(
+                "COPC层级的点数{}超过文件头点数{}",
+                selected,
+                reader.header().number_of_points()
+            )
+            .into());
+        }
+        let schema = LasSchema::from_header(reader.header())?;
+        let mut buffer = LasPointBuffer::new(&schema, 0);
+
+        let mut points = Vec::new();
+        for node in &nodes {
+            points.clear();
+            reader
+                .read_entry_points(&node.to_las(), &mut points)
+                .map_err(|e| format!("解码COPC数据块失败: {}", e))?;
+            for point in &points {
+                let inside = bounds.as_ref().is_none_or(|(min, max)| {
+                    let p = [point.x, point.y, point.z];
+                    (0..3).all(|k| p[k] >= min[k] && p[k] <= max[k])
                 });
+                if inside {
+                    buffer.push(point);
+                }
             }
+        }
 
-            writer.write_point(point).map_err(|e| e.to_string())?;
+        if buffer.len() == 0 {
+            return Ok(Self::new());
         }
+        buffer.into_cloud()
+    }
+}
 
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// 层级页条目字节：键 (level, x, y, z)、偏移、字节数、点数
+    fn copc_entry(key: [i32; 4], offset: u64, size: i32, count: i32) -> Vec<u8> {
+        let mut entry = Vec::with_capacity(COPC_ENTRY_SIZE);
+        for v in key {
+            entry.extend_from_slice(&v.to_le_bytes());
+        }
+        entry.extend_from_slice(&offset.to_le_bytes());
+        entry.extend_from_slice(&size.to_le_bytes());
+        entry.extend_from_slice(&count.to_le_bytes());
+        entry
     }
 
-    /// 删除LAS/LAZ文件
-    pub fn delete_file(path: &str) -> Result<()> {
-        if fs::metadata(path).is_err() {
-            return Err(format!("文件不存在: {}", path).into());
+    /// 写出只含一个数据块的 LAZ（格式 6），返回文件字节与数据块的字节范围
+    fn write_laz_chunk(
+        path: &Path,
+        points: &[[f64; 3]],
+        vlrs: Vec<las::Vlr>,
+    ) -> (Vec<u8>, std::ops::Range<usize>) {
+        let mut builder = Builder::from((1, 4));
+        builder.point_format = Format::new(6).unwrap();
+        builder.point_format.is_compressed = true;
+        builder.vlrs = vlrs;
+        let mut writer = Writer::from_path(path, builder.into_header().unwrap()).unwrap();
+        for p in points {
+            let point = Point {
+                x: p[0],
+                y: p[1],
+                z: p[2],
+                gps_time: Some(0.0),
+                ..Default::default()
+            };
+            writer.write_point(point).unwrap();
         }
+        writer.close().unwrap();
 
-        fs::remove_file(path).map_err(PointCloudError::IoError)?;
-        Ok(())
+        // 点数据以块表偏移开头，之后到块表之间为唯一的数据块
+        let bytes = fs::read(path).unwrap();
+        let start = u32::from_le_bytes(bytes[96..100].try_into().unwrap()) as usize;
+        let table = i64::from_le_bytes(bytes[start..start + 8].try_into().unwrap()) as usize;
+        (bytes, start + 8..table)
+    }
+
+    /// 构造三节点 COPC 文件，根立方体为 [0,100]^3
+    /// 根节点位于根层级页，两个一级节点各自位于独立的子层级页
+    fn write_copc_fixture(dir: &Path) -> String {
+        let nodes: [([i32; 4], [[f64; 3]; 2]); 3] = [
+            ([0, 0, 0, 0], [[10.0; 3], [90.0; 3]]),
+            ([1, 0, 0, 0], [[20.0; 3], [30.0; 3]]),
+            ([1, 1, 1, 1], [[60.0; 3], [70.0; 3]]),
+        ];
+        // 信息 VLR 先写占位内容，拼接完成后回填层级页位置
+        let placeholder = vec![0xA5u8; 160];
+        let info_vlr = las::Vlr {
+            user_id: COPC_USER_ID.to_string(),
+            record_id: COPC_INFO_RECORD_ID,
+            description: String::new(),
+            data: placeholder.clone(),
+        };
+        let path = dir.join("fixture.copc.laz");
+        let (mut bytes, root_chunk) = write_laz_chunk(&path, &nodes[0].1, vec![info_vlr]);
+
+        // 子节点的数据块与层级页追加在块表之后
+        let mut root_page = copc_entry(
+            nodes[0].0,
+            root_chunk.start as u64,
+            root_chunk.len() as i32,
+            2,
+        );
+        for (i, (key, points)) in nodes.iter().enumerate().skip(1) {
+            let (child, range) =
+                write_laz_chunk(&dir.join(format!("child_{}.laz", i)), points, Vec::new());
+            let chunk_offset = bytes.len() as u64;
+            bytes.extend_from_slice(&child[range.clone()]);
+            let page_offset = bytes.len() as u64;
+            bytes.extend(copc_entry(*key, chunk_offset, range.len() as i32, 2));
+            root_page.extend(copc_entry(*key, page_offset, COPC_ENTRY_SIZE as i32, -1));
+        }
+        let root_offset = bytes.len() as u64;
+        bytes.extend_from_slice(&root_page);
+
+        let mut info = Vec::new();
+        for v in [50.0f64, 50.0, 50.0, 50.0, 1.0] {
+            info.extend_from_slice(&v.to_le_bytes());
+        }
+        info.extend_from_slice(&root_offset.to_le_bytes());
+        info.extend_from_slice(&(root_page.len() as u64).to_le_bytes());
+        info.resize(placeholder.len(), 0);
+        let at = bytes
+            .windows(placeholder.len())
+            .position(|window| window == placeholder.as_slice())
+            .unwrap();
+        bytes[at..at + info.len()].copy_from_slice(&info);
+        // LAS 1.4 的 64 位点数
+        bytes[247..255].copy_from_slice(&6u64.to_le_bytes());
+
+        fs::write(&path, bytes).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_from_copc_bounds_and_level() {
+        let dir = std::env::temp_dir().join(format!("copc_fixture_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = write_copc_fixture(&dir);
+        assert!(HighPerformancePointCloud::is_copc(&path).unwrap());
+
+        let read_x = |bounds, max_level| {
+            let pc = HighPerformancePointCloud::from_copc(&path, bounds, max_level).unwrap();
+            let mut x: Vec<f64> = pc.global_points().iter().map(|p| p[0]).collect();
+            x.sort_by(f64::total_cmp);
+            x
+        };
+        assert_eq!(read_x(None, None).len(), 6);
+        assert_eq!(read_x(None, Some(0)), vec![10.0, 90.0]);
+        assert_eq!(
+            read_x(Some(([0.0; 3], [40.0; 3])), None),
+            vec![10.0, 20.0, 30.0]
+        );
+        assert_eq!(
+            read_x(Some(([55.0; 3], [100.0; 3])), Some(1)),
+            vec![60.0, 70.0, 90.0]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_copc_hierarchy_guards() {
+        let dir = std::env::temp_dir().join(format!("copc_pages_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pages.bin");
+        let mut info = CopcInfo {
+            center: [0.0; 3],
+            halfsize: 10.0,
+            spacing: 1.0,
+            root_hier_offset: 0,
+            root_hier_size: COPC_ENTRY_SIZE as u64,
+            gpstime_range: [0.0; 2],
+        };
+
+        // 引用自身的层级页
+        fs::write(
+            &path,
+            copc_entry([0, 0, 0, 0], 0, COPC_ENTRY_SIZE as i32, -1),
+        )
+        .unwrap();
+        let mut file = File::open(&path).unwrap();
+        assert!(read_copc_hierarchy(&mut file, &info, |_| true).is_err());
+
+        // 指向文件之外的子层级页只在需要读取时报错
+        let mut page = copc_entry([0, 0, 0, 0], 0, 16, 1);
+        page.extend(copc_entry([1, 1, 1, 1], 1 << 40, 1 << 30, -1));
+        fs::write(&path, &page).unwrap();
+        info.root_hier_size = page.len() as u64;
+        let mut file = File::open(&path).unwrap();
+        let nodes = read_copc_hierarchy(&mut file, &info, |key| key.level == 0).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert!(read_copc_hierarchy(&mut file, &info, |_| true).is_err());
+
+        // 层级页字节数超出文件范围，节点键越界
+        info.root_hier_size = 1 << 40;
+        assert!(read_copc_hierarchy(&mut file, &info, |_| true).is_err());
+        fs::write(&path, copc_entry([1, 2, 0, 0], 0, 0, 0)).unwrap();
+        info.root_hier_size = COPC_ENTRY_SIZE as u64;
+        let mut file = File::open(&path).unwrap();
+        assert!(read_copc_hierarchy(&mut file, &info, |_| true).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_copc_page_and_bounds() {
+        let mut page = copc_entry([1, 1, 0, 0], 4096, 128, 10);
+        page.extend(copc_entry([2, 3, 3, 3], 0, 0, -1));
+        let entries = parse_copc_page(&page).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].key.xyz, [1, 0, 0]);
+        assert_eq!(entries[0].offset, 4096);
+        assert_eq!(entries[1].point_count, -1);
+        assert!(parse_copc_page(&page[..31]).is_err());
+
+        let info = CopcInfo {
+            center: [0.0; 3],
+            halfsize: 10.0,
+            spacing: 1.0,
+            root_hier_offset: 0,
+            root_hier_size: 0,
+            gpstime_range: [0.0; 2],
+        };
+        let (min, max) = info.node_bounds(&entries[0].key);
+        assert_eq!(min, [0.0, -10.0, -10.0]);
+        assert_eq!(max, [10.0, 0.0, 0.0]);
+        assert!(boxes_intersect(
+            &(min, max),
+            &([5.0, -5.0, -5.0], [20.0, 1.0, 1.0])
+        ));
+        assert!(!boxes_intersect(&(min, max), &([-5.0; 3], [-1.0; 3])));
+    }
+
+    #[test]
+    fn test_las_layout_format_selection() {
+        let xyz = vec![vec![0.0, 0.0, 0.0], vec![1.0, 1.0, 1.0]];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        assert_eq!(LasLayout::of(&pc).format_id(), 0);
+
+        pc.set_gps_time(vec![1.0e9, 1.0e9 + 0.5]).unwrap();
+        pc.set_rgb(vec![0, 255], vec![0, 255], vec![0, 255])
+            .unwrap();
+        assert_eq!(LasLayout::of(&pc).format_id(), 3);
+
+        pc.set_attribute("classification".to_string(), vec![2.0, 40.0])
+            .unwrap();
+        assert_eq!(LasLayout::of(&pc).format_id(), 7);
+
+        pc.set_attribute("nir".to_string(), vec![100.0, 200.0])
+            .unwrap();
+        let layout = LasLayout::of(&pc);
+        assert_eq!(layout.format_id(), 8);
+        let format = Format::new(8).unwrap();
+        assert!(LasLayout::from_format(&format)
+            .conflicts(&layout)
+            .is_empty());
+        assert_eq!(
+            LasLayout::from_format(&Format::new(3).unwrap())
+                .conflicts(&layout)
+                .len(),
+            2
+        );
+
+        let points = pc.to_las_points(&format, &[]).unwrap();
+        assert_eq!(u8::from(points[1].classification), 40);
+        assert_eq!(points[1].nir, Some(200));
+        assert_eq!(points[1].gps_time, Some(1.0e9 + 0.5));
+    }
+
+    #[test]
+    fn test_stream_writer_point_format() {
+        let dir = std::env::temp_dir().join(format!("las_stream_{}", std::process::id()));
+        let path = dir.join("stream.las").to_string_lossy().into_owned();
+        let first = HighPerformancePointCloud::from_xyz(vec![vec![0.0, 0.0, 0.0]]).unwrap();
+        let mut later = HighPerformancePointCloud::from_xyz(vec![vec![1.0, 1.0, 1.0]]).unwrap();
+        later.set_gps_time(vec![1.0e9]).unwrap();
+
+        // 自动选择的格式 0 无法容纳后续块的 GPS 时间，已写入的块仍可正常收尾
+        let mut writer = LasStreamWriter::new(&path, LasWriteOptions::default()).unwrap();
+        writer.write_chunk(&first).unwrap();
+        let err = writer.write_chunk(&later).unwrap_err().to_string();
+        assert!(err.contains("GPS"), "{}", err);
+        writer.close().unwrap();
+        assert_eq!(
+            Reader::from_path(&path)
+                .unwrap()
+                .header()
+                .number_of_points(),
+            1
+        );
+
+        // 预先指定格式 1 时两块都能写入
+        let options = LasWriteOptions {
+            point_format: Some(1),
+            ..Default::default()
+        };
+        let mut writer = LasStreamWriter::new(&path, options).unwrap();
+        writer.write_chunk(&first).unwrap();
+        writer.write_chunk(&later).unwrap();
+        drop(writer);
+        let reader = Reader::from_path(&path).unwrap();
+        assert_eq!(reader.header().number_of_points(), 2);
+        assert_eq!(reader.header().point_format().to_u8().unwrap(), 1);
+
+        let invalid = LasWriteOptions {
+            point_format: Some(4),
+            ..Default::default()
+        };
+        assert!(LasStreamWriter::new(&path, invalid).is_err());
+        std::fs::remove_dir_all(dir).ok();
     }
 }
@@ -0,0 +1,704 @@
+/// LAS 元数据：头部信息、VLR/EVLR、坐标系 VLR 与 Extra Bytes 描述符（LAS 1.4 Extra Bytes VLR）
+use crate::point_cloud::attribute_data::{
+    match_attribute, AttributeData, AttributeType, AttributeValue,
+};
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::crs::Crs;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+use las::{GpsTimeType, Header, Vlr};
+
+/// Extra Bytes VLR 的 user_id 与 record_id
+pub const EXTRA_BYTES_USER_ID: &str = "LASF_Spec";
+pub const EXTRA_BYTES_RECORD_ID: u16 = 4;
+/// 读取时丢弃、写出时由写入器重新生成的 VLR：Extra Bytes、LASzip、COPC
+const REGENERATED_VLRS: [(&str, u16); 4] = [
+    (EXTRA_BYTES_USER_ID, EXTRA_BYTES_RECORD_ID),
+    ("laszip encoded", 22204),
+    ("copc", 1),
+    ("copc", 1000),
+];
+/// 坐标系 VLR：OGC WKT、GeoKey 目录，以及所有 LASF_Projection 记录
+const PROJECTION_USER_ID: &str = "LASF_Projection";
+const WKT_RECORD_ID: u16 = 2112;
+const GEO_KEY_DIRECTORY_RECORD_ID: u16 = 34735;
+const PROJECTION_RECORD_IDS: [u16; 5] = [2111, 2112, 34735, 34736, 34737];
+/// GeoTIFF 键：模型类型、栅格类型、地理坐标系、投影坐标系、投影线性单位
+const GT_MODEL_TYPE_KEY: u16 = 1024;
+const GT_RASTER_TYPE_KEY: u16 = 1025;
+const GEOGRAPHIC_TYPE_KEY: u16 = 2048;
+const PROJECTED_CS_TYPE_KEY: u16 = 3072;
+const PROJ_LINEAR_UNITS_KEY: u16 = 3076;
+/// GeoTIFF 中表示未定义/用户自定义的键值
+const GEO_KEY_USER_DEFINED: u16 = 32767;
+/// 每个描述符的字节数
+const DESCRIPTOR_SIZE: usize = 192;
+
+/// options 位：no_data / min / max / scale / offset 是否有效
+const OPTION_NO_DATA: u8 = 1;
+const OPTION_MIN: u8 = 1 << 1;
+const OPTION_MAX: u8 = 1 << 2;
+const OPTION_SCALE: u8 = 1 << 3;
+const OPTION_OFFSET: u8 = 1 << 4;
+
+/// Extra Bytes 数据类型（1-10 为标量，0 为未定义字节，11-30 为已废弃的数组类型）
+pub const EXTRA_BYTES_TYPES: [(&str, u8); 10] = [
+    ("uint8", 1),
+    ("int8", 2),
+    ("uint16", 3),
+    ("int16", 4),
+    ("uint32", 5),
+    ("int32", 6),
+    ("uint64", 7),
+    ("int64", 8),
+    ("float32", 9),
+    ("float64", 10),
+];
+
+/// Extra Bytes 描述符：一个自定义维度的类型、缩放与无效值
+/// no_data/min/max 为未缩放的原始值
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtraBytes {
+    pub name: String,
+    pub description: String,
+    pub data_type: u8,
+    /// data_type 为 0 时的字节数
+    pub undocumented_size: u8,
+    pub no_data: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub scale: Option<f64>,
+    pub offset: Option<f64>,
+}
+
+impl ExtraBytes {
+    /// 默认描述符：float32，无缩放与无效值
+    pub fn float32(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            data_type: 9,
+            undocumented_size: 0,
+            no_data: None,
+            min: None,
+            max: None,
+            scale: None,
+            offset: None,
+        }
+    }
+
+    /// 按属性类型创建描述符（bool 存为 uint8）
+    pub fn for_attribute(name: &str, dtype: AttributeType) -> Self {
+        let type_name = match dtype {
+            AttributeType::Bool => AttributeType::U8.name(),
+            other => other.name(),
+        };
+        let data_type = EXTRA_BYTES_TYPES
+            .iter()
+            .find(|(t, _)| *t == type_name)
+            .map_or(9, |&(_, id)| id);
+        Self {
+            data_type,
+            ..Self::float32(name)
+        }
+    }
+
+    /// 按类型名创建描述符（uint8/int8/.../float32/float64）
+    pub fn with_type(name: &str, type_name: &str) -> Result<Self> {
+        let data_type = EXTRA_BYTES_TYPES
+            .iter()
+            .find(|(t, _)| *t == type_name)
+            .map(|&(_, id)| id)
+            .ok_or_else(|| {
+                PointCloudError::InvalidParameter(format!("不支持的Extra Bytes类型: {}", type_name))
+            })?;
+        Ok(Self {
+            data_type,
+            ..Self::float32(name)
+        })
+    }
+
+    /// 检查名称能否放入描述符的 32 字节字段（按 UTF-8 字节计），不截断以免名称冲突
+    pub fn check_name(&self) -> Result<()> {
+        if self.name.is_empty() || self.name.len() > 32 {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "Extra Bytes名称'{}'长度必须为1-32字节（UTF-8编码为{}字节）",
+                self.name,
+                self.name.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// 类型名，未定义字节与废弃的数组类型返回 None
+    pub fn type_name(&self) -> Option<&'static str> {
+        EXTRA_BYTES_TYPES
+            .iter()
+            .find(|(_, id)| *id == self.data_type)
+            .map(|&(t, _)| t)
+    }
+
+    /// 单个元素的字节数
+    fn element_size(data_type: u8) -> usize {
+        match data_type {
+            1 | 2 => 1,
+            3 | 4 => 2,
+            5 | 6 | 9 => 4,
+            _ => 8,
+        }
+    }
+
+    /// 每个点占用的字节数
+    pub fn size(&self) -> usize {
+        match self.data_type {
+            0 => self.undocumented_size as usize,
+            1..=10 => Self::element_size(self.data_type),
+            // 已废弃的二维/三维数组类型
+            t => Self::element_size((t - 1) % 10 + 1) * ((t as usize - 1) / 10 + 1),
+        }
+    }
+
+    /// 是否为可映射到属性的标量类型
+    pub fn is_scalar(&self) -> bool {
+        (1..=10).contains(&self.data_type)
+    }
+
+    fn is_float(&self) -> bool {
+        matches!(self.data_type, 9 | 10)
+    }
+
+    fn is_scaled(&self) -> bool {
+        self.scale.is_some() || self.offset.is_some()
+    }
+
+    /// 读取后的属性类型：带缩放/偏移时为 float64，否则与存储类型一致
+    pub fn attribute_type(&self) -> AttributeType {
+        if self.is_scaled() {
+            return AttributeType::F64;
+        }
+        match self.data_type {
+            1 => AttributeType::U8,
+            2 => AttributeType::I8,
+            3 => AttributeType::U16,
+            4 => AttributeType::I16,
+            5 => AttributeType::U32,
+            6 => AttributeType::I32,
+            7 => AttributeType::U64,
+            8 => AttributeType::I64,
+            9 => AttributeType::F32,
+            _ => AttributeType::F64,
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        matches!(self.data_type, 2 | 4 | 6 | 8)
+    }
+
+    /// 读取原始值（未缩放）
+    fn read_raw(&self, bytes: &[u8]) -> f64 {
+        match self.data_type {
+            1 => bytes[0] as f64,
+            2 => bytes[0] as i8 as f64,
+            3 => u16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            4 => i16::from_le_bytes([bytes[0], bytes[1]]) as f64,
+            5 => u32::from_le_bytes(bytes[..4].try_into().unwrap()) as f64,
+            6 => i32::from_le_bytes(bytes[..4].try_into().unwrap()) as f64,
+            7 => u64::from_le_bytes(bytes[..8].try_into().unwrap()) as f64,
+            8 => i64::from_le_bytes(bytes[..8].try_into().unwrap()) as f64,
+            9 => f32::from_le_bytes(bytes[..4].try_into().unwrap()) as f64,
+            _ => f64::from_le_bytes(bytes[..8].try_into().unwrap()),
+        }
+    }
+
+    /// 写入原始值，整数类型四舍五入并截断到类型范围
+    fn write_raw(&self, raw: f64, out: &mut Vec<u8>) {
+        let int = raw.round();
+        match self.data_type {
+            1 => out.push(int.clamp(0.0, u8::MAX as f64) as u8),
+            2 => out.extend_from_slice(
+                &(int.clamp(i8::MIN as f64, i8::MAX as f64) as i8).to_le_bytes(),
+            ),
+            3 => out.extend_from_slice(&(int.clamp(0.0, u16::MAX as f64) as u16).to_le_bytes()),
+            4 => out.extend_from_slice(
+                &(int.clamp(i16::MIN as f64, i16::MAX as f64) as i16).to_le_bytes(),
+            ),
+            5 => out.extend_from_slice(&(int.clamp(0.0, u32::MAX as f64) as u32).to_le_bytes()),
+            6 => out.extend_from_slice(
+                &(int.clamp(i32::MIN as f64, i32::MAX as f64) as i32).to_le_bytes(),
+            ),
+            7 => out.extend_from_slice(&(int.max(0.0) as u64).to_le_bytes()),
+            8 => out.extend_from_slice(&(int as i64).to_le_bytes()),
+            9 => out.extend_from_slice(&(raw as f32).to_le_bytes()),
+            _ => out.extend_from_slice(&raw.to_le_bytes()),
+        }
+    }
+
+    /// 解码点记录中的值：无效值映射为 NaN，其余按 raw * scale + offset 转换
+    pub fn decode(&self, bytes: &[u8]) -> f64 {
+        let raw = self.read_raw(bytes);
+        if self.no_data == Some(raw) {
+            return f64::NAN;
+        }
+        raw * self.scale.unwrap_or(1.0) + self.offset.unwrap_or(0.0)
+    }
+
+    /// 编码属性值：NaN 写为无效值（未定义无效值时写 0）
+    pub fn encode(&self, value: f64, out: &mut Vec<u8>) {
+        let raw = if value.is_nan() {
+            self.no_data.unwrap_or(0.0)
+        } else {
+            (value - self.offset.unwrap_or(0.0)) / self.scale.unwrap_or(1.0)
+        };
+        self.write_raw(raw, out);
+    }
+
+    /// 解码一列附加字节（每点 size() 字节）为属性
+    /// 未缩放的整数按原类型无损读取（无效值保持原值），浮点与缩放值的无效值映射为 NaN
+    pub fn decode_column(&self, raw: &[u8]) -> AttributeData {
+        fn native<T: AttributeValue>(raw: &[u8], size: usize) -> AttributeData {
+            AttributeData::from_vec(raw.chunks_exact(size).map(T::read_le).collect::<Vec<T>>())
+        }
+        let size = self.size();
+        match self.attribute_type() {
+            AttributeType::U8 => native::<u8>(raw, size),
+            AttributeType::I8 => native::<i8>(raw, size),
+            AttributeType::U16 => native::<u16>(raw, size),
+            AttributeType::I16 => native::<i16>(raw, size),
+            AttributeType::U32 => native::<u32>(raw, size),
+            AttributeType::I32 => native::<i32>(raw, size),
+            AttributeType::U64 => native::<u64>(raw, size),
+            AttributeType::I64 => native::<i64>(raw, size),
+            dtype => {
+                let values: Vec<f64> = raw.chunks_exact(size).map(|b| self.decode(b)).collect();
+                AttributeData::from_values(dtype, &values)
+            }
+        }
+    }
+
+    /// 编码一列属性为附加字节，属性缺失时写无效值
+    /// 属性类型与未缩放的整数存储类型一致时按原类型无损写入
+    pub fn encode_column(&self, data: Option<&AttributeData>, rows: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(rows * self.size());
+        match data {
+            Some(data) if !self.is_float() && data.dtype() == self.attribute_type() => {
+                match_attribute!(
+                    data,
+                    t => tensor::tensor1_to_vec(t)
+                        .into_iter()
+                        .for_each(|v| v.write_le(&mut out)),
+                    m => tensor::tensor2_to_flat(m)
+                        .into_iter()
+                        .for_each(|v| v.write_le(&mut out)),
+                    values => values.iter().for_each(|v| v.write_le(&mut out))
+                )
+            }
+            Some(data) => data
+                .to_f64_vec()
+                .into_iter()
+                .for_each(|v| self.encode(v, &mut out)),
+            None => (0..rows).for_each(|_| self.encode(f64::NAN, &mut out)),
+        }
+        out
+    }
+
+    /// 解析 192 字节描述符
+    fn parse(data: &[u8]) -> Self {
+        let text = |bytes: &[u8]| {
+            let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            String::from_utf8_lossy(&bytes[..end]).into_owned()
+        };
+        let f64_at = |pos: usize| f64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        let data_type = data[2];
+        let options = data[3];
+
+        let base = Self {
+            name: text(&data[4..36]),
+            description: text(&data[160..192]),
+            data_type,
+            undocumented_size: if data_type == 0 { options } else { 0 },
+            no_data: None,
+            min: None,
+            max: None,
+            scale: None,
+            offset: None,
+        };
+        if data_type == 0 {
+            return base;
+        }
+
+        // no_data/min/max 按维度类型存放在 8 字节中
+        let (is_float, is_signed) = (base.is_float(), base.is_signed());
+        let any_at = |pos: usize| {
+            let bytes: [u8; 8] = data[pos..pos + 8].try_into().unwrap();
+            if is_float {
+                f64::from_le_bytes(bytes)
+            } else if is_signed {
+                i64::from_le_bytes(bytes) as f64
+            } else {
+                u64::from_le_bytes(bytes) as f64
+            }
+        };
+        let flag = |bit: u8, value: f64| (options & bit != 0).then_some(value);
+        Self {
+            no_data: flag(OPTION_NO_DATA, any_at(40)),
+            min: flag(OPTION_MIN, any_at(64)),
+            max: flag(OPTION_MAX, any_at(88)),
+            scale: flag(OPTION_SCALE, f64_at(112)),
+            offset: flag(OPTION_OFFSET, f64_at(136)),
+            ..base
+        }
+    }
+
+    /// 序列化为 192 字节描述符
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut data = vec![0u8; DESCRIPTOR_SIZE];
+        data[2] = self.data_type;
+
+        let mut options = 0u8;
+        let mut put_any = |pos: usize, bit: u8, value: Option<f64>, data: &mut [u8]| {
+            if let Some(value) = value {
+                options |= bit;
+                let bytes = if self.is_float() {
+                    value.to_le_bytes()
+                } else if self.is_signed() {
+                    (value as i64).to_le_bytes()
+                } else {
+                    (value as u64).to_le_bytes()
+                };
+                data[pos..pos + 8].copy_from_slice(&bytes);
+            }
+        };
+        put_any(40, OPTION_NO_DATA, self.no_data, &mut data);
+        put_any(64, OPTION_MIN, self.min, &mut data);
+        put_any(88, OPTION_MAX, self.max, &mut data);
+        if let Some(scale) = self.scale {
+            options |= OPTION_SCALE;
+            data[112..120].copy_from_slice(&scale.to_le_bytes());
+        }
+        if let Some(offset) = self.offset {
+            options |= OPTION_OFFSET;
+            data[136..144].copy_from_slice(&offset.to_le_bytes());
+        }
+        data[3] = if self.data_type == 0 {
+            self.undocumented_size
+        } else {
+            options
+        };
+
+        // 名称长度已由 check_name 保证；描述可能来自读取时的有损解码，按字符边界截断
+        let mut end = self.description.len().min(32);
+        while !self.description.is_char_boundary(end) {
+            end -= 1;
+        }
+        for (pos, bytes) in [
+            (4, self.name.as_bytes()),
+            (160, &self.description.as_bytes()[..end]),
+        ] {
+            data[pos..pos + bytes.len()].copy_from_slice(bytes);
+        }
+        data
+    }
+}
+
+/// 从 VLR/EVLR 中解析 Extra Bytes 描述符
+pub fn parse_extra_bytes<'a>(vlrs: impl IntoIterator<Item = &'a Vlr>) -> Result<Vec<ExtraBytes>> {
+    let Some(vlr) = vlrs
+        .into_iter()
+        .find(|vlr| vlr.user_id == EXTRA_BYTES_USER_ID && vlr.record_id == EXTRA_BYTES_RECORD_ID)
+    else {
+        return Ok(Vec::new());
+    };
+    if vlr.data.len() % DESCRIPTOR_SIZE != 0 {
+        return Err(format!("Extra Bytes VLR长度不是{}的整数倍", DESCRIPTOR_SIZE).into());
+    }
+    Ok(vlr
+        .data
+        .chunks_exact(DESCRIPTOR_SIZE)
+        .map(ExtraBytes::parse)
+        .collect())
+}
+
+/// 生成 Extra Bytes VLR
+pub fn extra_bytes_vlr(descriptors: &[ExtraBytes]) -> Vlr {
+    Vlr {
+        user_id: EXTRA_BYTES_USER_ID.to_string(),
+        record_id: EXTRA_BYTES_RECORD_ID,
+        description: "Extra Bytes".to_string(),
+        data: descriptors.iter().flat_map(ExtraBytes::to_bytes).collect(),
+    }
+}
+
+/// 是否为坐标系 VLR（LASF_Projection）
+pub fn is_crs_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id == PROJECTION_USER_ID && PROJECTION_RECORD_IDS.contains(&vlr.record_id)
+}
+
+/// 是否为 OGC WKT 坐标系 VLR
+pub fn is_wkt_vlr(vlr: &Vlr) -> bool {
+    vlr.user_id == PROJECTION_USER_ID && vlr.record_id == WKT_RECORD_ID
+}
+
+/// 从 VLR/EVLR 中读取坐标系，WKT 优先，其次为 GeoKey 目录中的 EPSG 代码
+pub fn crs_from_vlrs<'a>(vlrs: impl IntoIterator<Item = &'a Vlr>) -> Option<Crs> {
+    let vlrs: Vec<&Vlr> = vlrs.into_iter().collect();
+    if let Some(vlr) = vlrs.iter().find(|vlr| is_wkt_vlr(vlr)) {
+        let wkt = String::from_utf8_lossy(&vlr.data);
+        let wkt = wkt.trim_end_matches('\0').trim();
+        if !wkt.is_empty() {
+            return Some(Crs::Wkt(wkt.to_string()));
+        }
+    }
+    vlrs.iter()
+        .find(|vlr| {
+            vlr.user_id == PROJECTION_USER_ID && vlr.record_id == GEO_KEY_DIRECTORY_RECORD_ID
+        })
+        .and_then(|vlr| geo_keys_epsg(&vlr.data))
+}
+
+/// 解析 GeoKey 目录：投影坐标系键优先，其次为地理坐标系键
+fn geo_keys_epsg(data: &[u8]) -> Option<u32> {
+    let values: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    let key_count = *values.get(3)? as usize;
+    let keys: Vec<&[u16]> = values.get(4..4 + key_count * 4)?.chunks_exact(4).collect();
+    let value_of = |id: u16| {
+        keys.iter()
+            .find(|key| key[0] == id && key[1] == 0)
+            .map(|key| key[3])
+            .filter(|&value| value != 0 && value != GEO_KEY_USER_DEFINED)
+    };
+    value_of(PROJECTED_CS_TYPE_KEY)
+        .or_else(|| value_of(GEOGRAPHIC_TYPE_KEY))
+        .map(u32::from)
+}
+
+/// 生成坐标系 VLR：WKT 写为 WKT VLR，EPSG 代码写为 GeoKey 目录；ENU 无法表示，返回 None
+pub fn crs_vlr(crs: &Crs) -> Option<Vlr> {
+    match crs {
+        Crs::Wkt(wkt) => {
+            let mut data = wkt.as_bytes().to_vec();
+            data.push(0);
+            Some(Vlr {
+                user_id: PROJECTION_USER_ID.to_string(),
+                record_id: WKT_RECORD_ID,
+                description: "OGC WKT Coordinate System".to_string(),
+                data,
+            })
+        }
+        Crs::Epsg(code) => {
+            let code = u16::try_from(*code).ok()?;
+            // EPSG 4000-4999 多为地理坐标系，4978 为地心坐标系
+            let (model_type, type_key) = match code {
+                4978 => (3, GEOGRAPHIC_TYPE_KEY),
+                4000..=4999 => (2, GEOGRAPHIC_TYPE_KEY),
+                _ => (1, PROJECTED_CS_TYPE_KEY),
+            };
+            let mut keys = vec![
+                [GT_MODEL_TYPE_KEY, 0, 1, model_type],
+                [GT_RASTER_TYPE_KEY, 0, 1, 1],
+                [type_key, 0, 1, code],
+            ];
+            if model_type == 1 {
+                // 米
+                keys.push([PROJ_LINEAR_UNITS_KEY, 0, 1, 9001]);
+            }
+            let header = [1, 1, 0, keys.len() as u16];
+            Some(Vlr {
+                user_id: PROJECTION_USER_ID.to_string(),
+                record_id: GEO_KEY_DIRECTORY_RECORD_ID,
+                description: "GeoTIFF GeoKeyDirectoryTag".to_string(),
+                data: header
+                    .iter()
+                    .chain(keys.iter().flatten())
+                    .flat_map(|v| v.to_le_bytes())
+                    .collect(),
+            })
+        }
+        Crs::Enu { .. } => None,
+    }
+}
+
+/// 点云携带的 LAS 元数据（读取时填充，写出时沿用）
+#[derive(Clone, Debug)]
+pub struct LasMetadata {
+    /// 自定义属性对应的 Extra Bytes 描述符
+    pub extra_bytes: Vec<ExtraBytes>,
+    /// LAS 版本（主版本, 次版本）
+    pub version: (u8, u8),
+    /// 坐标量化比例
+    pub scale: [f64; 3],
+    /// 坐标量化偏移
+    pub offset: [f64; 3],
+    pub system_identifier: String,
+    pub generating_software: String,
+    pub file_source_id: u16,
+    /// GPS 时间为调整后的标准 GPS 时间（否则为 GPS 周秒）
+    pub standard_gps_time: bool,
+    /// 保留的 VLR（坐标系 WKT、GeoKeys 等）
+    pub vlrs: Vec<Vlr>,
+    /// 保留的 EVLR
+    pub evlrs: Vec<Vlr>,
+}
+
+impl Default for LasMetadata {
+    fn default() -> Self {
+        Self {
+            extra_bytes: Vec::new(),
+            version: (1, 4),
+            scale: [0.001; 3],
+            offset: [0.0; 3],
+            system_identifier: String::new(),
+            generating_software: String::new(),
+            file_source_id: 0,
+            standard_gps_time: false,
+            vlrs: Vec::new(),
+            evlrs: Vec::new(),
+        }
+    }
+}
+
+impl LasMetadata {
+    /// 从文件头提取元数据，extra_bytes 为需要保留的描述符
+    pub fn from_header(header: &Header, extra_bytes: Vec<ExtraBytes>) -> Self {
+        let keep = |vlr: &&Vlr| {
+            !REGENERATED_VLRS
+                .iter()
+                .any(|&(user_id, record_id)| vlr.user_id == user_id && vlr.record_id == record_id)
+        };
+        let transforms = header.transforms();
+        let version = header.version();
+        Self {
+            extra_bytes,
+            version: (version.major, version.minor),
+            scale: [transforms.x.scale, transforms.y.scale, transforms.z.scale],
+            offset: [
+                transforms.x.offset,
+                transforms.y.offset,
+                transforms.z.offset,
+            ],
+            system_identifier: header.system_identifier().to_string(),
+            generating_software: header.generating_software().to_string(),
+            file_source_id: header.file_source_id(),
+            standard_gps_time: header.gps_time_type().is_standard(),
+            vlrs: header.vlrs().iter().filter(keep).cloned().collect(),
+            evlrs: header.evlrs().iter().filter(keep).cloned().collect(),
+        }
+    }
+
+    /// GPS 时间类型
+    pub fn gps_time_type(&self) -> GpsTimeType {
+        if self.standard_gps_time {
+            GpsTimeType::Standard
+        } else {
+            GpsTimeType::Week
+        }
+    }
+}
+
+impl HighPerformancePointCloud {
+    /// 获取 LAS 元数据
+    pub fn las_metadata(&self) -> Option<&LasMetadata> {
+        self.las_metadata_ref()
+    }
+
+    /// 设置 LAS 元数据
+    pub fn set_las_metadata(&mut self, metadata: Option<LasMetadata>) {
+        *self.las_metadata_mut() = metadata;
+    }
+
+    /// 声明自定义属性写入 LAS 时的 Extra Bytes 描述符
+    pub fn set_extra_bytes(&mut self, descriptor: ExtraBytes) -> Result<()> {
+        descriptor.check_name()?;
+        if descriptor.description.len() > 32 {
+            return Err(PointCloudError::InvalidParameter(
+                "Extra Bytes描述不能超过32字节".to_string(),
+            ));
+        }
+        if !descriptor.is_scalar() {
+            return Err(PointCloudError::InvalidParameter(
+                "Extra Bytes只支持标量类型（1-10）".to_string(),
+            ));
+        }
+        if descriptor.scale == Some(0.0) {
+            return Err(PointCloudError::InvalidParameter(
+                "Extra Bytes的scale不能为0".to_string(),
+            ));
+        }
+        let metadata = self
+            .las_metadata_mut()
+            .get_or_insert_with(LasMetadata::default);
+        metadata.extra_bytes.retain(|d| d.name != descriptor.name);
+        metadata.extra_bytes.push(descriptor);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_bytes_roundtrip() {
+        let descriptor = ExtraBytes {
+            no_data: Some(65535.0),
+            scale: Some(0.01),
+            offset: Some(-100.0),
+            ..ExtraBytes::with_type("height", "uint16").unwrap()
+        };
+        let vlr = extra_bytes_vlr(&[descriptor.clone(), ExtraBytes::float32("score")]);
+        let parsed = parse_extra_bytes([&vlr]).unwrap();
+        assert_eq!(
+            parsed,
+            vec![descriptor.clone(), ExtraBytes::float32("score")]
+        );
+
+        let mut bytes = Vec::new();
+        descriptor.encode(12.34, &mut bytes);
+        descriptor.encode(f64::NAN, &mut bytes);
+        assert_eq!(bytes.len(), 4);
+        assert!((descriptor.decode(&bytes[..2]) - 12.34).abs() < 1e-3);
+        assert!(descriptor.decode(&bytes[2..]).is_nan());
+        assert_eq!(
+            ExtraBytes {
+                data_type: 23,
+                ..descriptor
+            }
+            .size(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_extra_bytes_name_limit() {
+        // 11 个汉字为 33 字节，不能截断为 32 字节的半个字符
+        let long = "高".repeat(11);
+        assert!(ExtraBytes::float32(&long).check_name().is_err());
+        assert!(ExtraBytes::float32(&"高".repeat(10)).check_name().is_ok());
+        let mut pc = HighPerformancePointCloud::new();
+        assert!(pc.set_extra_bytes(ExtraBytes::float32(&long)).is_err());
+    }
+
+    #[test]
+    fn test_extra_bytes_column_keeps_type() {
+        let descriptor = ExtraBytes::for_attribute("stamp", AttributeType::U64);
+        let stamps = AttributeData::from_vec(vec![u64::MAX - 1, 3]);
+        let bytes = descriptor.encode_column(Some(&stamps), 2);
+        assert_eq!(bytes.len(), 16);
+        match descriptor.decode_column(&bytes) {
+            AttributeData::U64(values) => assert_eq!(values, vec![u64::MAX - 1, 3]),
+            _ => panic!("类型应保持为 uint64"),
+        }
+
+        // 缺失的属性写为无效值，带缩放的整数读取为 float64
+        let scaled = ExtraBytes {
+            no_data: Some(0.0),
+            scale: Some(0.5),
+            ..ExtraBytes::for_attribute("hag", AttributeType::U16)
+        };
+        let values = scaled.decode_column(&scaled.encode_column(None, 1));
+        assert_eq!(values.dtype(), AttributeType::F64);
+        assert!(values.to_f64_vec()[0].is_nan());
+    }
+}
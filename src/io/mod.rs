@@ -1,5 +1,8 @@
 /// 多格式IO模块入口
+pub mod dataset;
 pub mod las_laz;
+pub mod las_meta;
+pub mod potree;
 pub mod table;
 
 use crate::point_cloud::core::HighPerformancePointCloud;
@@ -8,7 +11,7 @@
 
 impl HighPerformancePointCloud {
     /// 根据扩展名自动加载点云
-    /// 支持: .las/.laz/.csv/.parquet/.pq
+    /// 支持: .las/.laz/.csv/.parquet/.pq/.arrow/.feather/.ipc
     pub fn load_from_file(path: &str, columns: Option<table::TableColumnNames>) -> Result<Self> {
         let ext = Path::new(path)
             .extension()
@@ -26,12 +29,16 @@ pub fn load_from_file(path: &str, columns: Option<table::TableColumnNames>) -> R
                 let cols = columns.unwrap_or_default();
                 Self::from_table_parquet(path, cols)
             }
+            "arrow" | "feather" | "ipc" => {
+                let cols = columns.unwrap_or_default();
+                Self::from_table_ipc(path, cols)
+            }
             _ => Err(format!("不支持的文件格式: {}", ext).into()),
         }
     }
 
     /// 根据扩展名自动保存点云
-    /// 支持: .las/.laz/.csv/.parquet/.pq
+    /// 支持: .las/.laz/.csv/.parquet/.pq/.arrow/.feather/.ipc
     pub fn save_to_file(&self, path: &str, columns: Option<table::TableColumnNames>) -> Result<()> {
         let ext = Path::new(path)
             .extension()
@@ -49,6 +56,10 @@ pub fn save_to_file(&self, path: &str, columns: Option<table::TableColumnNames>)
                 let cols = columns.unwrap_or_default();
                 self.to_table_parquet(path, cols)
             }
+            "arrow" | "feather" | "ipc" => {
+                let cols = columns.unwrap_or_default();
+                self.to_table_ipc(path, cols)
+            }
             _ => Err(format!("不支持的文件格式: {}", ext).into()),
         }
     }
@@ -0,0 +1,375 @@
+/// Potree 2.0 格式导出：hierarchy.bin、octree.bin、metadata.json
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::octree::Octree;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// 每个节点的采样网格分辨率（与 PotreeConverter 一致，spacing = 立方体边长 / 128）
+const SAMPLING_GRID: u32 = 128;
+/// hierarchy.bin 中每个节点记录的字节数
+const HIERARCHY_ENTRY_SIZE: usize = 22;
+
+/// Potree 导出参数
+#[derive(Clone, Debug)]
+pub struct PotreeOptions {
+    /// 点云名称（写入 metadata.json）
+    pub name: String,
+    /// 八叉树最大深度
+    pub max_depth: u32,
+    /// 节点点数不超过该值时不再细分
+    pub leaf_size: usize,
+    /// 坐标量化精度
+    pub scale: f64,
+}
+
+impl Default for PotreeOptions {
+    fn default() -> Self {
+        Self {
+            name: "pointcloud".to_string(),
+            max_depth: 12,
+            leaf_size: 20_000,
+            scale: 0.001,
+        }
+    }
+}
+
+/// 单个点属性的编码描述
+struct PotreeAttribute {
+    name: String,
+    kind: &'static str,
+    num_elements: usize,
+    element_size: usize,
+    values: Vec<f32>,
+}
+
+impl PotreeAttribute {
+    fn size(&self) -> usize {
+        self.num_elements * self.element_size
+    }
+
+    /// 写入第 idx 个点的属性值
+    fn encode(&self, idx: usize, out: &mut Vec<u8>) {
+        let values = &self.values[idx * self.num_elements..(idx + 1) * self.num_elements];
+        for &value in values {
+            match self.kind {
+                "uint16" => out.extend_from_slice(&(value as u16).to_le_bytes()),
+                _ => out.extend_from_slice(&value.to_le_bytes()),
+            }
+        }
+    }
+
+    fn range(&self) -> (Vec<f64>, Vec<f64>) {
+        let mut min = vec![f64::INFINITY; self.num_elements];
+        let mut max = vec![f64::NEG_INFINITY; self.num_elements];
+        for chunk in self.values.chunks_exact(self.num_elements) {
+            for (k, &value) in chunk.iter().enumerate() {
+                min[k] = min[k].min(value as f64);
+                max[k] = max[k].max(value as f64);
+            }
+        }
+        (min, max)
+    }
+}
+
+impl HighPerformancePointCloud {
+    /// 导出为 Potree 2.0 格式目录
+    /// 每个点只写入一个节点：内部节点按 spacing 网格抽样，叶节点保存剩余点
+    pub fn to_potree(&self, dir: &str, options: &PotreeOptions) -> Result<()> {
+        if self.point_count() == 0 {
+            return Err("点云为空".into());
+        }
+        if options.scale <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "scale必须大于0".to_string(),
+            ));
+        }
+
+        let root = Path::new(dir);
+        fs::create_dir_all(root).map_err(PointCloudError::IoError)?;
+
+        let octree = Octree::new(self, options.max_depth, options.leaf_size)?;
+        let points = tensor::tensor2_to_points(self.xyz_ref());
+        let attributes = self.potree_attributes();
+        let nodes = octree.nodes();
+        let (cube_min, cube_max) = (nodes[0].min, nodes[0].max);
+        let offset: [f64; 3] = cube_min.map(|v| v as f64);
+
+        // 第一步：自顶向下为每个节点分配点（每个点只属于一个节点）
+        let mut taken = vec![false; points.len()];
+        let mut assigned: Vec<Vec<usize>> = Vec::with_capacity(nodes.len());
+        for (node_id, node) in nodes.iter().enumerate() {
+            let candidates = octree
+                .node_indices(node_id)
+                .iter()
+                .copied()
+                .filter(|&idx| !taken[idx]);
+            let members: Vec<usize> = if node.is_leaf() {
+                candidates.collect()
+            } else {
+                let cell = (node.max[0] - node.min[0]) / SAMPLING_GRID as f32;
+                let mut occupied = HashSet::new();
+                candidates
+                    .filter(|&idx| {
+                        let key: [u32; 3] = std::array::from_fn(|k| {
+                            let cell_index = (points[idx][k] - node.min[k]) / cell;
+                            (cell_index as u32).min(SAMPLING_GRID - 1)
+                        });
+                        occupied.insert(key)
+                    })
+                    .collect()
+            };
+            for &idx in &members {
+                taken[idx] = true;
+            }
+            assigned.push(members);
+        }
+
+        // 第二步：写入 octree.bin，记录每个节点的字节范围
+        let bytes_per_point = 12 + attributes.iter().map(|a| a.size()).sum::<usize>();
+        let mut octree_bin = BufWriter::new(
+            File::create(root.join("octree.bin")).map_err(PointCloudError::IoError)?,
+        );
+        let mut byte_ranges = Vec::with_capacity(nodes.len());
+        let mut byte_offset = 0u64;
+        let mut record = Vec::with_capacity(bytes_per_point);
+        for members in &assigned {
+            for &idx in members {
+                record.clear();
+                for (&value, &origin) in points[idx].iter().zip(&offset) {
+                    let q = ((value as f64 - origin) / options.scale).round() as i32;
+                    record.extend_from_slice(&q.to_le_bytes());
+                }
+                for attribute in &attributes {
+                    attribute.encode(idx, &mut record);
+                }
+                octree_bin
+                    .write_all(&record)
+                    .map_err(PointCloudError::IoError)?;
+            }
+            let byte_size = (members.len() * bytes_per_point) as u64;
+            byte_ranges.push((byte_offset, byte_size));
+            byte_offset += byte_size;
+        }
+        octree_bin.flush().map_err(PointCloudError::IoError)?;
+
+        // 第三步：写入 hierarchy.bin（单个块，节点按层序排列）
+        let mut hierarchy = Vec::with_capacity(nodes.len() * HIERARCHY_ENTRY_SIZE);
+        for (node, (members, &(offset, size))) in
+            nodes.iter().zip(assigned.iter().zip(&byte_ranges))
+        {
+            let child_mask = node
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(_, child)| child.is_some())
+                .fold(0u8, |mask, (octant, _)| mask | (1 << octant));
+            // 节点类型：0 为内部节点，1 为叶节点
+            hierarchy.push(u8::from(node.is_leaf()));
+            hierarchy.push(child_mask);
+            hierarchy.extend_from_slice(&(members.len() as u32).to_le_bytes());
+            hierarchy.extend_from_slice(&offset.to_le_bytes());
+            hierarchy.extend_from_slice(&size.to_le_bytes());
+        }
+        fs::write(root.join("hierarchy.bin"), &hierarchy).map_err(PointCloudError::IoError)?;
+
+        // 第四步：写入 metadata.json
+        // 量化以局部坐标计算，元数据中的坐标范围、偏移与包围盒加上点云原点还原为全局坐标
+        let origin = self.origin();
+        let to_global =
+            |local: [f32; 3]| -> [f64; 3] { std::array::from_fn(|k| local[k] as f64 + origin[k]) };
+        let (pos_min, pos_max) = point_range(&points);
+        let (pos_min, pos_max) = (to_global(pos_min), to_global(pos_max));
+        let mut attribute_meta = vec![json!({
+            "name": "position",
+            "description": "",
+            "size": 12,
+            "numElements": 3,
+            "elementSize": 4,
+            "type": "int32",
+            "min": pos_min,
+            "max": pos_max,
+        })];
+        for attribute in &attributes {
+            let (min, max) = attribute.range();
+            attribute_meta.push(json!({
+                "name": attribute.name,
+                "description": "",
+                "size": attribute.size(),
+                "numElements": attribute.num_elements,
+                "elementSize": attribute.element_size,
+                "type": attribute.kind,
+                "min": min,
+                "max": max,
+            }));
+        }
+
+        let spacing = (cube_max[0] - cube_min[0]) as f64 / SAMPLING_GRID as f64;
+        let metadata = json!({
+            "version": "2.0",
+            "name": options.name,
+            "description": "",
+            "points": points.len(),
+            "projection": self.crs().map(|crs| crs.to_string()).unwrap_or_default(),
+            "hierarchy": {
+                "firstChunkSize": hierarchy.len(),
+                "stepSize": 4,
+                "depth": octree.depth(),
+            },
+            "offset": to_global(cube_min),
+            "scale": [options.scale; 3],
+            "spacing": spacing,
+            "boundingBox": {
+                "min": to_global(cube_min),
+                "max": to_global(cube_max),
+            },
+            "encoding": "DEFAULT",
+            "attributes": attribute_meta,
+        });
+        let text = serde_json::to_string_pretty(&metadata).map_err(|e| e.to_string())?;
+        fs::write(root.join("metadata.json"), text).map_err(PointCloudError::IoError)?;
+
+        Ok(())
+    }
+
+    /// 收集需要导出的属性（intensity、rgb 与自定义属性）
+    fn potree_attributes(&self) -> Vec<PotreeAttribute> {
+        let mut attributes = Vec::new();
+
+        // intensity 与 to_las 一致，按 [0,1] 映射到 uint16
+        if let Some(intensity) = self.intensity_ref() {
+            let values = tensor::tensor1_to_vec(intensity)
+                .into_iter()
+                .map(|v| (v * 65535.0).clamp(0.0, 65535.0))
+                .collect();
+            attributes.push(PotreeAttribute {
+                name: "intensity".to_string(),
+                kind: "uint16",
+                num_elements: 1,
+                element_size: 2,
+                values,
+            });
+        }
+
+        let (r_ref, g_ref, b_ref) = self.rgb_channels_ref();
+        if let (Some(r), Some(g), Some(b)) = (r_ref, g_ref, b_ref) {
+            let (r, g, b) = (
+                tensor::tensor1_to_u8_vec(r),
+                tensor::tensor1_to_u8_vec(g),
+                tensor::tensor1_to_u8_vec(b),
+            );
+            let values = (0..r.len())
+                .flat_map(|i| [r[i], g[i], b[i]].map(|c| ((c as u16) << 8) as f32))
+                .collect();
+            attributes.push(PotreeAttribute {
+                name: "rgb".to_string(),
+                kind: "uint16",
+                num_elements: 3,
+                element_size: 2,
+                values,
+            });
+        }
+
+        let mut names: Vec<&String> = self.attributes_ref().keys().collect();
+        names.sort();
+        for name in names {
+            let data = &self.attributes_ref()[name];
+            attributes.push(PotreeAttribute {
+                name: name.clone(),
+                kind: "float",
+                num_elements: data.width(),
+                element_size: 4,
+                values: data.to_f32_vec(),
+            });
+        }
+
+        attributes
+    }
+}
+
+fn point_range(points: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for p in points {
+        for (k, &value) in p.iter().enumerate() {
+            min[k] = min[k].min(value);
+            max[k] = max[k].max(value);
+        }
+    }
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point_cloud::crs::Crs;
+    use crate::traits::PointCloudProperties;
+
+    #[test]
+    fn test_potree_export_layout() {
+        let xyz: Vec<Vec<f32>> = (0..1000)
+            .map(|i| vec![(i % 10) as f32, ((i / 10) % 10) as f32, (i / 100) as f32])
+            .collect();
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        pc.set_intensity(vec![0.5; 1000]).unwrap();
+        pc.add_attribute("score".to_string(), vec![1.0; 1000])
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("potree_layout_{}", std::process::id()));
+        let options = PotreeOptions {
+            leaf_size: 100,
+            ..Default::default()
+        };
+        pc.to_potree(dir.to_str().unwrap(), &options).unwrap();
+
+        // position(12) + intensity(2) + score(4)
+        let octree_len = fs::metadata(dir.join("octree.bin")).unwrap().len();
+        assert_eq!(octree_len, 1000 * 18);
+        let hierarchy_len = fs::metadata(dir.join("hierarchy.bin")).unwrap().len();
+        assert_eq!(hierarchy_len % HIERARCHY_ENTRY_SIZE as u64, 0);
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("metadata.json")).unwrap()).unwrap();
+        assert_eq!(metadata["points"], 1000);
+        assert_eq!(metadata["hierarchy"]["firstChunkSize"], hierarchy_len);
+        assert_eq!(metadata["projection"], "");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_potree_global_range() {
+        // UTM 量级的点云，position 的范围与包围盒、偏移同为全局坐标
+        let points: Vec<[f64; 3]> = (0..200)
+            .map(|i| {
+                [
+                    500_000.0 + (i % 10) as f64,
+                    4_000_000.0 + (i / 10) as f64,
+                    5.0,
+                ]
+            })
+            .collect();
+        let mut pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        pc.set_crs(Some(Crs::Epsg(32633)));
+
+        let dir = std::env::temp_dir().join(format!("potree_global_{}", std::process::id()));
+        pc.to_potree(dir.to_str().unwrap(), &PotreeOptions::default())
+            .unwrap();
+
+        let metadata: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("metadata.json")).unwrap()).unwrap();
+        let position = &metadata["attributes"][0];
+        assert_eq!(position["min"][0], 500_000.0);
+        assert_eq!(position["max"][1], 4_000_019.0);
+        let bbox_min = metadata["boundingBox"]["min"][0].as_f64().unwrap();
+        assert!(bbox_min <= 500_000.0 && bbox_min > 499_999.0);
+        assert_eq!(metadata["projection"], "EPSG:32633");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
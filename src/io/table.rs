@@ -1,9 +1,20 @@
 /// 表格IO：使用 polars 统一 CSV/Parquet 读写
+use crate::io::dataset;
+use crate::point_cloud::attribute_data::{component_name, match_attribute, AttributeData};
 use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::crs::Crs;
 use crate::traits::{PointCloudCore, PointCloudProperties};
 use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+use parquet::file::reader::{FileReader, SerializedFileReader};
 use polars::prelude::*;
+use serde_json::Value;
 use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// 表格文件元数据中记录坐标系的键
+const CRS_KEY: &str = "crs";
 
 #[derive(Clone, Debug)]
 pub struct TableColumnNames {
@@ -14,6 +25,13 @@ pub struct TableColumnNames {
     pub rgb_r: Option<String>,
     pub rgb_g: Option<String>,
     pub rgb_b: Option<String>,
+    /// 按原数据类型读写的自定义属性列，None 时读取不含属性、写出全部属性
+    /// 多维属性在 Parquet 中为列表列，在 CSV 中为 name_0..name_{D-1} 分量列
+    pub attributes: Option<Vec<String>>,
+    /// 读取时把坐标、强度、颜色以外的全部数值列作为属性（attributes 为 None 时生效）
+    pub all_attributes: bool,
+    /// 读写时排除的属性
+    pub exclude_attributes: Vec<String>,
 }
 
 impl Default for TableColumnNames {
@@ -26,6 +44,9 @@ fn default() -> Self {
             rgb_r: Some("r".to_string()),
             rgb_g: Some("g".to_string()),
             rgb_b: Some("b".to_string()),
+            attributes: None,
+            all_attributes: false,
+            exclude_attributes: Vec::new(),
         }
     }
 }
@@ -64,6 +85,181 @@ pub fn resolve(
         }
         cols
     }
+
+    /// 指定自定义属性列，None 时保持不变
+    pub fn with_attributes(mut self, attributes: Option<Vec<String>>) -> Self {
+        if attributes.is_some() {
+            self.attributes = attributes;
+        }
+        self
+    }
+
+    /// 读取全部数值列作为属性，exclude 为排除的属性（None 时保持不变）
+    pub fn with_all_attributes(mut self, all: bool, exclude: Option<Vec<String>>) -> Self {
+        self.all_attributes = all;
+        if let Some(exclude) = exclude {
+            self.exclude_attributes = exclude;
+        }
+        self
+    }
+
+    /// 坐标、强度与颜色占用的列名
+    fn reserved_columns(&self) -> Vec<&str> {
+        let mut reserved = vec![self.x.as_str(), self.y.as_str(), self.z.as_str()];
+        reserved.extend(
+            [&self.intensity, &self.rgb_r, &self.rgb_g, &self.rgb_b]
+                .into_iter()
+                .flatten()
+                .map(String::as_str),
+        );
+        reserved
+    }
+
+    /// 读取为属性的列：列出的属性，或全部模式下其余的数值/布尔/列表列
+    fn read_attributes(&self, schema: &Schema) -> Vec<String> {
+        let names = match &self.attributes {
+            Some(names) => names.clone(),
+            None if self.all_attributes => {
+                let reserved = self.reserved_columns();
+                schema
+                    .iter()
+                    .filter(|&(_, dtype)| is_attribute_dtype(dtype))
+                    .map(|(name, _)| name.to_string())
+                    .filter(|name| !reserved.contains(&name.as_str()))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        self.without_excluded(names)
+    }
+
+    /// 写出的属性：列出的属性，或按名称排序的全部属性（跳过与坐标、强度、颜色重名的属性）
+    fn write_attributes(&self, pc: &HighPerformancePointCloud) -> Vec<String> {
+        let names = match &self.attributes {
+            Some(names) => names.clone(),
+            None => {
+                let reserved = self.reserved_columns();
+                let mut names: Vec<String> = pc
+                    .attribute_names()
+                    .into_iter()
+                    .filter(|name| !reserved.contains(&name.as_str()))
+                    .collect();
+                names.sort();
+                names
+            }
+        };
+        self.without_excluded(names)
+    }
+
+    /// 扫描时读取的列：坐标、存在的强度与颜色列，以及所选属性列（含多维属性的分量列）
+    fn projected_columns(&self, schema: &Schema) -> Vec<String> {
+        let reserved = self.reserved_columns();
+        let mut names: Vec<String> = reserved
+            .iter()
+            .enumerate()
+            .filter(|&(k, name)| k < 3 || schema.contains(name))
+            .map(|(_, name)| name.to_string())
+            .collect();
+        for name in self.read_attributes(schema) {
+            if names.contains(&name) {
+                continue;
+            }
+            if schema.contains(&name) {
+                names.push(name);
+            } else {
+                names.extend(
+                    (0..)
+                        .map(|index| component_name(&name, index))
+                        .take_while(|column| schema.contains(column)),
+                );
+            }
+        }
+        names
+    }
+
+    fn without_excluded(&self, names: Vec<String>) -> Vec<String> {
+        names
+            .into_iter()
+            .filter(|name| !self.exclude_attributes.contains(name))
+            .collect()
+    }
+}
+
+/// 属性列过滤条件
+#[derive(Clone, Debug)]
+pub enum ColumnFilter {
+    /// 闭区间 [min, max]，None 表示不限
+    Range {
+        column: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// 等于任一取值
+    Values { column: String, values: Vec<f64> },
+}
+
+impl ColumnFilter {
+    fn expr(&self) -> Expr {
+        match self {
+            ColumnFilter::Range { column, min, max } => {
+                let mut expr = lit(true);
+                if let Some(min) = min {
+                    expr = expr.and(col(column.as_str()).gt_eq(lit(*min)));
+                }
+                if let Some(max) = max {
+                    expr = expr.and(col(column.as_str()).lt_eq(lit(*max)));
+                }
+                expr
+            }
+            ColumnFilter::Values { column, values } => {
+                values.iter().fold(lit(false), |expr, &v| {
+                    expr.or(col(column.as_str()).eq(lit(v)))
+                })
+            }
+        }
+    }
+}
+
+/// Parquet 延迟扫描条件：包围盒与属性过滤下推到扫描，limit 限制读取行数
+#[derive(Clone, Debug, Default)]
+pub struct ParquetScan {
+    /// 包围盒（全局坐标）：[min_x, min_y, max_x, max_y] 只过滤 XY，
+    /// [min_x, min_y, min_z, max_x, max_y, max_z] 过滤 XYZ
+    pub bbox: Option<Vec<f64>>,
+    pub filters: Vec<ColumnFilter>,
+    pub limit: Option<usize>,
+}
+
+impl ParquetScan {
+    /// 过滤谓词，无条件时返回 None
+    fn predicate(&self, columns: &TableColumnNames) -> Result<Option<Expr>> {
+        let mut predicates: Vec<Expr> = Vec::new();
+        if let Some(bbox) = &self.bbox {
+            let dims = match bbox.len() {
+                4 => 2,
+                6 => 3,
+                n => {
+                    return Err(PointCloudError::InvalidParameter(format!(
+                        "bbox必须是4个（XY）或6个（XYZ）数值，实际为{}个",
+                        n
+                    )))
+                }
+            };
+            let axes = [&columns.x, &columns.y, &columns.z];
+            for (k, axis) in axes.iter().take(dims).enumerate() {
+                predicates.push(
+                    ColumnFilter::Range {
+                        column: axis.to_string(),
+                        min: Some(bbox[k]),
+                        max: Some(bbox[dims + k]),
+                    }
+                    .expr(),
+                );
+            }
+        }
+        predicates.extend(self.filters.iter().map(ColumnFilter::expr));
+        Ok(predicates.into_iter().reduce(Expr::and))
+    }
 }
 
 impl HighPerformancePointCloud {
@@ -83,11 +279,92 @@ pub fn from_table_parquet(path: &str, columns: TableColumnNames) -> Result<Self>
         let df = ParquetReader::new(file)
             .finish()
             .map_err(|e| PointCloudError::ParseError(e.to_string()))?;
-        from_dataframe(df, columns)
+        let mut pc = from_dataframe(df, columns)?;
+        pc.set_crs(parquet_crs(path));
+        Ok(pc)
+    }
+
+    /// 读取 Arrow IPC 文件（Feather v2），列参数同 from_table_parquet
+    /// 坐标系取自 schema 元数据的 "crs" 键
+    pub fn from_table_ipc(path: &str, columns: TableColumnNames) -> Result<Self> {
+        let file = File::open(path).map_err(PointCloudError::IoError)?;
+        let mut reader = IpcReader::new(file);
+        let crs = reader
+            .custom_metadata()
+            .map_err(|e| PointCloudError::ParseError(e.to_string()))?
+            .and_then(|metadata| {
+                let text = metadata.get(CRS_KEY)?;
+                Crs::parse(text).ok()
+            });
+        let df = reader
+            .finish()
+            .map_err(|e| PointCloudError::ParseError(e.to_string()))?;
+        let mut pc = from_dataframe(df, columns)?;
+        pc.set_crs(crs);
+        Ok(pc)
+    }
+
+    /// 延迟扫描 Parquet 文件、glob 模式或目录（递归读取其中的 .parquet 文件）
+    /// 只读取坐标、强度、颜色与所选属性列，过滤条件下推到扫描
+    /// 目录含 _dataset.json 索引（to_parquet_dataset 写出）时按瓦片包围盒裁剪，只扫描相交的瓦片
+    pub fn scan_table_parquet(
+        path: &str,
+        mut columns: TableColumnNames,
+        scan: &ParquetScan,
+    ) -> Result<Self> {
+        let parse_err = |e: PolarsError| PointCloudError::ParseError(e.to_string());
+        let predicate = scan.predicate(&columns)?;
+        let root = Path::new(path);
+        let (sources, crs) = if root.join(dataset::DATASET_INDEX).is_file() {
+            let (tiles, crs) = dataset::dataset_tiles(root, scan.bbox.as_deref())?;
+            // 瓦片文件所在的 Hive 分区目录不作为属性
+            columns
+                .exclude_attributes
+                .extend(dataset::PARTITION_COLUMNS.map(str::to_string));
+            (tiles, crs.and_then(|text| Crs::parse(&text).ok()))
+        } else if root.is_dir() {
+            let pattern = format!("{}/**/*.parquet", path.trim_end_matches('/'));
+            (vec![pattern], None)
+        } else if root.is_file() {
+            (vec![path.to_string()], parquet_crs(path))
+        } else {
+            (vec![path.to_string()], None)
+        };
+        if sources.is_empty() {
+            let mut pc = Self::new();
+            pc.set_crs(crs);
+            return Ok(pc);
+        }
+
+        let frames = sources
+            .iter()
+            .map(|source| LazyFrame::scan_parquet(PlPath::new(source), ScanArgsParquet::default()))
+            .collect::<PolarsResult<Vec<_>>>()
+            .map_err(parse_err)?;
+        let mut lf = concat(frames, UnionArgs::default()).map_err(parse_err)?;
+        let schema = lf.collect_schema().map_err(parse_err)?;
+
+        if let Some(predicate) = predicate {
+            lf = lf.filter(predicate);
+        }
+        let projection: Vec<Expr> = columns
+            .projected_columns(&schema)
+            .iter()
+            .map(|name| col(name.as_str()))
+            .collect();
+        lf = lf.select(projection);
+        if let Some(limit) = scan.limit {
+            lf = lf.limit(IdxSize::try_from(limit).unwrap_or(IdxSize::MAX));
+        }
+
+        let df = lf.collect().map_err(parse_err)?;
+        let mut pc = from_dataframe(df, columns)?;
+        pc.set_crs(crs);
+        Ok(pc)
     }
 
     pub fn to_table_csv(&self, path: &str, delimiter: u8, columns: TableColumnNames) -> Result<()> {
-        let mut df = to_dataframe(self, columns)?;
+        let mut df = to_dataframe(self, columns, true)?;
         let file = File::create(path).map_err(PointCloudError::IoError)?;
         CsvWriter::new(file)
             .include_header(true)
@@ -97,20 +374,41 @@ pub fn to_table_csv(&self, path: &str, delimiter: u8, columns: TableColumnNames)
         Ok(())
     }
 
+    /// 坐标系写入文件键值元数据的 "crs" 键
     pub fn to_table_parquet(&self, path: &str, columns: TableColumnNames) -> Result<()> {
-        let mut df = to_dataframe(self, columns)?;
+        let mut df = to_dataframe(self, columns, false)?;
         let file = File::create(path).map_err(PointCloudError::IoError)?;
         ParquetWriter::new(file)
+            .with_key_value_metadata(crs_metadata(self))
+            .finish(&mut df)
+            .map_err(|e| PointCloudError::ParseError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 写出 Arrow IPC 文件（Feather v2），多维属性为 float32 列表列
+    /// 坐标系写入 schema 元数据的 "crs" 键
+    pub fn to_table_ipc(&self, path: &str, columns: TableColumnNames) -> Result<()> {
+        let mut df = to_dataframe(self, columns, false)?;
+        let file = File::create(path).map_err(PointCloudError::IoError)?;
+        let mut writer = IpcWriter::new(file);
+        if let Some(crs) = self.crs() {
+            let metadata = [(PlSmallStr::from_str(CRS_KEY), crs.to_string().into())];
+            writer.set_custom_schema_metadata(Arc::new(metadata.into_iter().collect()));
+        }
+        writer
             .finish(&mut df)
             .map_err(|e| PointCloudError::ParseError(e.to_string()))?;
         Ok(())
     }
 }
 
-fn from_dataframe(df: DataFrame, columns: TableColumnNames) -> Result<HighPerformancePointCloud> {
-    let x = get_f32_col(&df, &columns.x)?;
-    let y = get_f32_col(&df, &columns.y)?;
-    let z = get_f32_col(&df, &columns.z)?;
+pub(crate) fn from_dataframe(
+    df: DataFrame,
+    columns: TableColumnNames,
+) -> Result<HighPerformancePointCloud> {
+    let x = get_f64_col(&df, &columns.x)?;
+    let y = get_f64_col(&df, &columns.y)?;
+    let z = get_f64_col(&df, &columns.z)?;
 
     if x.len() != y.len() || x.len() != z.len() {
         return Err(PointCloudError::DimensionMismatch {
@@ -119,12 +417,13 @@ fn from_dataframe(df: DataFrame, columns: TableColumnNames) -> Result<HighPerfor
         });
     }
 
-    let mut xyz = Vec::with_capacity(x.len());
-    for i in 0..x.len() {
-        xyz.push(vec![x[i], y[i], z[i]]);
-    }
-
-    let mut pc = HighPerformancePointCloud::from_xyz(xyz)?;
+    // 坐标按 f64 读取，大坐标自动设置原点保持精度；过滤后没有行时得到空点云
+    let xyz: Vec<[f64; 3]> = (0..x.len()).map(|i| [x[i], y[i], z[i]]).collect();
+    let mut pc = if xyz.is_empty() {
+        HighPerformancePointCloud::new()
+    } else {
+        HighPerformancePointCloud::from_global_points(&xyz, None)?
+    };
 
     if let Some(intensity_name) = &columns.intensity {
         if df.column(intensity_name).is_ok() {
@@ -149,32 +448,40 @@ fn from_dataframe(df: DataFrame, columns: TableColumnNames) -> Result<HighPerfor
         }
     }
 
+    // 没有同名列时按 name_0..name_{D-1} 分量列读取多维属性
+    for name in &columns.read_attributes(df.schema()) {
+        let data = if df.column(name).is_ok() {
+            get_attribute_col(&df, name)?
+        } else {
+            get_vector_cols(&df, name)?
+        };
+        pc.set_attribute_data(name.clone(), data)?;
+    }
+
     Ok(pc)
 }
 
-fn to_dataframe(
+/// split_vectors: 多维属性拆分为分量列（CSV 不支持列表列）
+pub(crate) fn to_dataframe(
     pc: &HighPerformancePointCloud,
     column_names: TableColumnNames,
+    split_vectors: bool,
 ) -> Result<DataFrame> {
-    let xyz = pc.get_xyz();
-    let mut x = Vec::with_capacity(xyz.len());
-    let mut y = Vec::with_capacity(xyz.len());
-    let mut z = Vec::with_capacity(xyz.len());
-    for point in xyz {
-        x.push(point[0]);
-        y.push(point[1]);
-        z.push(point[2]);
-    }
-
-    let mut columns: Vec<Column> = vec![
-        Column::new(PlSmallStr::from_str(&column_names.x), x),
-        Column::new(PlSmallStr::from_str(&column_names.y), y),
-        Column::new(PlSmallStr::from_str(&column_names.z), z),
-    ];
+    let xyz_names = [&column_names.x, &column_names.y, &column_names.z];
+    // 坐标列始终按 f64 写出全局坐标，列类型不随原点变化
+    let xyz = pc.global_points();
+    let mut columns: Vec<Column> = xyz_names
+        .iter()
+        .enumerate()
+        .map(|(k, name)| {
+            let values: Vec<f64> = xyz.iter().map(|p| p[k]).collect();
+            Series::from_vec(PlSmallStr::from_str(name), values).into()
+        })
+        .collect();
 
     if let Some(name) = &column_names.intensity {
         if let Some(intensity) = pc.get_intensity() {
-            columns.push(Column::new(PlSmallStr::from_str(name), intensity));
+            columns.push(Series::from_vec(PlSmallStr::from_str(name), intensity).into());
         }
     }
 
@@ -187,9 +494,23 @@ fn to_dataframe(
             let r_u32: Vec<u32> = r.into_iter().map(|v| v as u32).collect();
             let g_u32: Vec<u32> = g.into_iter().map(|v| v as u32).collect();
             let b_u32: Vec<u32> = b.into_iter().map(|v| v as u32).collect();
-            columns.push(Column::new(PlSmallStr::from_str(rn), r_u32));
-            columns.push(Column::new(PlSmallStr::from_str(gn), g_u32));
-            columns.push(Column::new(PlSmallStr::from_str(bn), b_u32));
+            columns.push(Series::from_vec(PlSmallStr::from_str(rn), r_u32).into());
+            columns.push(Series::from_vec(PlSmallStr::from_str(gn), g_u32).into());
+            columns.push(Series::from_vec(PlSmallStr::from_str(bn), b_u32).into());
+        }
+    }
+
+    for name in &column_names.write_attributes(pc) {
+        let data = pc
+            .attribute(name)
+            .ok_or_else(|| format!("属性'{}'不存在", name))?;
+        match data {
+            AttributeData::Vector(_) if split_vectors => {
+                for (index, component) in data.components().iter().enumerate() {
+                    columns.push(attribute_column(&component_name(name, index), component));
+                }
+            }
+            _ => columns.push(attribute_column(name, data)),
         }
     }
 
@@ -197,10 +518,251 @@ fn to_dataframe(
     DataFrame::new(columns).map_err(|e| PointCloudError::ParseError(e.to_string()))
 }
 
-fn get_f32_col(df: &DataFrame, name: &str) -> Result<Vec<f32>> {
-    let series = df
+/// Parquet 键值元数据中的坐标系（写出 "crs" 文本，点云未设置坐标系时不写）
+pub(crate) fn crs_metadata(pc: &HighPerformancePointCloud) -> Option<KeyValueMetadata> {
+    let crs = pc.crs()?;
+    Some(KeyValueMetadata::from_static(vec![(
+        CRS_KEY.to_string(),
+        crs.to_string(),
+    )]))
+}
+
+/// 从 Parquet 键值元数据读取坐标系：GeoParquet 的 "geo" 优先，其次为 "crs" 文本
+fn parquet_crs(path: &str) -> Option<Crs> {
+    let file = File::open(path).ok()?;
+    let reader = SerializedFileReader::new(file).ok()?;
+    let metadata = reader.metadata().file_metadata().key_value_metadata()?;
+    let value_of = |key: &str| {
+        metadata
+            .iter()
+            .find(|kv| kv.key == key)
+            .and_then(|kv| kv.value.as_deref())
+    };
+    if let Some(geo) = value_of("geo") {
+        return geoparquet_crs(geo);
+    }
+    value_of(CRS_KEY).and_then(|text| Crs::parse(text).ok())
+}
+
+/// GeoParquet 主几何列的坐标系
+/// 缺省为 OGC:CRS84（经纬度，按 EPSG:4326 处理），null 表示未知，PROJJSON 取其 EPSG 标识
+fn geoparquet_crs(geo: &str) -> Option<Crs> {
+    let geo: Value = serde_json::from_str(geo).ok()?;
+    let primary = geo.get("primary_column")?.as_str()?;
+    let column = geo.get("columns")?.get(primary)?;
+    match column.get("crs") {
+        None => Some(Crs::Epsg(4326)),
+        Some(Value::Null) => None,
+        Some(Value::String(text)) => Crs::parse(text).ok(),
+        Some(projjson) => {
+            let id = projjson.get("id")?;
+            let code = id.get("code")?;
+            let code = code
+                .as_u64()
+                .map(|c| c.to_string())
+                .or_else(|| code.as_str().map(str::to_string))?;
+            match (id.get("authority")?.as_str()?, code.as_str()) {
+                ("OGC", "CRS84") => Some(Crs::Epsg(4326)),
+                ("EPSG", code) => code.parse().ok().map(Crs::Epsg),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// 自定义属性 -> 同类型的列，多维属性为 float32 列表列
+fn attribute_column(name: &str, data: &AttributeData) -> Column {
+    let name = PlSmallStr::from_str(name);
+    match_attribute!(
+        data,
+        t => Series::from_vec(name, tensor::tensor1_to_vec(t)).into(),
+        m => {
+            let (rows, cols) = (tensor::tensor2_rows(m), tensor::tensor2_cols(m));
+            let flat = tensor::tensor2_to_flat(m);
+            let mut builder = ListPrimitiveChunkedBuilder::<Float32Type>::new(
+                name,
+                rows,
+                flat.len(),
+                DataType::Float32,
+            );
+            for row in 0..rows {
+                builder.append_slice(&flat[row * cols..(row + 1) * cols]);
+            }
+            builder.finish().into_series().into()
+        },
+        values => Column::new(name, values.as_slice())
+    )
+}
+
+/// 按名称取列，含空值的列报错（空值无法映射为坐标、颜色或属性）
+fn non_null_column<'a>(df: &'a DataFrame, name: &str) -> Result<&'a Column> {
+    let column = df
         .column(name)
         .map_err(|_| PointCloudError::ParseError(format!("缺少列: {}", name)))?;
+    if column.null_count() > 0 {
+        return Err(PointCloudError::ParseError(format!("列{}包含空值", name)));
+    }
+    Ok(column)
+}
+
+/// 按列的数据类型读取自定义属性，布尔、整数与浮点列保持原类型
+fn get_attribute_col(df: &DataFrame, name: &str) -> Result<AttributeData> {
+    let series = non_null_column(df, name)?;
+    let parse_err = |e: PolarsError| PointCloudError::ParseError(e.to_string());
+    let data = match series.dtype() {
+        DataType::Boolean => AttributeData::Bool(
+            series
+                .bool()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::UInt8 => AttributeData::U8(
+            series
+                .u8()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::Int8 => AttributeData::I8(
+            series
+                .i8()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::UInt16 => AttributeData::U16(
+            series
+                .u16()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::Int16 => AttributeData::I16(
+            series
+                .i16()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::UInt32 => AttributeData::U32(
+            series
+                .u32()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::Int32 => AttributeData::I32(
+            series
+                .i32()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::UInt64 => AttributeData::U64(
+            series
+                .u64()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::Int64 => AttributeData::I64(
+            series
+                .i64()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::Float32 => AttributeData::from_vec(
+            series
+                .f32()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect::<Vec<f32>>(),
+        ),
+        DataType::Float64 => AttributeData::F64(
+            series
+                .f64()
+                .map_err(parse_err)?
+                .into_no_null_iter()
+                .collect(),
+        ),
+        DataType::List(_) => {
+            // 整列转换一次，再按偏移量读取扁平的内层值
+            let series = series
+                .cast(&DataType::List(Box::new(DataType::Float32)))
+                .map_err(parse_err)?;
+            let list = series.list().map_err(parse_err)?.rechunk();
+            let offsets = list.offsets().map_err(parse_err)?;
+            let width = offsets.lengths().next().unwrap_or(0);
+            if offsets.lengths().any(|len| len != width) {
+                return Err(PointCloudError::ParseError(format!(
+                    "列{}的列表长度不一致",
+                    name
+                )));
+            }
+            let start = *offsets.first();
+            let inner = list
+                .get_inner()
+                .slice(start, (*offsets.last() - start) as usize);
+            let values = inner.f32().map_err(parse_err)?;
+            if values.null_count() > 0 {
+                return Err(PointCloudError::ParseError(format!("列{}包含空值", name)));
+            }
+            let flat: Vec<f32> = values.into_no_null_iter().collect();
+            AttributeData::Vector(tensor::tensor2_from_slice(&flat, list.len(), width)?)
+        }
+        dtype => {
+            return Err(PointCloudError::ParseError(format!(
+                "列{}的类型{}不能作为属性",
+                name, dtype
+            )))
+        }
+    };
+    Ok(data)
+}
+
+/// 可作为属性读取的列类型
+fn is_attribute_dtype(dtype: &DataType) -> bool {
+    matches!(
+        dtype,
+        DataType::Boolean
+            | DataType::UInt8
+            | DataType::Int8
+            | DataType::UInt16
+            | DataType::Int16
+            | DataType::UInt32
+            | DataType::Int32
+            | DataType::UInt64
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+    ) || matches!(dtype, DataType::List(inner) if inner.is_primitive_numeric())
+}
+
+/// 按 name_0..name_{D-1} 分量列读取 float32 多维属性
+fn get_vector_cols(df: &DataFrame, name: &str) -> Result<AttributeData> {
+    let components: Vec<Vec<f32>> = (0..)
+        .map(|index| component_name(name, index))
+        .take_while(|column| df.column(column).is_ok())
+        .map(|column| get_f32_col(df, &column))
+        .collect::<Result<_>>()?;
+    if components.is_empty() {
+        return Err(PointCloudError::ParseError(format!("缺少列: {}", name)));
+    }
+    let rows = df.height();
+    let flat: Vec<f32> = (0..rows)
+        .flat_map(|row| components.iter().map(move |column| column[row]))
+        .collect();
+    Ok(AttributeData::Vector(tensor::tensor2_from_slice(
+        &flat,
+        rows,
+        components.len(),
+    )?))
+}
+
+fn get_f32_col(df: &DataFrame, name: &str) -> Result<Vec<f32>> {
+    let series = non_null_column(df, name)?;
     if let Ok(col) = series.f32() {
         return Ok(col.into_no_null_iter().collect());
     }
@@ -216,16 +778,35 @@ fn get_f32_col(df: &DataFrame, name: &str) -> Result<Vec<f32>> {
     Err(PointCloudError::ParseError(format!("列{}类型不支持", name)))
 }
 
+fn get_f64_col(df: &DataFrame, name: &str) -> Result<Vec<f64>> {
+    let series = non_null_column(df, name)?;
+    if let Ok(col) = series.f64() {
+        return Ok(col.into_no_null_iter().collect());
+    }
+    if let Ok(col) = series.f32() {
+        return Ok(col.into_no_null_iter().map(|v| v as f64).collect());
+    }
+    if let Ok(col) = series.i64() {
+        return Ok(col.into_no_null_iter().map(|v| v as f64).collect());
+    }
+    if let Ok(col) = series.i32() {
+        return Ok(col.into_no_null_iter().map(|v| v as f64).collect());
+    }
+    Err(PointCloudError::ParseError(format!("列{}类型不支持", name)))
+}
+
 fn get_u8_col(df: &DataFrame, name: &str) -> Result<Vec<u8>> {
-    let series = df
-        .column(name)
-        .map_err(|_| PointCloudError::ParseError(format!("缺少列: {}", name)))?;
+    let series = non_null_column(df, name)?;
     if let Ok(col) = series.u8() {
         return Ok(col.into_no_null_iter().collect());
     }
     if let Ok(col) = series.u16() {
         return Ok(col.into_no_null_iter().map(|v| (v >> 8) as u8).collect());
     }
+    // to_dataframe 按 uint32 写出颜色
+    if let Ok(col) = series.u32() {
+        return Ok(col.into_no_null_iter().map(|v| v.min(255) as u8).collect());
+    }
     if let Ok(col) = series.i64() {
         return Ok(col
             .into_no_null_iter()
@@ -5,9 +5,19 @@
 mod traits;
 mod utils;
 
+use interop::dlpack::HostTensor;
+use io::las_laz::{LasChunkReader, LasStreamWriter, LasWriteOptions};
+use io::las_meta::ExtraBytes;
+use point_cloud::attribute_data::AttributeData;
 use point_cloud::core::HighPerformancePointCloud;
+use point_cloud::crs::Crs;
+use point_cloud::octree::{Frustum, Octree};
+use point_cloud::voxel::VoxelAggregation;
+use point_cloud::voxel_grid::VoxelGrid;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyCapsule, PyDict};
+use std::collections::HashMap;
+use std::sync::Arc;
 use traits::{
     CoordinateTransform, DownsampleStrategy, PointCloudCore, PointCloudProperties, VoxelDownsample,
 };
@@ -19,6 +29,11 @@
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyPointCloud>()?;
     m.add_class::<PyDownsampleStrategy>()?;
+    m.add_class::<PyVoxelGrid>()?;
+    m.add_class::<PyOctree>()?;
+    m.add_class::<PyLasChunkIterator>()?;
+    m.add_class::<PyLasWriter>()?;
+    m.add_class::<PyHostTensor>()?;
     Ok(())
 }
 
@@ -46,6 +61,15 @@ fn from_xyz(xyz: &Bound<'_, pyo3::PyAny>) -> PyResult<Self> {
         Ok(PyPointCloud { inner })
     }
 
+    /// 从实现 __dlpack__ 的 [N, 3] 张量创建点云（torch.Tensor、numpy 数组等，仅 CPU）
+    /// float64 为全局坐标并自动设置原点，其他类型按 float32 读取
+    #[staticmethod]
+    fn from_dlpack(xyz: &Bound<'_, pyo3::PyAny>) -> PyResult<Self> {
+        let host = interop::dlpack::import_dlpack(xyz)?;
+        let inner = HighPerformancePointCloud::from_host_xyz(host).map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
     /// 从 numpy XYZ 和 intensity 数组创建点云
     /// xyz: 形状为 [N, 3] 的 2D numpy 数组
     /// intensity: 形状为 [N] 的 1D numpy 数组
@@ -100,27 +124,143 @@ fn from_dict(py: Python, data: &Bound<'_, PyDict>) -> PyResult<Self> {
         Ok(PyPointCloud { inner })
     }
 
+    /// 从深度图反投影创建点云
+    /// depth: 形状为 [H, W] 的 uint16/float32 numpy 数组
+    /// intrinsics: 3x3 针孔内参矩阵；extrinsic: 可选 4x4 外参（世界->相机）
+    #[staticmethod]
+    #[pyo3(signature = (depth, intrinsics, depth_scale = 1000.0, depth_trunc = 1000.0, extrinsic = None))]
+    fn from_depth_image(
+        depth: &Bound<'_, pyo3::PyAny>,
+        intrinsics: Vec<Vec<f32>>,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<Vec<Vec<f64>>>,
+    ) -> PyResult<Self> {
+        let (intrinsics, extrinsic) = parse_camera(&intrinsics, extrinsic.as_deref())?;
+        let inner = HighPerformancePointCloud::from_depth_array(
+            depth,
+            intrinsics,
+            depth_scale,
+            depth_trunc,
+            extrinsic.as_ref(),
+        )
+        .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
+    /// 从RGB-D图像反投影创建带颜色的点云
+    /// color: 形状为 [H, W, 3] 的 uint8 numpy 数组
+    /// depth: 形状为 [H, W] 的 uint16/float32 numpy 数组
+    #[staticmethod]
+    #[pyo3(signature = (color, depth, intrinsics, depth_scale = 1000.0, depth_trunc = 1000.0, extrinsic = None))]
+    fn from_rgbd(
+        color: &Bound<'_, pyo3::PyAny>,
+        depth: &Bound<'_, pyo3::PyAny>,
+        intrinsics: Vec<Vec<f32>>,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<Vec<Vec<f64>>>,
+    ) -> PyResult<Self> {
+        let (intrinsics, extrinsic) = parse_camera(&intrinsics, extrinsic.as_deref())?;
+        let inner = HighPerformancePointCloud::from_rgbd_arrays(
+            color,
+            depth,
+            intrinsics,
+            depth_scale,
+            depth_trunc,
+            extrinsic.as_ref(),
+        )
+        .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
     /// 获取点数
     fn point_count(&self) -> usize {
         self.inner.point_count()
     }
 
-    /// 获取XYZ坐标（返回 numpy 数组字典中的 xyz）
+    /// 获取XYZ坐标：始终为 float64 全局坐标
     fn get_xyz(&self, py: Python) -> PyResult<Py<PyAny>> {
-        use crate::utils::tensor;
-        use numpy::ndarray::Array2;
-        use numpy::IntoPyArray;
+        self.inner.xyz_to_pyarray(py).map_err(PyErr::from)
+    }
 
-        let xyz_vec = tensor::tensor2_to_vec(self.inner.xyz_ref());
-        let n = xyz_vec.len();
-        let mut xyz_flat: Vec<f32> = Vec::with_capacity(n * 3);
-        for row in &xyz_vec {
-            xyz_flat.extend_from_slice(row);
+    /// 取出坐标、颜色、强度、GPS时间或属性的主机张量，支持 DLPack 与 NumPy 数组接口
+    /// copy=False 时要求共享点云存储，需要复制的数据抛出 BufferError
+    #[pyo3(signature = (name = "xyz", copy = None))]
+    fn host_tensor(&self, name: &str, copy: Option<bool>) -> PyResult<PyHostTensor> {
+        let host = self.inner.host_tensor(name).map_err(PyErr::from)?;
+        if copy == Some(false) && !host.is_shared() {
+            return Err(pyo3::exceptions::PyBufferError::new_err(format!(
+                "'{}'需要复制，不能以 copy=False 取出",
+                name
+            )));
         }
-        let xyz_nd = Array2::from_shape_vec((n, 3), xyz_flat)
-            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("形状错误: {}", e)))?;
-        let xyz_np = IntoPyArray::into_pyarray(xyz_nd, py);
-        Ok(xyz_np.into())
+        let host = if copy == Some(true) && host.is_shared() {
+            host.deep_copy()
+        } else {
+            host
+        };
+        Ok(PyHostTensor {
+            inner: Arc::new(host),
+        })
+    }
+
+    /// DLPack 导出 XYZ（同 get_xyz），供 torch.from_dlpack / np.from_dlpack 使用
+    #[pyo3(signature = (*, stream = None, max_version = None, dl_device = None, copy = None))]
+    fn __dlpack__<'py>(
+        &self,
+        py: Python<'py>,
+        stream: Option<Bound<'py, pyo3::PyAny>>,
+        max_version: Option<(u32, u32)>,
+        dl_device: Option<(i32, i32)>,
+        copy: Option<bool>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // 全局坐标需加上原点转为 float64，每次导出都复制一份新的缓冲区
+        let _ = (stream, max_version);
+        check_dlpack_device(dl_device)?;
+        if copy == Some(false) {
+            return Err(pyo3::exceptions::PyBufferError::new_err(
+                "点云坐标需要复制为 float64 全局坐标，不能以 copy=False 导出；\
+                 共享局部坐标请使用 host_tensor(\"local_xyz\")",
+            ));
+        }
+        let host = self.inner.host_tensor("xyz").map_err(PyErr::from)?;
+        interop::dlpack::export_dlpack(py, &Arc::new(host))
+    }
+
+    fn __dlpack_device__(&self) -> (i32, i32) {
+        (interop::dlpack::DL_CPU, 0)
+    }
+
+    /// 局部坐标原点（全局坐标 = 局部 float32 坐标 + 原点）
+    fn origin(&self) -> (f64, f64, f64) {
+        let [x, y, z] = self.inner.origin();
+        (x, y, z)
+    }
+
+    /// 更换局部坐标原点，全局坐标不变
+    fn set_origin(&mut self, origin: [f64; 3]) -> PyResult<()> {
+        self.inner.set_origin(origin).map_err(PyErr::from)
+    }
+
+    /// 坐标参考系（"EPSG:n"、WKT 或 "ENU:lon,lat,h"），未设置时为 None
+    fn crs(&self) -> Option<String> {
+        self.inner.crs().map(Crs::to_string)
+    }
+
+    /// 设置坐标参考系（仅标记元数据，不变换坐标），None 表示清除
+    #[pyo3(signature = (crs=None))]
+    fn set_crs(&mut self, crs: Option<&str>) -> PyResult<()> {
+        let crs = crs.map(Crs::parse).transpose().map_err(PyErr::from)?;
+        self.inner.set_crs(crs);
+        Ok(())
+    }
+
+    /// 重投影到目标坐标系，返回新点云
+    fn reproject(&self, target_crs: &str) -> PyResult<PyPointCloud> {
+        let target = Crs::parse(target_crs).map_err(PyErr::from)?;
+        let inner = self.inner.reproject(&target).map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
     }
 
     /// 检查是否有intensity
@@ -196,40 +336,26 @@ fn set_rgb(
         Ok(())
     }
 
-    /// 添加自定义属性（从 numpy 数组）
+    /// 添加自定义属性（从 numpy 数组，保持 dtype）
     fn add_attribute(&mut self, name: String, data: &Bound<'_, pyo3::PyAny>) -> PyResult<()> {
-        let tensor = read_attribute_array(data)?;
-        let point_count = self.inner.point_count();
-        let data_len = utils::tensor::tensor1_len(&tensor);
-        if data_len != point_count {
-            return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "属性'{}'长度{}与点数{}不匹配",
-                name, data_len, point_count
-            )));
-        }
+        let data = read_attribute_array(data)?;
         if self.inner.attributes_ref().contains_key(&name) {
             return Err(pyo3::exceptions::PyValueError::new_err(format!(
                 "属性'{}'已存在",
                 name
             )));
         }
-        self.inner.attributes_mut().insert(name, tensor);
-        Ok(())
+        self.inner
+            .set_attribute_data(name, data)
+            .map_err(PyErr::from)
     }
 
-    /// 设置自定义属性（从 numpy 数组）
+    /// 设置自定义属性（从 numpy 数组，保持 dtype）
     fn set_attribute(&mut self, name: String, data: &Bound<'_, pyo3::PyAny>) -> PyResult<()> {
-        let tensor = read_attribute_array(data)?;
-        let point_count = self.inner.point_count();
-        let data_len = utils::tensor::tensor1_len(&tensor);
-        if data_len != point_count {
-            return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                "属性'{}'长度{}与点数{}不匹配",
-                name, data_len, point_count
-            )));
-        }
-        self.inner.attributes_mut().insert(name, tensor);
-        Ok(())
+        let data = read_attribute_array(data)?;
+        self.inner
+            .set_attribute_data(name, data)
+            .map_err(PyErr::from)
     }
 
     /// 获取属性名列表
@@ -237,19 +363,13 @@ fn attribute_names(&self) -> Vec<String> {
         self.inner.attribute_names()
     }
 
-    /// 获取属性（返回 numpy 数组）
+    /// 获取属性（返回与属性类型一致的 numpy 数组，多维属性为[M,D]）
     fn get_attribute(&self, py: Python, name: &str) -> PyResult<Option<Py<PyAny>>> {
-        use numpy::ndarray::Array1;
-        use numpy::IntoPyArray;
-
-        if let Some(attr) = self.inner.attributes_ref().get(name) {
-            let vec = utils::tensor::tensor1_to_vec(attr);
-            let nd = Array1::from_vec(vec);
-            let np = IntoPyArray::into_pyarray(nd, py);
-            Ok(Some(np.into()))
-        } else {
-            Ok(None)
-        }
+        self.inner
+            .attribute(name)
+            .map(|data| interop::numpy::attribute_to_pyarray(py, data))
+            .transpose()
+            .map_err(PyErr::from)
     }
 
     /// 删除属性
@@ -295,6 +415,114 @@ fn remove_rgb(&mut self) {
         self.inner.remove_rgb();
     }
 
+    /// 检查是否有GPS时间
+    fn has_gps_time(&self) -> bool {
+        self.inner.gps_time().is_some()
+    }
+
+    /// 获取 GPS 时间（float64 numpy 数组）
+    fn get_gps_time(&self, py: Python) -> Option<Py<PyAny>> {
+        self.inner
+            .gps_time()
+            .map(|times| IntoPyArray::into_pyarray(Array1::from_vec(times.to_vec()), py).into())
+    }
+
+    /// 设置 GPS 时间（float64 numpy 数组）
+    fn set_gps_time(&mut self, gps_time: numpy::PyReadonlyArray1<'_, f64>) -> PyResult<()> {
+        let gps_time = gps_time
+            .as_slice()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("无法读取数据，数组可能不连续"))?
+            .to_vec();
+        self.inner.set_gps_time(gps_time).map_err(PyErr::from)
+    }
+
+    /// 移除GPS时间
+    fn remove_gps_time(&mut self) {
+        self.inner.remove_gps_time();
+    }
+
+    /// 声明自定义属性写入LAS时的 Extra Bytes 类型、缩放与无效值
+    /// data_type: uint8/int8/uint16/int16/uint32/int32/uint64/int64/float32/float64
+    #[pyo3(signature = (name, data_type = "float32", scale = None, offset = None, no_data = None, description = None))]
+    fn set_las_extra_bytes(
+        &mut self,
+        name: &str,
+        data_type: &str,
+        scale: Option<f64>,
+        offset: Option<f64>,
+        no_data: Option<f64>,
+        description: Option<String>,
+    ) -> PyResult<()> {
+        let descriptor = ExtraBytes {
+            scale,
+            offset,
+            no_data,
+            description: description.unwrap_or_default(),
+            ..ExtraBytes::with_type(name, data_type).map_err(PyErr::from)?
+        };
+        self.inner.set_extra_bytes(descriptor).map_err(PyErr::from)
+    }
+
+    /// 已声明的 Extra Bytes 描述符列表（读取LAS时从 Extra Bytes VLR 填充）
+    fn las_extra_bytes(&self, py: Python) -> PyResult<Vec<Py<PyDict>>> {
+        let Some(metadata) = self.inner.las_metadata() else {
+            return Ok(Vec::new());
+        };
+        metadata
+            .extra_bytes
+            .iter()
+            .map(|descriptor| {
+                let dict = PyDict::new(py);
+                dict.set_item("name", &descriptor.name)?;
+                dict.set_item("data_type", descriptor.type_name())?;
+                dict.set_item("scale", descriptor.scale)?;
+                dict.set_item("offset", descriptor.offset)?;
+                dict.set_item("no_data", descriptor.no_data)?;
+                dict.set_item("description", &descriptor.description)?;
+                Ok(dict.unbind())
+            })
+            .collect()
+    }
+
+    /// 读取LAS文件时保留的头部信息，写出时沿用；非LAS来源返回 None
+    fn las_header(&self, py: Python) -> PyResult<Option<Py<PyDict>>> {
+        let Some(metadata) = self.inner.las_metadata() else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("version", metadata.version)?;
+        dict.set_item("scale", metadata.scale)?;
+        dict.set_item("offset", metadata.offset)?;
+        dict.set_item("system_identifier", &metadata.system_identifier)?;
+        dict.set_item("generating_software", &metadata.generating_software)?;
+        dict.set_item("file_source_id", metadata.file_source_id)?;
+        let vlrs = metadata
+            .vlrs
+            .iter()
+            .map(|vlr| {
+                (
+                    vlr.user_id.as_str(),
+                    vlr.record_id,
+                    vlr.description.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let evlrs = metadata
+            .evlrs
+            .iter()
+            .map(|vlr| {
+                (
+                    vlr.user_id.as_str(),
+                    vlr.record_id,
+                    vlr.description.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        dict.set_item("vlrs", vlrs)?;
+        dict.set_item("evlrs", evlrs)?;
+        Ok(Some(dict.unbind()))
+    }
+
     /// 删除文件
     #[staticmethod]
     fn delete_file(path: &str) -> PyResult<()> {
@@ -302,14 +530,14 @@ fn delete_file(path: &str) -> PyResult<()> {
         Ok(())
     }
 
-    /// 坐标变换（矩阵）
-    fn transform(&self, matrix: Vec<Vec<f32>>) -> PyResult<Self> {
+    /// 坐标变换（矩阵），按 f64 作用于全局坐标
+    fn transform(&self, matrix: Vec<Vec<f64>>) -> PyResult<Self> {
         let result = self.inner.transform(matrix).map_err(PyErr::from)?;
         Ok(PyPointCloud { inner: result })
     }
 
-    /// 刚体变换（旋转+平移）
-    fn rigid_transform(&self, rotation: Vec<Vec<f32>>, translation: Vec<f32>) -> PyResult<Self> {
+    /// 刚体变换（旋转+平移），平移按 f64 作用于全局坐标
+    fn rigid_transform(&self, rotation: Vec<Vec<f64>>, translation: Vec<f64>) -> PyResult<Self> {
         let result = self
             .inner
             .rigid_transform(rotation, translation)
@@ -318,9 +546,42 @@ fn rigid_transform(&self, rotation: Vec<Vec<f32>>, translation: Vec<f32>) -> PyR
     }
 
     /// 体素下采样
-    fn voxel_downsample(&self, voxel_size: f32, strategy: i32) -> PyResult<Self> {
+    /// seed: RANDOM 策略的随机种子（None 时每次随机）
+    /// attribute_modes: 聚合策略下按通道覆盖聚合方式，如 {"classification": DownsampleStrategy.MODE}
+    #[pyo3(signature = (voxel_size, strategy, seed = None, attribute_modes = None))]
+    fn voxel_downsample(
+        &self,
+        voxel_size: f32,
+        strategy: i32,
+        seed: Option<u64>,
+        attribute_modes: Option<HashMap<String, i32>>,
+    ) -> PyResult<Self> {
+        if let Some(aggregation) = aggregation_from_code(strategy) {
+            let mut overrides = HashMap::new();
+            for (name, code) in attribute_modes.unwrap_or_default() {
+                let mode = aggregation_from_code(code).ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "属性'{}'的聚合方式必须是 MEAN/MEDIAN/MAX/MODE",
+                        name
+                    ))
+                })?;
+                overrides.insert(name, mode);
+            }
+            let result = self
+                .inner
+                .voxel_downsample_aggregate(voxel_size, aggregation, &overrides)
+                .map_err(PyErr::from)?;
+            return Ok(PyPointCloud { inner: result });
+        }
+
+        if attribute_modes.is_some() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "attribute_modes仅适用于聚合策略（MEAN/MEDIAN/MAX/MODE）",
+            ));
+        }
+
         let strategy_impl: Box<dyn DownsampleStrategy> = match strategy {
-            0 => Box::new(point_cloud::voxel::RandomSampleStrategy),
+            0 => Box::new(point_cloud::voxel::RandomSampleStrategy::new(seed)),
             1 => Box::new(point_cloud::voxel::CentroidSampleStrategy),
             _ => return Err(pyo3::exceptions::PyValueError::new_err("未知的采样策略")),
         };
@@ -332,6 +593,205 @@ fn voxel_downsample(&self, voxel_size: f32, strategy: i32) -> PyResult<Self> {
         Ok(PyPointCloud { inner: result })
     }
 
+    /// 最远点采样，返回 (采样后的点云, 原始索引 int64 数组)
+    #[pyo3(signature = (n, start_index = 0))]
+    fn farthest_point_sampling(
+        &self,
+        py: Python,
+        n: usize,
+        start_index: usize,
+    ) -> PyResult<(Self, Py<PyAny>)> {
+        use numpy::ndarray::Array1;
+        use numpy::IntoPyArray;
+
+        let (result, indices) = self
+            .inner
+            .farthest_point_sampling(n, start_index)
+            .map_err(PyErr::from)?;
+        let indices: Vec<i64> = indices.into_iter().map(|idx| idx as i64).collect();
+        let indices_np = IntoPyArray::into_pyarray(Array1::from_vec(indices), py);
+        Ok((PyPointCloud { inner: result }, indices_np.into()))
+    }
+
+    /// 随机采样（按点数 n 或比例 ratio，二者必须且只能指定一个）
+    #[pyo3(signature = (n = None, ratio = None, seed = None))]
+    fn random_sample(
+        &self,
+        n: Option<usize>,
+        ratio: Option<f32>,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let result = match (n, ratio) {
+            (Some(n), None) => self.inner.random_sample(n, seed),
+            (None, Some(ratio)) => self.inner.random_sample_ratio(ratio, seed),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "n 与 ratio 必须且只能指定一个",
+                ))
+            }
+        }
+        .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner: result })
+    }
+
+    /// 均匀采样：每隔 k 个点取一个
+    fn uniform_sample(&self, k: usize) -> PyResult<Self> {
+        let result = self.inner.uniform_sample(k).map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner: result })
+    }
+
+    /// 泊松圆盘采样：采样点间距不小于 min_distance
+    #[pyo3(signature = (min_distance, seed = None))]
+    fn poisson_disk_sample(&self, min_distance: f32, seed: Option<u64>) -> PyResult<Self> {
+        let result = self
+            .inner
+            .poisson_disk_sample(min_distance, seed)
+            .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner: result })
+    }
+
+    /// 生成BEV伪图像（返回形状为[H,W,C]的 numpy 数组）
+    /// x_range/y_range: 全局坐标范围，height/intensity 通道的空像素为 NaN
+    /// channels: 通道名列表（height/density/intensity），默认 height+density(+intensity)
+    #[pyo3(signature = (x_range, y_range, resolution, channels = None))]
+    fn to_bev(
+        &self,
+        py: Python,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        resolution: f32,
+        channels: Option<Vec<String>>,
+    ) -> PyResult<Py<PyAny>> {
+        use point_cloud::projection::BevChannel;
+
+        let channels = match channels {
+            Some(names) => names
+                .iter()
+                .map(|name| BevChannel::parse(name))
+                .collect::<utils::error::Result<Vec<_>>>()
+                .map_err(PyErr::from)?,
+            None => {
+                let mut default = vec![BevChannel::Height, BevChannel::Density];
+                if self.inner.has_intensity() {
+                    default.push(BevChannel::Intensity);
+                }
+                default
+            }
+        };
+        let bev = self
+            .inner
+            .to_bev(x_range, y_range, resolution, &channels)
+            .map_err(PyErr::from)?;
+        interop::numpy::tensor3_to_pyarray(py, &bev).map_err(PyErr::from)
+    }
+
+    /// 生成球面距离图像
+    /// 返回字典：depth [H,W]、index_map [H,W]（int64，-1 表示空像素）、可选 intensity [H,W]
+    /// sensor: 传感器的全局坐标，默认为点云原点
+    #[pyo3(signature = (
+        h_fov = (-180.0, 180.0),
+        v_fov = (-25.0, 3.0),
+        width = 1024,
+        height = 64,
+        sensor = None
+    ))]
+    fn to_range_image(
+        &self,
+        py: Python,
+        h_fov: (f32, f32),
+        v_fov: (f32, f32),
+        width: usize,
+        height: usize,
+        sensor: Option<(f64, f64, f64)>,
+    ) -> PyResult<Py<PyAny>> {
+        use numpy::ndarray::Array2;
+        use numpy::IntoPyArray;
+
+        let image = self
+            .inner
+            .to_range_image(
+                h_fov,
+                v_fov,
+                width,
+                height,
+                sensor.map(|(x, y, z)| [x, y, z]),
+            )
+            .map_err(PyErr::from)?;
+
+        let dict = PyDict::new(py);
+        dict.set_item(
+            "depth",
+            interop::numpy::tensor2_to_pyarray(py, &image.depth).map_err(PyErr::from)?,
+        )?;
+        if let Some(intensity) = &image.intensity {
+            dict.set_item(
+                "intensity",
+                interop::numpy::tensor2_to_pyarray(py, intensity).map_err(PyErr::from)?,
+            )?;
+        }
+        let index_nd = Array2::from_shape_vec((image.height, image.width), image.index_map)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("形状错误: {}", e)))?;
+        dict.set_item("index_map", IntoPyArray::into_pyarray(index_nd, py))?;
+        Ok(dict.into())
+    }
+
+    /// 将点云投影到相机图像（z-buffer 处理遮挡）
+    /// 返回 (pixels [N,2] float32, visible [N] bool)，相机后方的点像素坐标为 -1
+    #[pyo3(signature = (intrinsics, extrinsic, width, height, distortion = None))]
+    fn project_to_image(
+        &self,
+        py: Python,
+        intrinsics: Vec<Vec<f32>>,
+        extrinsic: Option<Vec<Vec<f64>>>,
+        width: usize,
+        height: usize,
+        distortion: Option<Vec<f32>>,
+    ) -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+        use numpy::ndarray::Array1;
+        use numpy::IntoPyArray;
+
+        let (intrinsics, extrinsic) = parse_camera(&intrinsics, extrinsic.as_deref())?;
+        let distortion = parse_distortion(distortion.as_deref())?;
+        let projection = self
+            .inner
+            .project_to_image(
+                intrinsics,
+                extrinsic.as_ref(),
+                width,
+                height,
+                distortion.as_ref(),
+            )
+            .map_err(PyErr::from)?;
+
+        let pixels =
+            interop::numpy::tensor2_to_pyarray(py, &projection.pixels).map_err(PyErr::from)?;
+        let visible = IntoPyArray::into_pyarray(Array1::from_vec(projection.visible), py);
+        Ok((pixels, visible.into()))
+    }
+
+    /// 从相机图像双线性采样颜色，写入可见点的RGB通道
+    /// image: 形状为 [H, W, 3] 的 uint8 numpy 数组；返回 visible [N] bool
+    #[pyo3(signature = (image, intrinsics, extrinsic = None, distortion = None))]
+    fn colorize_from_image(
+        &mut self,
+        py: Python,
+        image: &Bound<'_, pyo3::PyAny>,
+        intrinsics: Vec<Vec<f32>>,
+        extrinsic: Option<Vec<Vec<f64>>>,
+        distortion: Option<Vec<f32>>,
+    ) -> PyResult<Py<PyAny>> {
+        use numpy::ndarray::Array1;
+        use numpy::IntoPyArray;
+
+        let (intrinsics, extrinsic) = parse_camera(&intrinsics, extrinsic.as_deref())?;
+        let distortion = parse_distortion(distortion.as_deref())?;
+        let visible = self
+            .inner
+            .colorize_from_array(image, intrinsics, extrinsic.as_ref(), distortion.as_ref())
+            .map_err(PyErr::from)?;
+        Ok(IntoPyArray::into_pyarray(Array1::from_vec(visible), py).into())
+    }
+
     /// 从LAS/LAZ文件读取
     #[staticmethod]
     fn from_las(path: &str) -> PyResult<Self> {
@@ -339,9 +799,96 @@ fn from_las(path: &str) -> PyResult<Self> {
         Ok(PyPointCloud { inner })
     }
 
+    /// 按块读取LAS/LAZ文件，返回逐块产出 PointCloud 的迭代器
+    #[staticmethod]
+    #[pyo3(signature = (path, chunk_size = 1_000_000))]
+    fn iter_las(path: &str, chunk_size: usize) -> PyResult<PyLasChunkIterator> {
+        let inner = HighPerformancePointCloud::iter_las(path, chunk_size).map_err(PyErr::from)?;
+        Ok(PyLasChunkIterator { inner })
+    }
+
+    /// 判断LAZ文件是否为COPC（含 copc 信息VLR）
+    #[staticmethod]
+    fn is_copc(path: &str) -> PyResult<bool> {
+        HighPerformancePointCloud::is_copc(path).map_err(PyErr::from)
+    }
+
+    /// 读取COPC信息VLR（中心、半边长、点间距、GPS时间范围），非COPC文件返回None
+    #[staticmethod]
+    fn copc_info(py: Python, path: &str) -> PyResult<Option<Py<PyDict>>> {
+        let Some(info) = HighPerformancePointCloud::copc_info(path).map_err(PyErr::from)? else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("center", info.center)?;
+        dict.set_item("halfsize", info.halfsize)?;
+        dict.set_item("spacing", info.spacing)?;
+        dict.set_item("root_hier_offset", info.root_hier_offset)?;
+        dict.set_item("root_hier_size", info.root_hier_size)?;
+        dict.set_item("gpstime_range", info.gpstime_range)?;
+        Ok(Some(dict.unbind()))
+    }
+
+    /// 按包围盒与最大层级读取COPC文件，只读取相交的层级页并解码相交的数据块
+    /// bounds: ((min_x, min_y, min_z), (max_x, max_y, max_z))
+    #[staticmethod]
+    #[pyo3(signature = (path, bounds = None, max_level = None))]
+    fn from_copc(
+        path: &str,
+        bounds: Option<([f64; 3], [f64; 3])>,
+        max_level: Option<i32>,
+    ) -> PyResult<Self> {
+        let inner =
+            HighPerformancePointCloud::from_copc(path, bounds, max_level).map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
     /// 保存为LAS文件
-    fn to_las(&self, path: &str, compress: bool) -> PyResult<()> {
-        self.inner.to_las(path, compress).map_err(PyErr::from)?;
+    /// version/scale/offset 为 None 时沿用读取时保留的头部（无则为 1.4、0.001、0）
+    /// point_format 为 None 时按点云内容自动选择
+    #[pyo3(signature = (path, compress = false, version = None, scale = None, offset = None, point_format = None))]
+    fn to_las(
+        &self,
+        path: &str,
+        compress: bool,
+        version: Option<(u8, u8)>,
+        scale: Option<[f64; 3]>,
+        offset: Option<[f64; 3]>,
+        point_format: Option<u8>,
+    ) -> PyResult<()> {
+        let options = LasWriteOptions {
+            compress,
+            version,
+            scale,
+            offset,
+            point_format,
+        };
+        self.inner
+            .to_las_with_options(path, options)
+            .map_err(PyErr::from)?;
+        Ok(())
+    }
+
+    /// 导出为 Potree 2.0 格式目录（hierarchy.bin、octree.bin、metadata.json）
+    #[pyo3(signature = (output_dir, name = None, max_depth = 12, leaf_size = 20000, scale = 0.001))]
+    fn to_potree(
+        &self,
+        output_dir: &str,
+        name: Option<String>,
+        max_depth: u32,
+        leaf_size: usize,
+        scale: f64,
+    ) -> PyResult<()> {
+        let defaults = io::potree::PotreeOptions::default();
+        let options = io::potree::PotreeOptions {
+            name: name.unwrap_or(defaults.name),
+            max_depth,
+            leaf_size,
+            scale,
+        };
+        self.inner
+            .to_potree(output_dir, &options)
+            .map_err(PyErr::from)?;
         Ok(())
     }
 
@@ -356,7 +903,10 @@ fn to_las(&self, path: &str, compress: bool) -> PyResult<()> {
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn from_csv(
@@ -369,8 +919,13 @@ fn from_csv(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<Self> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
         let inner = HighPerformancePointCloud::from_table_csv(path, delimiter, columns)
             .map_err(PyErr::from)?;
         Ok(PyPointCloud { inner })
@@ -386,7 +941,10 @@ fn from_csv(
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn from_parquet(
@@ -398,13 +956,66 @@ fn from_parquet(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<Self> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
         let inner =
             HighPerformancePointCloud::from_table_parquet(path, columns).map_err(PyErr::from)?;
         Ok(PyPointCloud { inner })
     }
 
+    /// 延迟扫描Parquet文件、glob或目录，过滤条件与列投影下推到扫描
+    #[staticmethod]
+    #[pyo3(signature = (
+        path,
+        bbox = None,
+        filters = None,
+        limit = None,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn scan_parquet(
+        path: &str,
+        bbox: Option<Vec<f64>>,
+        filters: Option<HashMap<String, Bound<'_, pyo3::PyAny>>>,
+        limit: Option<usize>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
+        let scan = io::table::ParquetScan {
+            bbox,
+            filters: parse_column_filters(filters.unwrap_or_default())?,
+            limit,
+        };
+        let inner = HighPerformancePointCloud::scan_table_parquet(path, columns, &scan)
+            .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
     /// 保存为CSV
     #[pyo3(signature = (
         path,
@@ -415,7 +1026,9 @@ fn from_parquet(
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn to_csv(
@@ -429,8 +1042,12 @@ fn to_csv(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<()> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
         self.inner
             .to_table_csv(path, delimiter, columns)
             .map_err(PyErr::from)?;
@@ -446,7 +1063,9 @@ fn to_csv(
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn to_parquet(
@@ -459,15 +1078,69 @@ fn to_parquet(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<()> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
         self.inner
             .to_table_parquet(path, columns)
             .map_err(PyErr::from)?;
         Ok(())
     }
 
-    /// 根据扩展名自动读取
+    /// 写出按XY瓦片分区的Parquet数据集（Hive目录），根目录_dataset.json记录瓦片包围盒
+    #[pyo3(signature = (
+        root,
+        tile_size,
+        scheme = "grid",
+        row_group_size = None,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None,
+        overwrite = false
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_parquet_dataset(
+        &self,
+        root: &str,
+        tile_size: f64,
+        scheme: &str,
+        row_group_size: Option<usize>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
+        overwrite: bool,
+    ) -> PyResult<()> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
+        let options = io::dataset::DatasetOptions {
+            tile_size,
+            scheme: io::dataset::TileScheme::parse(scheme).map_err(PyErr::from)?,
+            row_group_size,
+            overwrite,
+        };
+        self.inner
+            .to_parquet_dataset(root, &options, columns)
+            .map_err(PyErr::from)?;
+        Ok(())
+    }
+
+    /// 从Arrow IPC文件（Feather v2）读取
     #[staticmethod]
     #[pyo3(signature = (
         path,
@@ -477,10 +1150,13 @@ fn to_parquet(
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
-    fn load_from_file(
+    fn from_ipc(
         path: &str,
         x: Option<String>,
         y: Option<String>,
@@ -489,14 +1165,19 @@ fn load_from_file(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<Self> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
         let inner =
-            HighPerformancePointCloud::load_from_file(path, Some(columns)).map_err(PyErr::from)?;
+            HighPerformancePointCloud::from_table_ipc(path, columns).map_err(PyErr::from)?;
         Ok(PyPointCloud { inner })
     }
 
-    /// 根据扩展名自动保存
+    /// 保存为Arrow IPC文件（Feather v2）
     #[pyo3(signature = (
         path,
         x = None,
@@ -505,10 +1186,12 @@ fn load_from_file(
         intensity = None,
         rgb_r = None,
         rgb_g = None,
-        rgb_b = None
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
     ))]
     #[allow(clippy::too_many_arguments)]
-    fn save_to_file(
+    fn to_ipc(
         &self,
         path: &str,
         x: Option<String>,
@@ -518,35 +1201,347 @@ fn save_to_file(
         rgb_r: Option<String>,
         rgb_g: Option<String>,
         rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
     ) -> PyResult<()> {
-        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b);
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
         self.inner
-            .save_to_file(path, Some(columns))
+            .to_table_ipc(path, columns)
             .map_err(PyErr::from)?;
         Ok(())
     }
 
-    /// 获取内存占用（字节）
-    fn memory_usage(&self) -> usize {
-        self.inner.memory_usage()
-    }
-
-    /// 转换为Python字典（包含numpy数组）
-    fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
-        self.inner.to_numpy(py).map_err(PyErr::from)
-    }
-
-    /// 创建点云副本
-    fn clone(&self) -> Self {
-        PyPointCloud {
-            inner: self.inner.clone(),
-        }
-    }
-
-    fn __repr__(&self) -> String {
-        format!(
-            "PointCloud(points={}, intensity={}, rgb={}, attributes={})",
-            self.inner.point_count(),
+    /// 根据扩展名自动读取
+    #[staticmethod]
+    #[pyo3(signature = (
+        path,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn load_from_file(
+        path: &str,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
+        let inner =
+            HighPerformancePointCloud::load_from_file(path, Some(columns)).map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
+    /// 根据扩展名自动保存
+    #[pyo3(signature = (
+        path,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn save_to_file(
+        &self,
+        path: &str,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
+        self.inner
+            .save_to_file(path, Some(columns))
+            .map_err(PyErr::from)?;
+        Ok(())
+    }
+
+    /// 获取内存占用（字节）
+    fn memory_usage(&self) -> usize {
+        self.inner.memory_usage()
+    }
+
+    /// 转换为Python字典（包含numpy数组）
+    fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
+        self.inner.to_numpy(py).map_err(PyErr::from)
+    }
+
+    /// 从实现Arrow PyCapsule接口的对象构建点云（pyarrow.Table/RecordBatch、polars.DataFrame等）
+    #[staticmethod]
+    #[pyo3(signature = (
+        data,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn from_arrow(
+        data: &Bound<'_, pyo3::PyAny>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
+        Self::from_arrow_data(data, columns)
+    }
+
+    /// 转换为pyarrow.Table，经Arrow C数据接口移交缓冲区
+    #[pyo3(signature = (
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_arrow<'py>(
+        &self,
+        py: Python<'py>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, pyo3::PyAny>> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
+        let stream = self.arrow_stream(py, columns)?;
+        py.import("pyarrow")?.call_method1("table", (stream,))
+    }
+
+    /// Arrow PyCapsule流接口：默认列名并包含全部自定义属性
+    #[pyo3(signature = (requested_schema = None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, pyo3::PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // 不按 requested_schema 转换列类型，由消费方自行转换（PyCapsule 接口允许）
+        let _ = requested_schema;
+        let batch = self
+            .inner
+            .to_record_batch(io::table::TableColumnNames::default())
+            .map_err(PyErr::from)?;
+        interop::arrow::export_stream(py, batch)
+    }
+
+    /// 从polars.DataFrame构建点云
+    #[staticmethod]
+    #[pyo3(signature = (
+        df,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn from_polars(
+        df: &Bound<'_, pyo3::PyAny>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
+        // 旧版 polars 未实现 PyCapsule 接口，经 pyarrow 转换
+        if df.hasattr("__arrow_c_stream__")? {
+            Self::from_arrow_data(df, columns)
+        } else {
+            Self::from_arrow_data(&df.call_method0("to_arrow")?, columns)
+        }
+    }
+
+    /// 转换为polars.DataFrame，列布局同to_parquet
+    #[pyo3(signature = (
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_polars<'py>(
+        &self,
+        py: Python<'py>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, pyo3::PyAny>> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
+        let stream = self.arrow_stream(py, columns)?;
+        py.import("polars")?.call_method1("DataFrame", (stream,))
+    }
+
+    /// 从pandas.DataFrame构建点云（经pyarrow转换，忽略索引）
+    #[staticmethod]
+    #[pyo3(signature = (
+        df,
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        all_attributes = false,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn from_pandas(
+        py: Python<'_>,
+        df: &Bound<'_, pyo3::PyAny>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        all_attributes: bool,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(all_attributes, exclude_attributes);
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("preserve_index", false)?;
+        let table = py.import("pyarrow")?.getattr("Table")?.call_method(
+            "from_pandas",
+            (df,),
+            Some(&kwargs),
+        )?;
+        Self::from_arrow_data(&table, columns)
+    }
+
+    /// 转换为pandas.DataFrame（经pyarrow转换，多维属性列的元素为numpy数组）
+    #[pyo3(signature = (
+        x = None,
+        y = None,
+        z = None,
+        intensity = None,
+        rgb_r = None,
+        rgb_g = None,
+        rgb_b = None,
+        attributes = None,
+        exclude_attributes = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn to_pandas<'py>(
+        &self,
+        py: Python<'py>,
+        x: Option<String>,
+        y: Option<String>,
+        z: Option<String>,
+        intensity: Option<String>,
+        rgb_r: Option<String>,
+        rgb_g: Option<String>,
+        rgb_b: Option<String>,
+        attributes: Option<Vec<String>>,
+        exclude_attributes: Option<Vec<String>>,
+    ) -> PyResult<Bound<'py, pyo3::PyAny>> {
+        let columns = io::table::TableColumnNames::resolve(x, y, z, intensity, rgb_r, rgb_g, rgb_b)
+            .with_attributes(attributes)
+            .with_all_attributes(false, exclude_attributes);
+        let stream = self.arrow_stream(py, columns)?;
+        py.import("pyarrow")?
+            .call_method1("table", (stream,))?
+            .call_method0("to_pandas")
+    }
+
+    /// 创建点云副本
+    fn clone(&self) -> Self {
+        PyPointCloud {
+            inner: self.inner.clone(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PointCloud(points={}, intensity={}, rgb={}, attributes={})",
+            self.inner.point_count(),
             if self.inner.has_intensity() {
                 "Yes"
             } else {
@@ -558,6 +1553,544 @@ fn __repr__(&self) -> String {
     }
 }
 
+// ============ 体素网格 ============
+
+use numpy::ndarray::{Array1, Array2, Array3};
+use numpy::IntoPyArray;
+
+/// Python级别的体素网格
+#[pyclass(name = "VoxelGrid")]
+pub struct PyVoxelGrid {
+    inner: VoxelGrid,
+}
+
+#[pymethods]
+impl PyVoxelGrid {
+    /// 从点云构建体素网格
+    /// origin: 网格原点的全局坐标 (x, y, z)，默认为 (0, 0, 0)
+    #[new]
+    #[pyo3(signature = (cloud, voxel_size, origin = None))]
+    fn new(
+        cloud: PyRef<'_, PyPointCloud>,
+        voxel_size: f32,
+        origin: Option<[f64; 3]>,
+    ) -> PyResult<Self> {
+        let inner = VoxelGrid::new(&cloud.inner, voxel_size, origin).map_err(PyErr::from)?;
+        Ok(PyVoxelGrid { inner })
+    }
+
+    /// 体素边长
+    #[getter]
+    fn voxel_size(&self) -> f32 {
+        self.inner.voxel_size()
+    }
+
+    /// 网格原点（全局坐标）
+    #[getter]
+    fn origin(&self) -> (f64, f64, f64) {
+        let [x, y, z] = self.inner.origin();
+        (x, y, z)
+    }
+
+    /// 非空体素数量
+    fn voxel_count(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// 每个体素的点数（int64数组，形状[V]）
+    fn counts(&self, py: Python) -> Py<PyAny> {
+        let counts: Vec<i64> = self.inner.counts().into_iter().map(|c| c as i64).collect();
+        IntoPyArray::into_pyarray(Array1::from_vec(counts), py).into()
+    }
+
+    /// 每个体素相对网格原点的整数坐标（int64数组，形状[V,3]）
+    fn voxel_coords(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let flat: Vec<i64> = (0..self.inner.len())
+            .flat_map(|voxel| self.inner.voxel_coord(voxel))
+            .collect();
+        rows_to_pyarray(py, flat, self.inner.len(), 3)
+    }
+
+    /// 每个体素的点质心（float64全局坐标，形状[V,3]）
+    fn centroids(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let flat: Vec<f64> = self.inner.centroids().into_iter().flatten().collect();
+        rows_to_pyarray(py, flat, self.inner.len(), 3)
+    }
+
+    /// 每个体素的点协方差（float32数组，形状[V,3,3]）
+    fn covariances(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let flat: Vec<f32> = self
+            .inner
+            .covariances()
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect();
+        let nd = Array3::from_shape_vec((self.inner.len(), 3, 3), flat)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(IntoPyArray::into_pyarray(nd, py).into())
+    }
+
+    /// 体素内的点索引（int64数组）
+    fn point_indices(&self, py: Python, voxel: usize) -> PyResult<Py<PyAny>> {
+        self.check_voxel(voxel)?;
+        Ok(indices_to_pyarray(py, self.inner.point_indices(voxel)))
+    }
+
+    /// 构建点云中每个点所属的体素（int64数组，形状[N]）
+    fn point_voxels(&self, py: Python) -> Py<PyAny> {
+        indices_to_pyarray(py, self.inner.point_voxels())
+    }
+
+    /// 查找空间位置所在的体素（int64数组，空体素为 -1）
+    /// points: 形状为 [M, 3] 的 float32/float64 全局坐标数组
+    fn lookup(&self, py: Python, points: &Bound<'_, pyo3::PyAny>) -> PyResult<Py<PyAny>> {
+        let positions = interop::numpy::read_points_array(points).map_err(PyErr::from)?;
+        let voxels: Vec<i64> = self
+            .inner
+            .lookup(&positions)
+            .into_iter()
+            .map(|voxel| voxel.map_or(-1, |v| v as i64))
+            .collect();
+        Ok(IntoPyArray::into_pyarray(Array1::from_vec(voxels), py).into())
+    }
+
+    /// 查询邻域内的非空体素（切比雪夫距离不超过 radius，不含自身）
+    #[pyo3(signature = (voxel, radius = 1))]
+    fn neighbors(&self, py: Python, voxel: usize, radius: u32) -> PyResult<Py<PyAny>> {
+        self.check_voxel(voxel)?;
+        Ok(indices_to_pyarray(py, &self.inner.neighbors(voxel, radius)))
+    }
+
+    /// 导出体素中心为点云（附带 point_count 属性）
+    fn to_point_cloud(&self) -> PyResult<PyPointCloud> {
+        let inner = self.inner.to_point_cloud().map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VoxelGrid(voxels={}, points={}, voxel_size={})",
+            self.inner.len(),
+            self.inner.point_count(),
+            self.inner.voxel_size()
+        )
+    }
+}
+
+impl PyVoxelGrid {
+    fn check_voxel(&self, voxel: usize) -> PyResult<()> {
+        if voxel >= self.inner.len() {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "体素序号{}超出范围（共{}个体素）",
+                voxel,
+                self.inner.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 行优先数据转换为二维 numpy 数组
+fn rows_to_pyarray<T: numpy::Element>(
+    py: Python,
+    flat: Vec<T>,
+    rows: usize,
+    cols: usize,
+) -> PyResult<Py<PyAny>> {
+    let nd = Array2::from_shape_vec((rows, cols), flat)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    Ok(IntoPyArray::into_pyarray(nd, py).into())
+}
+
+// ============ 八叉树 ============
+
+/// Python级别的八叉树
+/// 引用构建时的点云而不复制，origin 为构建时的局部坐标原点
+#[pyclass(name = "Octree")]
+pub struct PyOctree {
+    inner: Octree,
+    cloud: Py<PyPointCloud>,
+    origin: [f64; 3],
+}
+
+#[pymethods]
+impl PyOctree {
+    /// 从点云构建八叉树
+    #[new]
+    #[pyo3(signature = (cloud, max_depth = 8, leaf_size = 64))]
+    fn new(cloud: Bound<'_, PyPointCloud>, max_depth: u32, leaf_size: usize) -> PyResult<Self> {
+        let (inner, origin) = {
+            let cloud = cloud.borrow();
+            let inner = Octree::new(&cloud.inner, max_depth, leaf_size).map_err(PyErr::from)?;
+            (inner, cloud.inner.origin())
+        };
+        Ok(PyOctree {
+            inner,
+            cloud: cloud.unbind(),
+            origin,
+        })
+    }
+
+    /// 节点数量
+    fn node_count(&self) -> usize {
+        self.inner.node_count()
+    }
+
+    /// 实际最大深度
+    fn depth(&self) -> u32 {
+        self.inner.depth()
+    }
+
+    /// 第 level 层的 LOD 抽样点索引（int64数组）
+    fn level_indices(&self, py: Python, level: u32) -> Py<PyAny> {
+        indices_to_pyarray(py, &self.inner.level_indices(level))
+    }
+
+    /// 按 LOD 层级抽取点云（点数与构建时不一致时报错）
+    fn extract_level(&self, py: Python, level: u32) -> PyResult<PyPointCloud> {
+        let cloud = self.cloud.borrow(py);
+        let inner = self
+            .inner
+            .extract_level(&cloud.inner, level)
+            .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+
+    /// 查询包围盒内的点索引（int64数组），min/max 为全局坐标
+    fn query_box(&self, py: Python, min: [f64; 3], max: [f64; 3]) -> Py<PyAny> {
+        let origin = self.origin;
+        let local =
+            |p: [f64; 3]| -> [f32; 3] { std::array::from_fn(|k| (p[k] - origin[k]) as f32) };
+        indices_to_pyarray(py, &self.inner.query_box(local(min), local(max)))
+    }
+
+    /// 查询视锥内的点索引（int64数组），矩阵与平面均作用于全局坐标
+    /// view_projection: 4x4 视图投影矩阵（OpenGL 约定）；planes: [K,4] 平面 ax+by+cz+d>=0
+    #[pyo3(signature = (view_projection = None, planes = None))]
+    fn query_frustum(
+        &self,
+        py: Python,
+        view_projection: Option<Vec<Vec<f64>>>,
+        planes: Option<Vec<[f64; 4]>>,
+    ) -> PyResult<Py<PyAny>> {
+        let origin = self.origin;
+        let frustum = match (view_projection, planes) {
+            (Some(matrix), None) => Frustum::from_view_projection(&matrix, origin),
+            (None, Some(planes)) => Frustum::from_planes(planes, origin),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "view_projection 与 planes 必须且只能指定一个",
+                ))
+            }
+        }
+        .map_err(PyErr::from)?;
+        Ok(indices_to_pyarray(py, &self.inner.query_frustum(&frustum)))
+    }
+
+    /// 节点统计字典
+    /// depth/count/parent/is_leaf: [K]；centroid/min/max/bounds_min/bounds_max: [K,3] float64 全局坐标
+    fn node_stats(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let nodes = self.inner.nodes();
+        let k = nodes.len();
+        let dict = PyDict::new(py);
+
+        let depth: Vec<i64> = nodes.iter().map(|n| n.depth as i64).collect();
+        let count: Vec<i64> = nodes.iter().map(|n| n.point_count() as i64).collect();
+        let parent: Vec<i64> = nodes
+            .iter()
+            .map(|n| n.parent.map_or(-1, |p| p as i64))
+            .collect();
+        let is_leaf: Vec<bool> = nodes.iter().map(|n| n.is_leaf()).collect();
+        dict.set_item(
+            "depth",
+            IntoPyArray::into_pyarray(Array1::from_vec(depth), py),
+        )?;
+        dict.set_item(
+            "count",
+            IntoPyArray::into_pyarray(Array1::from_vec(count), py),
+        )?;
+        dict.set_item(
+            "parent",
+            IntoPyArray::into_pyarray(Array1::from_vec(parent), py),
+        )?;
+        dict.set_item(
+            "is_leaf",
+            IntoPyArray::into_pyarray(Array1::from_vec(is_leaf), py),
+        )?;
+
+        let vec3_fields: [(&str, fn(&point_cloud::octree::OctreeNode) -> [f32; 3]); 5] = [
+            ("centroid", |n| n.centroid),
+            ("min", |n| n.min),
+            ("max", |n| n.max),
+            ("bounds_min", |n| n.bounds_min),
+            ("bounds_max", |n| n.bounds_max),
+        ];
+        let origin = self.origin;
+        for (name, field) in vec3_fields {
+            let flat: Vec<f64> = nodes
+                .iter()
+                .flat_map(|n| {
+                    let local = field(n);
+                    std::array::from_fn::<f64, 3, _>(|k| local[k] as f64 + origin[k])
+                })
+                .collect();
+            dict.set_item(name, rows_to_pyarray(py, flat, k, 3)?)?;
+        }
+        Ok(dict.into())
+    }
+
+    /// 节点内的点索引（int64数组）
+    fn node_indices(&self, py: Python, node: usize) -> PyResult<Py<PyAny>> {
+        if node >= self.inner.node_count() {
+            return Err(pyo3::exceptions::PyIndexError::new_err(format!(
+                "节点序号{}超出范围（共{}个节点）",
+                node,
+                self.inner.node_count()
+            )));
+        }
+        Ok(indices_to_pyarray(py, self.inner.node_indices(node)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Octree(nodes={}, depth={}, points={}, max_depth={}, leaf_size={})",
+            self.inner.node_count(),
+            self.inner.depth(),
+            self.inner.point_count(),
+            self.inner.max_depth(),
+            self.inner.leaf_size()
+        )
+    }
+}
+
+/// 点索引转换为 int64 numpy 数组
+fn indices_to_pyarray(py: Python, indices: &[usize]) -> Py<PyAny> {
+    let indices: Vec<i64> = indices.iter().map(|&idx| idx as i64).collect();
+    IntoPyArray::into_pyarray(Array1::from_vec(indices), py).into()
+}
+
+// ============ 流式LAS读写 ============
+
+/// LAS/LAZ 分块读取迭代器（由 PointCloud.iter_las 创建）
+#[pyclass(name = "LasChunkIterator", unsendable)]
+pub struct PyLasChunkIterator {
+    inner: LasChunkReader,
+}
+
+#[pymethods]
+impl PyLasChunkIterator {
+    /// 文件总点数
+    #[getter]
+    fn total_points(&self) -> u64 {
+        self.inner.total_points()
+    }
+
+    /// 尚未读取的点数
+    #[getter]
+    fn remaining(&self) -> u64 {
+        self.inner.remaining()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PyResult<Option<PyPointCloud>> {
+        let chunk = self.inner.next_chunk().map_err(PyErr::from)?;
+        Ok(chunk.map(|inner| PyPointCloud { inner }))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LasChunkIterator(total_points={}, remaining={})",
+            self.inner.total_points(),
+            self.inner.remaining()
+        )
+    }
+}
+
+/// LAS/LAZ 流式写入器，支持 with 语句，关闭时回写头部包围盒与点数
+#[pyclass(name = "LasWriter", unsendable)]
+pub struct PyLasWriter {
+    inner: LasStreamWriter,
+}
+
+#[pymethods]
+impl PyLasWriter {
+    /// point_format 为 None 时按第一块的内容自动选择
+    #[new]
+    #[pyo3(signature = (path, compress = false, version = None, scale = None, offset = None, point_format = None))]
+    fn new(
+        path: &str,
+        compress: bool,
+        version: Option<(u8, u8)>,
+        scale: Option<[f64; 3]>,
+        offset: Option<[f64; 3]>,
+        point_format: Option<u8>,
+    ) -> PyResult<Self> {
+        let options = LasWriteOptions {
+            compress,
+            version,
+            scale,
+            offset,
+            point_format,
+        };
+        let inner = LasStreamWriter::new(path, options).map_err(PyErr::from)?;
+        Ok(PyLasWriter { inner })
+    }
+
+    /// 已写入的点数
+    #[getter]
+    fn point_count(&self) -> u64 {
+        self.inner.point_count()
+    }
+
+    /// 追加一块点云
+    fn write(&mut self, chunk: PyRef<'_, PyPointCloud>) -> PyResult<()> {
+        self.inner.write_chunk(&chunk.inner).map_err(PyErr::from)
+    }
+
+    /// 关闭文件并回写头部
+    fn close(&mut self) -> PyResult<()> {
+        self.inner.close().map_err(PyErr::from)
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type = None, _exc_value = None, _traceback = None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<Bound<'_, PyAny>>,
+        _exc_value: Option<Bound<'_, PyAny>>,
+        _traceback: Option<Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        if self.inner.is_open() {
+            self.inner.close().map_err(PyErr::from)?;
+        }
+        Ok(false)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LasWriter(point_count={})", self.inner.point_count())
+    }
+}
+
+// ============ Arrow互通 ============
+
+/// 持有RecordBatch的Arrow流对象，供pyarrow经PyCapsule接口导入
+#[pyclass(name = "ArrowStream", frozen)]
+struct PyArrowStream {
+    batch: arrow::record_batch::RecordBatch,
+}
+
+#[pymethods]
+impl PyArrowStream {
+    #[pyo3(signature = (requested_schema = None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<Bound<'py, pyo3::PyAny>>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // 不按 requested_schema 转换列类型，由消费方自行转换（PyCapsule 接口允许）
+        let _ = requested_schema;
+        interop::arrow::export_stream(py, self.batch.clone())
+    }
+}
+
+impl PyPointCloud {
+    /// 按列参数导出为Arrow流对象
+    fn arrow_stream<'py>(
+        &self,
+        py: Python<'py>,
+        columns: io::table::TableColumnNames,
+    ) -> PyResult<Bound<'py, PyArrowStream>> {
+        let batch = self.inner.to_record_batch(columns).map_err(PyErr::from)?;
+        Bound::new(py, PyArrowStream { batch })
+    }
+
+    /// 从实现Arrow PyCapsule接口的对象构建点云
+    fn from_arrow_data(
+        data: &Bound<'_, pyo3::PyAny>,
+        columns: io::table::TableColumnNames,
+    ) -> PyResult<Self> {
+        let batches = interop::arrow::import_batches(data)?;
+        let inner = HighPerformancePointCloud::from_record_batches(&batches, columns)
+            .map_err(PyErr::from)?;
+        Ok(PyPointCloud { inner })
+    }
+}
+
+// ============ DLPack互通 ============
+
+/// 主机内存张量，经 DLPack 或 NumPy 数组接口共享缓冲区（NdArray 后端上即点云的存储）
+#[pyclass(name = "HostTensor", frozen)]
+pub struct PyHostTensor {
+    inner: Arc<HostTensor>,
+}
+
+#[pymethods]
+impl PyHostTensor {
+    #[getter]
+    fn shape(&self) -> Vec<usize> {
+        self.inner.shape().to_vec()
+    }
+
+    /// 是否与点云共享存储（未复制）
+    #[getter]
+    fn shared(&self) -> bool {
+        self.inner.is_shared()
+    }
+
+    /// NumPy 数组接口（np.asarray 得到共享缓冲区的视图）
+    #[getter]
+    fn __array_interface__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let interface = PyDict::new(py);
+        interface.set_item("shape", pyo3::types::PyTuple::new(py, self.inner.shape())?)?;
+        interface.set_item("typestr", self.inner.typestr().map_err(PyErr::from)?)?;
+        interface.set_item("data", (self.inner.data_ptr(), true))?;
+        interface.set_item("version", 3)?;
+        Ok(interface)
+    }
+
+    #[pyo3(signature = (*, stream = None, max_version = None, dl_device = None, copy = None))]
+    fn __dlpack__<'py>(
+        &self,
+        py: Python<'py>,
+        stream: Option<Bound<'py, pyo3::PyAny>>,
+        max_version: Option<(u32, u32)>,
+        dl_device: Option<(i32, i32)>,
+        copy: Option<bool>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        // CPU 张量无需同步 stream；只导出未版本化的 dltensor 胶囊
+        let _ = (stream, max_version);
+        check_dlpack_device(dl_device)?;
+        if copy == Some(true) {
+            return interop::dlpack::export_dlpack(py, &Arc::new(self.inner.deep_copy()));
+        }
+        interop::dlpack::export_dlpack(py, &self.inner)
+    }
+
+    fn __dlpack_device__(&self) -> (i32, i32) {
+        (interop::dlpack::DL_CPU, 0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "HostTensor(shape={:?}, typestr={})",
+            self.inner.shape(),
+            self.inner.typestr().unwrap_or("?")
+        )
+    }
+}
+
 /// Python下采样策略枚举
 #[pyclass(name = "DownsampleStrategy")]
 pub struct PyDownsampleStrategy;
@@ -577,24 +2110,114 @@ fn RANDOM() -> i32 {
     fn CENTROID() -> i32 {
         1
     }
+
+    /// 聚合策略：质心坐标 + 各通道平均值
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn MEAN() -> i32 {
+        2
+    }
+
+    /// 聚合策略：质心坐标 + 各通道中位数
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn MEDIAN() -> i32 {
+        3
+    }
+
+    /// 聚合策略：质心坐标 + 各通道最大值
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn MAX() -> i32 {
+        4
+    }
+
+    /// 聚合策略：质心坐标 + 各通道众数（适用于分类等整数型属性）
+    #[classattr]
+    #[allow(non_snake_case)]
+    fn MODE() -> i32 {
+        5
+    }
 }
 
-// ============ 辅助函数：从 PyAny 读取 numpy 数组 ============
+/// 策略编号转换为体素聚合方式，非聚合策略返回 None
+fn aggregation_from_code(code: i32) -> Option<VoxelAggregation> {
+    match code {
+        2 => Some(VoxelAggregation::Mean),
+        3 => Some(VoxelAggregation::Median),
+        4 => Some(VoxelAggregation::Max),
+        5 => Some(VoxelAggregation::Mode),
+        _ => None,
+    }
+}
 
-use numpy::{PyArray1, PyArrayMethods};
-use utils::tensor::Tensor1;
+// ============ 辅助函数：从 PyAny 读取 numpy 数组 ============
 
-/// 从 PyAny 读取 1D 数组作为属性，仅支持 f32 dtype
-fn read_attribute_array(obj: &Bound<'_, pyo3::PyAny>) -> PyResult<Tensor1> {
-    let arr = obj.cast::<PyArray1<f32>>().map_err(|_| {
+/// 从 PyAny 读取属性数组：1D 支持 bool/整数/float32/float64 并保持 dtype，2D 为 float32 多维属性
+fn read_attribute_array(obj: &Bound<'_, pyo3::PyAny>) -> PyResult<AttributeData> {
+    interop::numpy::read_attribute_array(obj).ok_or_else(|| {
         pyo3::exceptions::PyTypeError::new_err(
-            "必须是dtype=float32的1D numpy数组，请使用 arr.astype(np.float32) 转换",
+            "属性必须是dtype为bool/int8-64/uint8-64/float32/float64的1D numpy数组，\
+             或dtype=float32的[M,D]数组",
         )
-    })?;
+    })
+}
+
+/// 解析列过滤条件：(min, max) 元组为闭区间（None 表示不限），列表为取值集合
+fn parse_column_filters(
+    filters: HashMap<String, Bound<'_, pyo3::PyAny>>,
+) -> PyResult<Vec<io::table::ColumnFilter>> {
+    filters
+        .into_iter()
+        .map(|(column, condition)| {
+            if let Ok((min, max)) = condition.extract::<(Option<f64>, Option<f64>)>() {
+                Ok(io::table::ColumnFilter::Range { column, min, max })
+            } else if let Ok(values) = condition.extract::<Vec<f64>>() {
+                Ok(io::table::ColumnFilter::Values { column, values })
+            } else {
+                Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                    "列'{}'的过滤条件必须是(min, max)元组或取值列表",
+                    column
+                )))
+            }
+        })
+        .collect()
+}
+
+/// DLPack 导出只支持 CPU 设备
+fn check_dlpack_device(dl_device: Option<(i32, i32)>) -> PyResult<()> {
+    match dl_device {
+        Some((device_type, _)) if device_type != interop::dlpack::DL_CPU => Err(
+            pyo3::exceptions::PyBufferError::new_err("HostTensor 只能导出到 CPU 设备"),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// 解析相机内参（3x3）与可选外参（4x4）
+fn parse_camera(
+    intrinsics: &[Vec<f32>],
+    extrinsic: Option<&[Vec<f64>]>,
+) -> PyResult<(
+    point_cloud::camera::PinholeIntrinsics,
+    Option<point_cloud::camera::Extrinsic>,
+)> {
+    use point_cloud::camera::{Extrinsic, PinholeIntrinsics};
+
+    let intrinsics = PinholeIntrinsics::from_matrix(intrinsics).map_err(PyErr::from)?;
+    let extrinsic = extrinsic
+        .map(Extrinsic::from_matrix)
+        .transpose()
+        .map_err(PyErr::from)?;
+    Ok((intrinsics, extrinsic))
+}
 
-    let readonly = arr.readonly();
-    let slice = readonly
-        .as_slice()
-        .map_err(|_| pyo3::exceptions::PyValueError::new_err("无法读取数据，数组可能不连续"))?;
-    Ok(utils::tensor::tensor1_from_slice(slice))
+/// 解析可选的畸变系数（[k1, k2, p1, p2] 或 [k1, k2, p1, p2, k3]）
+fn parse_distortion(
+    distortion: Option<&[f32]>,
+) -> PyResult<Option<point_cloud::camera::Distortion>> {
+    distortion
+        .map(point_cloud::camera::Distortion::from_slice)
+        .transpose()
+        .map_err(PyErr::from)
 }
@@ -4,7 +4,23 @@
 基于Burn张量库的批量张量运算，支持LAZ/LAS/Parquet/CSV多格式I/O
 """
 
-from ._core import DownsampleStrategy, PointCloud
+from ._core import (
+    DownsampleStrategy,
+    HostTensor,
+    LasChunkIterator,
+    LasWriter,
+    Octree,
+    PointCloud,
+    VoxelGrid,
+)
 
 __version__ = "0.1.0"
-__all__ = ["PointCloud", "DownsampleStrategy"]
+__all__ = [
+    "PointCloud",
+    "DownsampleStrategy",
+    "VoxelGrid",
+    "Octree",
+    "LasChunkIterator",
+    "LasWriter",
+    "HostTensor",
+]
@@ -4,7 +4,7 @@
 类型注解和API规范（基于NumPy数组，dtype=float32）
 """
 
-from typing import Dict, List, Optional, Tuple
+from typing import Any, Dict, List, Optional, Sequence, Tuple
 
 import numpy as np
 from numpy.typing import NDArray
@@ -16,12 +16,21 @@ class PointCloud:
         - xyz: [N,3]的三维坐标 (float32)
         - intensity: 可选的强度值 [N] (float32)
         - rgb: 可选的RGB颜色通道 [N] (float32 输入, uint8 输出)
-        - attributes: 自定义属性字典 (float32)
+        - attributes: 自定义属性字典 (bool/整数/float32/float64，保持 dtype)
     """
 
     def __new__(cls) -> "PointCloud": ...
     @staticmethod
-    def from_xyz(xyz: NDArray[np.float32]) -> "PointCloud": ...
+    def from_xyz(xyz: NDArray[np.float32] | NDArray[np.float64]) -> "PointCloud":
+        """float64 坐标按包围盒自动设置原点，张量保存 float32 局部坐标"""
+        ...
+    @staticmethod
+    def from_dlpack(xyz: Any) -> "PointCloud":
+        """从实现 __dlpack__ 的 [N, 3] CPU 张量（torch.Tensor、numpy 数组等）创建点云
+
+        float64 为全局坐标并自动设置原点，其他类型按 float32 读取
+        """
+        ...
     @staticmethod
     def from_xyz_intensity(
         xyz: NDArray[np.float32], intensity: NDArray[np.float32]
@@ -43,8 +52,70 @@ def from_xyz_intensity_rgb(
     ) -> "PointCloud": ...
     @staticmethod
     def from_dict(data: Dict[str, NDArray[np.float32]]) -> "PointCloud": ...
+    @staticmethod
+    def from_depth_image(
+        depth: NDArray,
+        intrinsics: List[List[float]],
+        depth_scale: float = 1000.0,
+        depth_trunc: float = 1000.0,
+        extrinsic: Optional[List[List[float]]] = None,
+    ) -> "PointCloud":
+        """从深度图（uint16/float32，[H,W]）反投影创建点云"""
+        ...
+    @staticmethod
+    def from_rgbd(
+        color: NDArray[np.uint8],
+        depth: NDArray,
+        intrinsics: List[List[float]],
+        depth_scale: float = 1000.0,
+        depth_trunc: float = 1000.0,
+        extrinsic: Optional[List[List[float]]] = None,
+    ) -> "PointCloud":
+        """从RGB-D图像（color [H,W,3] uint8）反投影创建带颜色的点云"""
+        ...
     def point_count(self) -> int: ...
-    def get_xyz(self) -> NDArray[np.float32]: ...
+    def get_xyz(self) -> NDArray[np.float64]:
+        """float64 全局坐标（局部坐标 + 原点）"""
+        ...
+    def host_tensor(self, name: str = "xyz", copy: bool | None = None) -> "HostTensor":
+        """取出主机张量：xyz（同 get_xyz）、local_xyz（float32 局部坐标）、
+        rgb（[N,3] uint8）、intensity、gps_time 或自定义属性名
+
+        NdArray 后端上 local_xyz、intensity、float32 与多维属性直接共享点云的存储，
+        其余数据取出时复制一次；copy=False 时需要复制的数据抛出 BufferError，
+        copy=True 时总是复制
+        """
+        ...
+    def __dlpack__(
+        self,
+        *,
+        stream: Any = None,
+        max_version: Tuple[int, int] | None = None,
+        dl_device: Tuple[int, int] | None = None,
+        copy: bool | None = None,
+    ) -> Any:
+        """DLPack 导出 XYZ（同 get_xyz），torch.from_dlpack(pc) 直接使用
+
+        每次导出都复制一份 float64 缓冲区，copy=False 时抛出 BufferError；
+        共享局部坐标请使用 host_tensor("local_xyz")
+        """
+        ...
+    def __dlpack_device__(self) -> Tuple[int, int]: ...
+    def origin(self) -> Tuple[float, float, float]:
+        """局部坐标原点（全局坐标 = 局部坐标 + 原点）"""
+        ...
+    def set_origin(self, origin: Tuple[float, float, float]) -> None:
+        """更换局部坐标原点，全局坐标不变"""
+        ...
+    def crs(self) -> Optional[str]:
+        """坐标参考系（"EPSG:n"、WKT 或 "ENU:lon,lat,h"），未设置时为 None"""
+        ...
+    def set_crs(self, crs: Optional[str] = None) -> None:
+        """设置坐标参考系（仅标记元数据，不变换坐标），None 表示清除"""
+        ...
+    def reproject(self, target_crs: str) -> "PointCloud":
+        """重投影到目标坐标系（经纬度/UTM/ECEF/ENU），返回新点云"""
+        ...
     def has_intensity(self) -> bool: ...
     def has_rgb(self) -> bool: ...
     def get_intensity(self) -> Optional[NDArray[np.float32]]: ...
@@ -58,10 +129,18 @@ def set_rgb(
         g: NDArray[np.float32],
         b: NDArray[np.float32],
     ) -> None: ...
-    def add_attribute(self, name: str, data: NDArray[np.float32]) -> None: ...
-    def set_attribute(self, name: str, data: NDArray[np.float32]) -> None: ...
+    def add_attribute(self, name: str, data: NDArray[np.generic]) -> None:
+        """添加自定义属性，1D 支持 bool/int8-64/uint8-64/float32/float64
+        并保持 dtype，2D float32 [M,D] 为多维属性"""
+        ...
+    def set_attribute(self, name: str, data: NDArray[np.generic]) -> None:
+        """设置自定义属性，1D 支持 bool/int8-64/uint8-64/float32/float64
+        并保持 dtype，2D float32 [M,D] 为多维属性"""
+        ...
     def attribute_names(self) -> List[str]: ...
-    def get_attribute(self, name: str) -> Optional[NDArray[np.float32]]: ...
+    def get_attribute(self, name: str) -> Optional[NDArray[np.generic]]:
+        """获取自定义属性，dtype 与形状与写入时一致"""
+        ...
     def remove_attribute(self, name: str) -> None: ...
     def clear_attributes(self) -> None: ...
     def set_all_attributes(self, attributes: Dict[str, List[float]]) -> None: ...
@@ -69,16 +148,186 @@ def has_attributes(self, names: List[str]) -> bool: ...
     def attribute_info(self) -> List[Tuple[str, int]]: ...
     def remove_intensity(self) -> None: ...
     def remove_rgb(self) -> None: ...
+    def has_gps_time(self) -> bool: ...
+    def get_gps_time(self) -> Optional[NDArray[np.float64]]: ...
+    def set_gps_time(self, gps_time: NDArray[np.float64]) -> None: ...
+    def remove_gps_time(self) -> None: ...
+    def set_las_extra_bytes(
+        self,
+        name: str,
+        data_type: str = "float32",
+        scale: Optional[float] = None,
+        offset: Optional[float] = None,
+        no_data: Optional[float] = None,
+        description: Optional[str] = None,
+    ) -> None:
+        """声明自定义属性写入 LAS 时的 Extra Bytes 类型、缩放与无效值
+
+        data_type: uint8/int8/uint16/int16/uint32/int32/uint64/int64/float32/float64
+        """
+        ...
+    def las_extra_bytes(self) -> List[Dict[str, object]]:
+        """已声明的 Extra Bytes 描述符（读取 LAS 时从 Extra Bytes VLR 填充）"""
+        ...
+    def las_header(self) -> Optional[Dict[str, object]]:
+        """读取 LAS 时保留的头部信息，写出时沿用
+
+        包含 version、scale、offset、system_identifier、generating_software、
+        file_source_id，以及 vlrs/evlrs 的 (user_id, record_id, description)
+        """
+        ...
     @staticmethod
     def delete_file(path: str) -> None: ...
-    def transform(self, matrix: NDArray[np.float32]) -> "PointCloud": ...
+    def transform(self, matrix: NDArray[np.float64]) -> "PointCloud":
+        """按 f64 全局坐标应用 3x3 或 4x4 变换矩阵"""
+        ...
     def rigid_transform(
-        self, rotation: NDArray[np.float32], translation: NDArray[np.float32]
-    ) -> "PointCloud": ...
-    def voxel_downsample(self, voxel_size: float, strategy: int) -> "PointCloud": ...
+        self, rotation: NDArray[np.float64], translation: NDArray[np.float64]
+    ) -> "PointCloud":
+        """按 f64 全局坐标应用旋转与平移，平移可为 UTM 量级"""
+        ...
+    def voxel_downsample(
+        self,
+        voxel_size: float,
+        strategy: int,
+        seed: Optional[int] = None,
+        attribute_modes: Optional[Dict[str, int]] = None,
+    ) -> "PointCloud":
+        """体素下采样
+
+        RANDOM/CENTROID 从体素内选取一个已有点；MEAN/MEDIAN/MAX/MODE
+        输出体素质心并归约 intensity/RGB/GPS 时间/自定义属性，attribute_modes
+        可按通道（"intensity"、"rgb"、"gps_time" 或属性名）覆盖聚合方式
+        """
+        ...
+    def farthest_point_sampling(
+        self, n: int, start_index: int = 0
+    ) -> Tuple["PointCloud", NDArray[np.int64]]:
+        """最远点采样，返回采样后的点云与原始索引
+
+        不同位置的点少于 n 个时提前停止，返回的点数少于 n
+        """
+        ...
+    def random_sample(
+        self,
+        n: Optional[int] = None,
+        ratio: Optional[float] = None,
+        seed: Optional[int] = None,
+    ) -> "PointCloud":
+        """随机采样（按点数或比例），seed 可复现"""
+        ...
+    def uniform_sample(self, k: int) -> "PointCloud":
+        """每隔 k 个点取一个"""
+        ...
+    def poisson_disk_sample(
+        self, min_distance: float, seed: Optional[int] = None
+    ) -> "PointCloud":
+        """泊松圆盘采样，采样点间距不小于 min_distance"""
+        ...
+    def to_bev(
+        self,
+        x_range: Tuple[float, float],
+        y_range: Tuple[float, float],
+        resolution: float,
+        channels: Optional[List[str]] = None,
+    ) -> NDArray[np.float32]:
+        """生成BEV伪图像，形状[H,W,C]，通道可选 height/density/intensity
+
+        x_range/y_range 为全局坐标范围，height/intensity 通道的空像素为 NaN，
+        density 为 0；像素数超过 2^28 时报 ValueError
+        """
+        ...
+    def to_range_image(
+        self,
+        h_fov: Tuple[float, float] = (-180.0, 180.0),
+        v_fov: Tuple[float, float] = (-25.0, 3.0),
+        width: int = 1024,
+        height: int = 64,
+        sensor: Optional[Tuple[float, float, float]] = None,
+    ) -> Dict[str, NDArray]:
+        """生成球面距离图像，返回 depth/index_map/可选 intensity
+
+        sensor 为传感器的全局坐标，距离相对该位置计算，默认为点云原点
+        """
+        ...
+    def project_to_image(
+        self,
+        intrinsics: List[List[float]],
+        extrinsic: Optional[List[List[float]]],
+        width: int,
+        height: int,
+        distortion: Optional[List[float]] = None,
+    ) -> Tuple[NDArray[np.float32], NDArray[np.bool_]]:
+        """投影到相机图像，返回像素坐标 [N,2] 与可见性 [N]"""
+        ...
+    def colorize_from_image(
+        self,
+        image: NDArray[np.uint8],
+        intrinsics: List[List[float]],
+        extrinsic: Optional[List[List[float]]] = None,
+        distortion: Optional[List[float]] = None,
+    ) -> NDArray[np.bool_]:
+        """从 [H,W,3] 图像双线性采样着色，返回可见性"""
+        ...
     @staticmethod
-    def from_las(path: str) -> "PointCloud": ...
-    def to_las(self, path: str, compress: bool = False) -> None: ...
+    def from_las(path: str) -> "PointCloud":
+        """读取 LAS/LAZ，标准字段（classification、return_number 等）作为同名属性，
+        GPS 时间以 float64 保存"""
+        ...
+    @staticmethod
+    def iter_las(path: str, chunk_size: int = 1000000) -> "LasChunkIterator":
+        """按块读取 LAS/LAZ 文件，每块最多 chunk_size 个点"""
+        ...
+    @staticmethod
+    def is_copc(path: str) -> bool: ...
+    @staticmethod
+    def copc_info(path: str) -> Optional[Dict[str, object]]:
+        """COPC 信息 VLR，非 COPC 文件返回 None
+
+        键：center/halfsize/spacing/root_hier_offset/root_hier_size/gpstime_range
+        """
+        ...
+    @staticmethod
+    def from_copc(
+        path: str,
+        bounds: Optional[
+            Tuple[Tuple[float, float, float], Tuple[float, float, float]]
+        ] = None,
+        max_level: Optional[int] = None,
+    ) -> "PointCloud":
+        """按包围盒与最大层级读取 COPC 文件，只读取相交的层级页并解码相交的数据块
+
+        bounds 为 ((min_x, min_y, min_z), (max_x, max_y, max_z))，max_level 根节点为 0
+        """
+        ...
+    def to_las(
+        self,
+        path: str,
+        compress: bool = False,
+        version: Optional[Tuple[int, int]] = None,
+        scale: Optional[Tuple[float, float, float]] = None,
+        offset: Optional[Tuple[float, float, float]] = None,
+        point_format: Optional[int] = None,
+    ) -> None:
+        """写入 LAS/LAZ，point_format 为 None 时按点云内容自动选择点格式（0/1/2/3/6/7/8）
+
+        version/scale/offset 为 None 时沿用读取时保留的头部，
+        坐标系 WKT、GeoKeys 等 VLR/EVLR 原样写回
+        """
+        ...
+    def to_potree(
+        self,
+        output_dir: str,
+        name: Optional[str] = None,
+        max_depth: int = 12,
+        leaf_size: int = 20000,
+        scale: float = 0.001,
+    ) -> None:
+        """导出为 Potree 2.0 格式目录（hierarchy.bin、octree.bin、metadata.json）
+
+        intensity 与 RGB 按 uint16 写出，自定义属性按 float 写出
+        """
+        ...
     @staticmethod
     def from_csv(
         path: str,
@@ -90,7 +339,13 @@ def from_csv(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> "PointCloud": ...
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """attributes 列出的列按原类型读为属性；all_attributes=True 时
+        坐标、强度、颜色以外的全部数值列都读为属性，exclude_attributes 排除"""
+        ...
     @staticmethod
     def from_parquet(
         path: str,
@@ -101,7 +356,37 @@ def from_parquet(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> "PointCloud": ...
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """属性参数同 from_csv"""
+        ...
+    @staticmethod
+    def scan_parquet(
+        path: str,
+        bbox: Sequence[float] | None = None,
+        filters: Dict[str, Tuple[float | None, float | None] | List[float]]
+        | None = None,
+        limit: int | None = None,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """延迟扫描 Parquet 文件、glob 模式或目录，只读取所需列
+
+        bbox 为 (min_x, min_y, max_x, max_y) 或含 Z 的 6 元组（全局坐标），
+        filters 按列给出 (min, max) 闭区间或取值列表，与 bbox 一起下推到扫描；
+        limit 限制过滤后读取的行数。属性参数同 from_csv
+        """
+        ...
     def to_csv(
         self,
         path: str,
@@ -113,7 +398,11 @@ def to_csv(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> None: ...
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> None:
+        """attributes 为 None 时写出全部自定义属性，exclude_attributes 排除"""
+        ...
     def to_parquet(
         self,
         path: str,
@@ -124,7 +413,74 @@ def to_parquet(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> None: ...
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> None:
+        """属性参数同 to_csv"""
+        ...
+    def to_parquet_dataset(
+        self,
+        root: str,
+        tile_size: float,
+        scheme: str = "grid",
+        row_group_size: int | None = None,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+        overwrite: bool = False,
+    ) -> None:
+        """按 XY 瓦片写出 Hive 分区的 Parquet 数据集
+
+        scheme 为 "grid"（tile_x=i/tile_y=j）、"morton" 或 "hilbert"
+        （tile=曲线键）；瓦片内按空间填充曲线排序，根目录 _dataset.json
+        记录瓦片包围盒，scan_parquet 据此跳过不相交的瓦片。属性参数同 to_csv
+
+        root 非空时报错；overwrite=True 时先删除旧索引与瓦片分区目录
+        """
+        ...
+    @staticmethod
+    def from_ipc(
+        path: str,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """读取 Arrow IPC 文件（.arrow/.feather，Feather v2），属性参数同 from_csv
+
+        坐标系取自 schema 元数据的 "crs" 键
+        """
+        ...
+    def to_ipc(
+        self,
+        path: str,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> None:
+        """写出 Arrow IPC 文件（Feather v2），多维属性为列表列，属性参数同 to_csv
+
+        坐标系写入 schema 元数据的 "crs" 键
+        """
+        ...
     @staticmethod
     def load_from_file(
         path: str,
@@ -135,7 +491,12 @@ def load_from_file(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> "PointCloud": ...
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """属性参数同 from_csv"""
+        ...
     def save_to_file(
         self,
         path: str,
@@ -146,9 +507,118 @@ def save_to_file(
         rgb_r: str | None = None,
         rgb_g: str | None = None,
         rgb_b: str | None = None,
-    ) -> None: ...
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> None:
+        """属性参数同 to_csv"""
+        ...
     def memory_usage(self) -> int: ...
-    def to_dict(self) -> Dict[str, NDArray[np.float32]]: ...
+    def to_dict(self) -> Dict[str, NDArray[np.generic]]: ...
+    @staticmethod
+    def from_arrow(
+        data: Any,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """从实现 Arrow PyCapsule 接口的对象构建点云
+
+        支持 pyarrow.Table/RecordBatch/RecordBatchReader、polars.DataFrame、
+        DuckDB 查询结果等（__arrow_c_stream__ 或 __arrow_c_array__），
+        定长列表与列表列读为多维属性，所用列含空值时报错。属性参数同 from_csv
+        """
+        ...
+    def to_arrow(
+        self,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> Any:
+        """转换为 pyarrow.Table（需要安装 pyarrow），列布局同 to_parquet
+
+        从点云复制一次生成各列，经 Arrow C 数据接口移交列缓冲区。属性参数同 to_csv
+        """
+        ...
+    def __arrow_c_stream__(self, requested_schema: Any = None) -> Any:
+        """Arrow PyCapsule 流接口，按默认列名导出全部自定义属性，
+        可直接传给 pyarrow.table()、polars.DataFrame() 或 DuckDB"""
+        ...
+    @staticmethod
+    def from_polars(
+        df: Any,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """从 polars.DataFrame 构建点云，列表列读为多维属性，属性参数同 from_csv"""
+        ...
+    def to_polars(
+        self,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> Any:
+        """转换为 polars.DataFrame（需要安装 polars），列布局同 to_parquet，
+        属性参数同 to_csv"""
+        ...
+    @staticmethod
+    def from_pandas(
+        df: Any,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        all_attributes: bool = False,
+        exclude_attributes: List[str] | None = None,
+    ) -> "PointCloud":
+        """从 pandas.DataFrame 构建点云（经 pyarrow 转换，忽略索引），
+        属性参数同 from_csv"""
+        ...
+    def to_pandas(
+        self,
+        x: str | None = None,
+        y: str | None = None,
+        z: str | None = None,
+        intensity: str | None = None,
+        rgb_r: str | None = None,
+        rgb_g: str | None = None,
+        rgb_b: str | None = None,
+        attributes: List[str] | None = None,
+        exclude_attributes: List[str] | None = None,
+    ) -> Any:
+        """转换为 pandas.DataFrame（需要安装 pandas 与 pyarrow），
+        多维属性列的元素为 numpy 数组。属性参数同 to_csv"""
+        ...
     def clone(self) -> "PointCloud": ...
     def __repr__(self) -> str: ...
 
@@ -156,7 +626,165 @@ class DownsampleStrategy:
     """下采样策略枚举"""
 
     RANDOM: int
-    """随机采样策略"""
+    """随机采样策略（通过 voxel_downsample 的 seed 参数复现）"""
 
     CENTROID: int
     """重心采样策略（最接近体素中心的点）"""
+
+    MEAN: int
+    """聚合策略：质心坐标 + 各通道平均值"""
+
+    MEDIAN: int
+    """聚合策略：质心坐标 + 各通道中位数"""
+
+    MAX: int
+    """聚合策略：质心坐标 + 各通道最大值"""
+
+    MODE: int
+    """聚合策略：质心坐标 + 各通道众数（适用于分类等整数型属性）"""
+
+class VoxelGrid:
+    """体素网格：每个非空体素的点索引、计数、质心与协方差
+
+    点索引指向构建时的点云，点云修改后需要重新构建
+    """
+
+    def __init__(
+        self,
+        cloud: PointCloud,
+        voxel_size: float,
+        origin: Optional[Tuple[float, float, float]] = None,
+    ) -> None:
+        """origin 为网格原点的全局坐标，默认为 (0, 0, 0)，体素边界与全局网格对齐"""
+        ...
+    @property
+    def voxel_size(self) -> float: ...
+    @property
+    def origin(self) -> Tuple[float, float, float]: ...
+    def voxel_count(self) -> int: ...
+    def __len__(self) -> int: ...
+    def counts(self) -> NDArray[np.int64]: ...
+    def voxel_coords(self) -> NDArray[np.int64]:
+        """每个体素相对网格原点的整数坐标 [V,3]"""
+        ...
+    def centroids(self) -> NDArray[np.float64]:
+        """每个体素的点质心 [V,3]（全局坐标）"""
+        ...
+    def covariances(self) -> NDArray[np.float32]:
+        """每个体素的协方差 [V,3,3]（无偏估计，单点体素为零矩阵）"""
+        ...
+    def point_indices(self, voxel: int) -> NDArray[np.int64]: ...
+    def point_voxels(self) -> NDArray[np.int64]:
+        """构建点云中每个点所属的体素 [N]"""
+        ...
+    def lookup(
+        self, points: NDArray[np.float32] | NDArray[np.float64]
+    ) -> NDArray[np.int64]:
+        """查找 [M,3] 全局位置所在的体素，空体素为 -1"""
+        ...
+    def neighbors(self, voxel: int, radius: int = 1) -> NDArray[np.int64]:
+        """切比雪夫距离不超过 radius 的非空邻域体素（不含自身）"""
+        ...
+    def to_point_cloud(self) -> PointCloud:
+        """导出体素中心为点云，附带 point_count 属性"""
+        ...
+    def __repr__(self) -> str: ...
+
+class Octree:
+    """八叉树：LOD 分层抽样、包围盒/视锥查询、节点统计
+
+    子节点序号按 (x << 2) | (y << 1) | z 排列；八叉树引用构建时的点云而不复制，
+    点索引指向该点云，点数改变后 extract_level 报错
+    """
+
+    def __init__(
+        self, cloud: PointCloud, max_depth: int = 8, leaf_size: int = 64
+    ) -> None: ...
+    def node_count(self) -> int: ...
+    def depth(self) -> int: ...
+    def level_indices(self, level: int) -> NDArray[np.int64]:
+        """第 level 层的抽样点索引：该层节点各取一个代表点，更浅的叶节点取全部点"""
+        ...
+    def extract_level(self, level: int) -> PointCloud: ...
+    def query_box(
+        self, min: Tuple[float, float, float], max: Tuple[float, float, float]
+    ) -> NDArray[np.int64]: ...
+    def query_frustum(
+        self,
+        view_projection: Optional[List[List[float]]] = None,
+        planes: Optional[List[List[float]]] = None,
+    ) -> NDArray[np.int64]:
+        """视锥查询：4x4 视图投影矩阵（OpenGL 约定）或 [K,4] 平面 ax+by+cz+d>=0
+
+        矩阵与平面均作用于全局坐标
+        """
+        ...
+    def node_stats(self) -> Dict[str, NDArray]:
+        """节点统计：depth/count/parent/is_leaf 与 centroid/min/max/bounds_min/bounds_max
+
+        坐标类统计为 float64 全局坐标
+        """
+        ...
+    def node_indices(self, node: int) -> NDArray[np.int64]: ...
+    def __repr__(self) -> str: ...
+
+class HostTensor:
+    """主机内存中的行优先张量（由 PointCloud.host_tensor 创建）
+
+    持有点云的共享存储或取出时复制的一份缓冲区：np.asarray 得到它的只读视图，
+    torch.from_dlpack/np.from_dlpack 经 DLPack 共享它，不再复制
+    """
+
+    @property
+    def shape(self) -> List[int]: ...
+    @property
+    def shared(self) -> bool:
+        """是否与点云共享存储（未复制）"""
+        ...
+    @property
+    def __array_interface__(self) -> Dict[str, Any]: ...
+    def __dlpack__(
+        self,
+        *,
+        stream: Any = None,
+        max_version: Tuple[int, int] | None = None,
+        dl_device: Tuple[int, int] | None = None,
+        copy: bool | None = None,
+    ) -> Any: ...
+    def __dlpack_device__(self) -> Tuple[int, int]: ...
+    def __repr__(self) -> str: ...
+
+class LasChunkIterator:
+    """LAS/LAZ 分块读取迭代器（由 PointCloud.iter_las 创建）"""
+
+    @property
+    def total_points(self) -> int: ...
+    @property
+    def remaining(self) -> int: ...
+    def __iter__(self) -> "LasChunkIterator": ...
+    def __next__(self) -> PointCloud: ...
+    def __repr__(self) -> str: ...
+
+class LasWriter:
+    """LAS/LAZ 流式写入器，逐块追加点，关闭时回写头部包围盒与点数
+
+    点格式取自 point_format，未指定时按第一块的内容自动选择，后续块必须能被该格式容纳；
+    后续块需要 GPS 时间、NIR 或扩展格式时应预先指定 point_format。支持 with 语句
+    """
+
+    def __init__(
+        self,
+        path: str,
+        compress: bool = False,
+        version: Optional[Tuple[int, int]] = None,
+        scale: Optional[Tuple[float, float, float]] = None,
+        offset: Optional[Tuple[float, float, float]] = None,
+        point_format: Optional[int] = None,
+    ) -> None: ...
+    @property
+    def point_count(self) -> int: ...
+    def write(self, chunk: PointCloud) -> None: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> "LasWriter": ...
+    def __exit__(self, exc_type, exc_value, traceback) -> bool: ...
+    def __repr__(self) -> str: ...
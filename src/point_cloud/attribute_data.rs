@@ -0,0 +1,475 @@
+/// 类型化自定义属性：float32 保存为张量参与计算，其他类型保存在主机内存以保持精度与位宽
+use crate::utils::error::Result;
+use crate::utils::tensor::{self, IntTensor1, Tensor1, Tensor2};
+
+/// 属性元素类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttributeType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+}
+
+impl AttributeType {
+    /// 类型名（与 numpy dtype 名称一致）
+    pub fn name(self) -> &'static str {
+        match self {
+            AttributeType::Bool => "bool",
+            AttributeType::U8 => "uint8",
+            AttributeType::I8 => "int8",
+            AttributeType::U16 => "uint16",
+            AttributeType::I16 => "int16",
+            AttributeType::U32 => "uint32",
+            AttributeType::I32 => "int32",
+            AttributeType::U64 => "uint64",
+            AttributeType::I64 => "int64",
+            AttributeType::F32 => "float32",
+            AttributeType::F64 => "float64",
+        }
+    }
+
+    /// 单个元素的字节数
+    pub fn size(self) -> usize {
+        match self {
+            AttributeType::Bool | AttributeType::U8 | AttributeType::I8 => 1,
+            AttributeType::U16 | AttributeType::I16 => 2,
+            AttributeType::U32 | AttributeType::I32 | AttributeType::F32 => 4,
+            AttributeType::U64 | AttributeType::I64 | AttributeType::F64 => 8,
+        }
+    }
+}
+
+/// 自定义属性数据（形状[M,]，Vector 为[M,D]）
+#[derive(Clone)]
+pub enum AttributeData {
+    F32(Tensor1),
+    /// float32 多维属性（特征向量、嵌入、类别概率等）
+    Vector(Tensor2),
+    F64(Vec<f64>),
+    I8(Vec<i8>),
+    I16(Vec<i16>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    U64(Vec<u64>),
+    Bool(Vec<bool>),
+}
+
+/// 按元素类型分派：F32 变体绑定 Tensor1，Vector 变体绑定 Tensor2，其余变体绑定主机端 Vec
+macro_rules! match_attribute {
+    (
+        $data:expr,
+        $tensor:ident => $f32:expr,
+        $matrix:ident => $vector:expr,
+        $values:ident => $host:expr
+    ) => {
+        match $data {
+            $crate::point_cloud::attribute_data::AttributeData::F32($tensor) => $f32,
+            $crate::point_cloud::attribute_data::AttributeData::Vector($matrix) => $vector,
+            $crate::point_cloud::attribute_data::AttributeData::F64($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::I8($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::I16($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::I32($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::I64($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::U8($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::U16($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::U32($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::U64($values) => $host,
+            $crate::point_cloud::attribute_data::AttributeData::Bool($values) => $host,
+        }
+    };
+}
+pub(crate) use match_attribute;
+
+/// 一个分量在主机端的数值：浮点按 f64，整数与布尔按 i128（覆盖 u64/i64 全范围）
+pub enum ComponentValues {
+    Float(Vec<f64>),
+    Integer(Vec<i128>),
+}
+
+/// 属性元素：与 f64 互转、小端字节读写
+pub trait AttributeValue: Copy + Send + Sync + 'static {
+    fn to_f64(self) -> f64;
+    /// 从 f64 转换：整数四舍五入并截断到类型范围，布尔以 0.5 为阈值
+    fn from_f64(value: f64) -> Self;
+    fn read_le(bytes: &[u8]) -> Self;
+    fn write_le(self, out: &mut Vec<u8>);
+    fn into_attribute(values: Vec<Self>) -> AttributeData;
+}
+
+/// 整数类型：转换为 f64 时精确到 2^53，从 f64 转换时四舍五入并截断
+macro_rules! impl_integer_value {
+    ($($ty:ty => $variant:ident),*) => {$(
+        impl AttributeValue for $ty {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value.round() as $ty
+            }
+
+            fn read_le(bytes: &[u8]) -> Self {
+                let size = std::mem::size_of::<$ty>();
+                <$ty>::from_le_bytes(bytes[..size].try_into().unwrap())
+            }
+
+            fn write_le(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn into_attribute(values: Vec<Self>) -> AttributeData {
+                AttributeData::$variant(values)
+            }
+        }
+    )*};
+}
+
+impl_integer_value!(
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64
+);
+
+impl AttributeValue for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn read_le(bytes: &[u8]) -> Self {
+        f64::from_le_bytes(bytes[..8].try_into().unwrap())
+    }
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn into_attribute(values: Vec<Self>) -> AttributeData {
+        AttributeData::F64(values)
+    }
+}
+
+impl AttributeValue for f32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn read_le(bytes: &[u8]) -> Self {
+        f32::from_le_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn into_attribute(values: Vec<Self>) -> AttributeData {
+        AttributeData::F32(tensor::tensor1_from_slice(&values))
+    }
+}
+
+impl AttributeValue for bool {
+    fn to_f64(self) -> f64 {
+        f64::from(u8::from(self))
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value >= 0.5
+    }
+
+    fn read_le(bytes: &[u8]) -> Self {
+        bytes[0] != 0
+    }
+
+    fn write_le(self, out: &mut Vec<u8>) {
+        out.push(u8::from(self));
+    }
+
+    fn into_attribute(values: Vec<Self>) -> AttributeData {
+        AttributeData::Bool(values)
+    }
+}
+
+impl AttributeData {
+    /// 由主机端数据创建（float32 转为张量）
+    pub fn from_vec<T: AttributeValue>(values: Vec<T>) -> Self {
+        T::into_attribute(values)
+    }
+
+    /// 按指定类型从数值创建
+    pub fn from_values<V: Into<f64> + Copy>(dtype: AttributeType, values: &[V]) -> Self {
+        fn convert<T: AttributeValue, V: Into<f64> + Copy>(values: &[V]) -> AttributeData {
+            T::into_attribute(values.iter().map(|&v| T::from_f64(v.into())).collect())
+        }
+        match dtype {
+            AttributeType::Bool => convert::<bool, V>(values),
+            AttributeType::U8 => convert::<u8, V>(values),
+            AttributeType::I8 => convert::<i8, V>(values),
+            AttributeType::U16 => convert::<u16, V>(values),
+            AttributeType::I16 => convert::<i16, V>(values),
+            AttributeType::U32 => convert::<u32, V>(values),
+            AttributeType::I32 => convert::<i32, V>(values),
+            AttributeType::U64 => convert::<u64, V>(values),
+            AttributeType::I64 => convert::<i64, V>(values),
+            AttributeType::F32 => convert::<f32, V>(values),
+            AttributeType::F64 => convert::<f64, V>(values),
+        }
+    }
+
+    /// 元素类型
+    pub fn dtype(&self) -> AttributeType {
+        match self {
+            AttributeData::F32(_) | AttributeData::Vector(_) => AttributeType::F32,
+            AttributeData::F64(_) => AttributeType::F64,
+            AttributeData::I8(_) => AttributeType::I8,
+            AttributeData::I16(_) => AttributeType::I16,
+            AttributeData::I32(_) => AttributeType::I32,
+            AttributeData::I64(_) => AttributeType::I64,
+            AttributeData::U8(_) => AttributeType::U8,
+            AttributeData::U16(_) => AttributeType::U16,
+            AttributeData::U32(_) => AttributeType::U32,
+            AttributeData::U64(_) => AttributeType::U64,
+            AttributeData::Bool(_) => AttributeType::Bool,
+        }
+    }
+
+    /// 点数
+    pub fn len(&self) -> usize {
+        match_attribute!(
+            self,
+            t => tensor::tensor1_len(t),
+            m => tensor::tensor2_rows(m),
+            values => values.len()
+        )
+    }
+
+    /// 每点的分量数（标量属性为 1）
+    pub fn width(&self) -> usize {
+        match self {
+            AttributeData::Vector(m) => tensor::tensor2_cols(m),
+            _ => 1,
+        }
+    }
+
+    /// 内存占用（字节）
+    pub fn byte_size(&self) -> usize {
+        self.len() * self.width() * self.dtype().size()
+    }
+
+    /// 转换为 f32（整数超出 2^24 时损失精度），多维属性按行展开
+    pub fn to_f32_vec(&self) -> Vec<f32> {
+        match_attribute!(
+            self,
+            t => tensor::tensor1_to_vec(t),
+            m => tensor::tensor2_to_flat(m),
+            values => values.iter().map(|&v| v.to_f64() as f32).collect()
+        )
+    }
+
+    /// 转换为 f64，多维属性按行展开
+    pub fn to_f64_vec(&self) -> Vec<f64> {
+        match_attribute!(
+            self,
+            t => tensor::tensor1_to_vec(t).into_iter().map(f64::from).collect(),
+            m => tensor::tensor2_to_flat(m).into_iter().map(f64::from).collect(),
+            values => values.iter().map(|&v| v.to_f64()).collect()
+        )
+    }
+
+    /// 按索引选取点（多维属性按行选取）
+    pub fn select(&self, indices: &IntTensor1) -> Self {
+        match_attribute!(
+            self,
+            t => AttributeData::F32(t.clone().select(0, indices.clone())),
+            m => AttributeData::Vector(m.clone().select(0, indices.clone())),
+            values => {
+                let indices = tensor::int_tensor1_to_vec(indices);
+                AttributeData::from_vec(indices.iter().map(|&idx| values[idx as usize]).collect())
+            }
+        )
+    }
+
+    /// 拆分为标量分量：多维属性得到 D 个 float32 列，标量属性返回自身
+    pub fn components(&self) -> Vec<AttributeData> {
+        match self {
+            AttributeData::Vector(m) => (0..tensor::tensor2_cols(m))
+                .map(|col| AttributeData::F32(tensor::tensor2_column(m, col)))
+                .collect(),
+            other => vec![other.clone()],
+        }
+    }
+
+    /// 整数与布尔属性的 i128 值，浮点属性返回 None
+    fn to_i128_vec(&self) -> Option<Vec<i128>> {
+        macro_rules! widen {
+            ($values:expr) => {
+                $values.iter().map(|&v| i128::from(v)).collect()
+            };
+        }
+        Some(match self {
+            AttributeData::I8(values) => widen!(values),
+            AttributeData::I16(values) => widen!(values),
+            AttributeData::I32(values) => widen!(values),
+            AttributeData::I64(values) => widen!(values),
+            AttributeData::U8(values) => widen!(values),
+            AttributeData::U16(values) => widen!(values),
+            AttributeData::U32(values) => widen!(values),
+            AttributeData::U64(values) => widen!(values),
+            AttributeData::Bool(values) => widen!(values),
+            AttributeData::F32(_) | AttributeData::Vector(_) | AttributeData::F64(_) => {
+                return None
+            }
+        })
+    }
+
+    /// 按指定类型从 i128 创建：整数截断到类型范围，布尔以大于 0 为真
+    fn from_i128(dtype: AttributeType, values: &[i128]) -> Self {
+        macro_rules! narrow {
+            ($ty:ty) => {
+                AttributeData::from_vec(
+                    values
+                        .iter()
+                        .map(|&v| v.clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty)
+                        .collect::<Vec<$ty>>(),
+                )
+            };
+        }
+        match dtype {
+            AttributeType::Bool => AttributeData::Bool(values.iter().map(|&v| v > 0).collect()),
+            AttributeType::U8 => narrow!(u8),
+            AttributeType::I8 => narrow!(i8),
+            AttributeType::U16 => narrow!(u16),
+            AttributeType::I16 => narrow!(i16),
+            AttributeType::U32 => narrow!(u32),
+            AttributeType::I32 => narrow!(i32),
+            AttributeType::U64 => narrow!(u64),
+            AttributeType::I64 => narrow!(i64),
+            AttributeType::F32 | AttributeType::F64 => {
+                let values: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+                AttributeData::from_values(dtype, &values)
+            }
+        }
+    }
+
+    /// 逐分量变换并按原类型与形状重建（用于体素聚合等改变点数的归约）
+    /// 浮点分量按 f64 传入，整数与布尔分量按 i128 传入，u64/i64 超过 2^53 也不损失精度
+    pub fn map_components(&self, f: impl Fn(ComponentValues) -> ComponentValues) -> Result<Self> {
+        match self {
+            AttributeData::Vector(m) => {
+                // 整个矩阵只回读一次，分量按步长在主机端取出
+                let source = tensor::tensor2_to_flat(m);
+                let cols = tensor::tensor2_cols(m);
+                let columns: Vec<Vec<f64>> = (0..cols)
+                    .map(|col| {
+                        let component = source
+                            .iter()
+                            .skip(col)
+                            .step_by(cols)
+                            .map(|&v| v as f64)
+                            .collect();
+                        match f(ComponentValues::Float(component)) {
+                            ComponentValues::Float(values) => values,
+                            ComponentValues::Integer(values) => {
+                                values.into_iter().map(|v| v as f64).collect()
+                            }
+                        }
+                    })
+                    .collect();
+                let rows = columns.first().map_or(0, Vec::len);
+                let flat: Vec<f32> = (0..rows)
+                    .flat_map(|row| columns.iter().map(move |column| column[row] as f32))
+                    .collect();
+                Ok(AttributeData::Vector(tensor::tensor2_from_slice(
+                    &flat, rows, cols,
+                )?))
+            }
+            other => {
+                let values = match other.to_i128_vec() {
+                    Some(values) => ComponentValues::Integer(values),
+                    None => ComponentValues::Float(other.to_f64_vec()),
+                };
+                Ok(match f(values) {
+                    ComponentValues::Float(values) => {
+                        AttributeData::from_values(other.dtype(), &values)
+                    }
+                    ComponentValues::Integer(values) => {
+                        AttributeData::from_i128(other.dtype(), &values)
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// 多维属性第 index 个分量的列名（CSV 列、LAS Extra Bytes 等只支持标量的格式）
+pub fn component_name(name: &str, index: usize) -> String {
+    format!("{}_{}", name, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_values_keep_dtype() {
+        let data = AttributeData::from_vec(vec![u64::MAX, 7]);
+        assert_eq!(data.dtype(), AttributeType::U64);
+        assert_eq!(data.byte_size(), 16);
+
+        let picked = data.select(&tensor::indices_to_tensor(&[1, 0]));
+        match picked {
+            AttributeData::U64(values) => assert_eq!(values, vec![7, u64::MAX]),
+            _ => panic!("类型应保持为 uint64"),
+        }
+
+        let flags = AttributeData::from_values(AttributeType::Bool, &[0.2f32, 0.7]);
+        assert_eq!(flags.to_f64_vec(), vec![0.0, 1.0]);
+        let labels = AttributeData::from_values(AttributeType::U8, &[2.6f64, 300.0, -1.0]);
+        assert_eq!(labels.to_f32_vec(), vec![3.0, 255.0, 0.0]);
+    }
+
+    #[test]
+    fn test_vector_attribute_gather_and_reduce() {
+        let flat = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let data = AttributeData::Vector(tensor::tensor2_from_slice(&flat, 3, 2).unwrap());
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.width(), 2);
+        assert_eq!(data.byte_size(), 24);
+
+        let picked = data.select(&tensor::indices_to_tensor(&[2, 0]));
+        assert_eq!(picked.to_f32_vec(), vec![4.0, 5.0, 0.0, 1.0]);
+        assert_eq!(picked.components()[1].to_f32_vec(), vec![5.0, 1.0]);
+
+        // 每个分量求和为一行
+        let summed = data
+            .map_components(|values| match values {
+                ComponentValues::Float(values) => ComponentValues::Float(vec![values.iter().sum()]),
+                integer => integer,
+            })
+            .unwrap();
+        assert_eq!(summed.width(), 2);
+        assert_eq!(summed.to_f32_vec(), vec![6.0, 9.0]);
+    }
+}
@@ -1,8 +1,10 @@
 /// 属性管理：intensity/RGB批量操作、自定义HashMap属性增/改
+use crate::point_cloud::attribute_data::{component_name, AttributeData};
 use crate::point_cloud::core::HighPerformancePointCloud;
 use crate::traits::PointCloudCore;
-use crate::utils::error::Result;
+use crate::utils::error::{PointCloudError, Result};
 use crate::utils::tensor;
+use std::collections::HashMap;
 
 impl HighPerformancePointCloud {
     /// 清除所有自定义属性
@@ -11,10 +13,7 @@ pub fn clear_attributes(&mut self) {
     }
 
     /// 批量替换自定义属性
-    pub fn set_all_attributes(
-        &mut self,
-        attributes: std::collections::HashMap<String, Vec<f32>>,
-    ) -> Result<()> {
+    pub fn set_all_attributes(&mut self, attributes: HashMap<String, Vec<f32>>) -> Result<()> {
         // 验证所有属性的长度
         for (name, data) in &attributes {
             if data.len() != self.point_count() {
@@ -31,12 +30,46 @@ pub fn set_all_attributes(
         // 替换所有属性
         let converted = attributes
             .into_iter()
-            .map(|(name, data)| (name, tensor::intensity_to_tensor(data)))
+            .map(|(name, data)| (name, AttributeData::F32(tensor::intensity_to_tensor(data))))
             .collect();
         *self.attributes_mut() = converted;
         Ok(())
     }
 
+    /// 获取类型化的自定义属性
+    pub fn attribute(&self, name: &str) -> Option<&AttributeData> {
+        self.attributes_ref().get(name)
+    }
+
+    /// 设置类型化的自定义属性（长度必须与点数一致）
+    pub fn set_attribute_data(&mut self, name: String, data: AttributeData) -> Result<()> {
+        if data.len() != self.point_count() {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "属性'{}'长度{}与点数{}不匹配",
+                name,
+                data.len(),
+                self.point_count()
+            )));
+        }
+        self.attributes_mut().insert(name, data);
+        Ok(())
+    }
+
+    /// 标量形式的自定义属性：多维属性拆分为 name_0..name_{D-1} 的 float32 分量
+    pub fn scalar_attributes(&self) -> HashMap<String, AttributeData> {
+        let mut scalars = HashMap::new();
+        for (name, data) in self.attributes_ref() {
+            if let AttributeData::Vector(_) = data {
+                for (index, component) in data.components().into_iter().enumerate() {
+                    scalars.insert(component_name(name, index), component);
+                }
+            } else {
+                scalars.insert(name.clone(), data.clone());
+            }
+        }
+        scalars
+    }
+
     /// 移除intensity
     pub fn remove_intensity(&mut self) {
         *self.intensity_mut() = None;
@@ -50,6 +83,30 @@ pub fn remove_rgb(&mut self) {
         *b = None;
     }
 
+    /// 获取GPS时间
+    pub fn gps_time(&self) -> Option<&[f64]> {
+        self.gps_time_ref()
+    }
+
+    /// 设置GPS时间（f64，长度必须与点数一致）
+    pub fn set_gps_time(&mut self, gps_time: Vec<f64>) -> Result<()> {
+        if gps_time.len() != self.point_count() {
+            return Err(format!(
+                "GPS时间长度{}与点数{}不匹配",
+                gps_time.len(),
+                self.point_count()
+            )
+            .into());
+        }
+        *self.gps_time_mut() = Some(gps_time);
+        Ok(())
+    }
+
+    /// 移除GPS时间
+    pub fn remove_gps_time(&mut self) {
+        *self.gps_time_mut() = None;
+    }
+
     /// 检查是否包含所有必要的属性
     pub fn has_attributes(&self, names: &[&str]) -> bool {
         let attrs = self.attributes_ref();
@@ -60,7 +117,7 @@ pub fn has_attributes(&self, names: &[&str]) -> bool {
     pub fn attribute_info(&self) -> Vec<(String, usize)> {
         self.attributes_ref()
             .iter()
-            .map(|(name, data)| (name.clone(), tensor::tensor1_len(data)))
+            .map(|(name, data)| (name.clone(), data.len()))
             .collect()
     }
 }
@@ -0,0 +1,538 @@
+/// 相机模型：针孔内参/外参、深度图与RGB-D反投影、点云投影与着色
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::{CoordinateTransform, PointCloudCore};
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor::{self, Backend, Tensor1, Tensor2};
+use burn::tensor::{Bool, Int, Tensor, TensorData};
+
+/// z-buffer 遮挡判断的相对深度容差（同一像素内比最近点远5%以内视为可见）
+const ZBUFFER_TOLERANCE: f32 = 0.05;
+
+/// 针孔相机内参
+#[derive(Clone, Copy, Debug)]
+pub struct PinholeIntrinsics {
+    pub fx: f32,
+    pub fy: f32,
+    pub cx: f32,
+    pub cy: f32,
+}
+
+impl PinholeIntrinsics {
+    /// 从3x3内参矩阵K解析
+    pub fn from_matrix(k: &[Vec<f32>]) -> Result<Self> {
+        if k.len() != 3 || !k.iter().all(|row| row.len() == 3) {
+            return Err(PointCloudError::MatrixError(
+                "内参矩阵必须为3x3".to_string(),
+            ));
+        }
+        let intrinsics = Self {
+            fx: k[0][0],
+            fy: k[1][1],
+            cx: k[0][2],
+            cy: k[1][2],
+        };
+        if intrinsics.fx == 0.0 || intrinsics.fy == 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "内参fx/fy不能为0".to_string(),
+            ));
+        }
+        Ok(intrinsics)
+    }
+}
+
+/// 相机外参（世界坐标系 -> 相机坐标系的刚体变换），按 f64 保存以容纳全局坐标的平移
+#[derive(Clone, Debug)]
+pub struct Extrinsic {
+    pub rotation: [[f64; 3]; 3],
+    pub translation: [f64; 3],
+}
+
+impl Extrinsic {
+    /// 从4x4齐次矩阵解析（仅取旋转与平移部分）
+    pub fn from_matrix(m: &[Vec<f64>]) -> Result<Self> {
+        if m.len() != 4 || !m.iter().all(|row| row.len() == 4) {
+            return Err(PointCloudError::MatrixError(
+                "外参矩阵必须为4x4".to_string(),
+            ));
+        }
+        Ok(Self {
+            rotation: [
+                [m[0][0], m[0][1], m[0][2]],
+                [m[1][0], m[1][1], m[1][2]],
+                [m[2][0], m[2][1], m[2][2]],
+            ],
+            translation: [m[0][3], m[1][3], m[2][3]],
+        })
+    }
+
+    /// 逆变换（相机坐标系 -> 世界坐标系）：R^T, -R^T·t
+    pub fn inverse(&self) -> Self {
+        let r = &self.rotation;
+        let t = &self.translation;
+        let rotation = [
+            [r[0][0], r[1][0], r[2][0]],
+            [r[0][1], r[1][1], r[2][1]],
+            [r[0][2], r[1][2], r[2][2]],
+        ];
+        let translation = rotation.map(|row| -(row[0] * t[0] + row[1] * t[1] + row[2] * t[2]));
+        Self {
+            rotation,
+            translation,
+        }
+    }
+
+    /// 以 rigid_transform 所需的 Vec 形式返回 (rotation, translation)
+    pub fn to_vecs(&self) -> (Vec<Vec<f64>>, Vec<f64>) {
+        (
+            self.rotation.iter().map(|row| row.to_vec()).collect(),
+            self.translation.to_vec(),
+        )
+    }
+}
+
+/// 镜头畸变系数（OpenCV Brown-Conrady 模型）
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Distortion {
+    pub k1: f32,
+    pub k2: f32,
+    pub p1: f32,
+    pub p2: f32,
+    pub k3: f32,
+}
+
+impl Distortion {
+    /// 按 OpenCV 顺序解析 [k1, k2, p1, p2] 或 [k1, k2, p1, p2, k3]
+    pub fn from_slice(coeffs: &[f32]) -> Result<Self> {
+        match coeffs {
+            [k1, k2, p1, p2] => Ok(Self {
+                k1: *k1,
+                k2: *k2,
+                p1: *p1,
+                p2: *p2,
+                k3: 0.0,
+            }),
+            [k1, k2, p1, p2, k3] => Ok(Self {
+                k1: *k1,
+                k2: *k2,
+                p1: *p1,
+                p2: *p2,
+                k3: *k3,
+            }),
+            _ => Err(PointCloudError::InvalidParameter(format!(
+                "畸变系数必须为4或5个，实际为{}",
+                coeffs.len()
+            ))),
+        }
+    }
+}
+
+/// 点云到图像的投影结果
+pub struct ImageProjection {
+    /// 像素坐标（形状[N,2]，列为u,v；相机后方的点为-1）
+    pub pixels: Tensor2,
+    /// 可见性（在相机前方、图像范围内且未被遮挡）
+    pub visible: Vec<bool>,
+}
+
+impl HighPerformancePointCloud {
+    /// 从深度图反投影创建点云
+    /// depth: 深度图（形状[H,W]，原始单位，除以 depth_scale 得到米）
+    /// depth_trunc: 超过该深度（米）的像素被丢弃，0 或负深度同样丢弃
+    /// extrinsic: 可选外参（世界->相机），提供时输出世界坐标
+    pub fn from_depth_image(
+        depth: Tensor2,
+        intrinsics: PinholeIntrinsics,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<&Extrinsic>,
+    ) -> Result<Self> {
+        let (result, _) = back_project(depth, intrinsics, depth_scale, depth_trunc, extrinsic)?;
+        Ok(result)
+    }
+
+    /// 从RGB-D图像反投影创建带颜色的点云
+    /// color: 颜色图（形状[H*W,3]，取值0-255，行优先与深度图一一对应）
+    pub fn from_rgbd(
+        color: Tensor2,
+        depth: Tensor2,
+        intrinsics: PinholeIntrinsics,
+        depth_scale: f32,
+        depth_trunc: f32,
+        extrinsic: Option<&Extrinsic>,
+    ) -> Result<Self> {
+        let [height, width] = depth.dims();
+        let [color_rows, color_cols] = color.dims();
+        if color_rows != height * width || color_cols != 3 {
+            return Err(PointCloudError::TensorShapeError(format!(
+                "颜色图形状[{},{}]与深度图[{},{}]不匹配",
+                color_rows, color_cols, height, width
+            )));
+        }
+
+        let (mut result, kept) =
+            back_project(depth, intrinsics, depth_scale, depth_trunc, extrinsic)?;
+
+        let kept_color = color.select(0, kept);
+        let (r_mut, g_mut, b_mut) = result.rgb_channels_mut();
+        *r_mut = Some(tensor::xyz_column(&kept_color, 0));
+        *g_mut = Some(tensor::xyz_column(&kept_color, 1));
+        *b_mut = Some(tensor::xyz_column(&kept_color, 2));
+        Ok(result)
+    }
+
+    /// 将点云投影到相机图像
+    /// extrinsic: 外参（世界->相机），None 表示点云已在相机坐标系
+    /// 同一像素内按 z-buffer 处理遮挡
+    pub fn project_to_image(
+        &self,
+        intrinsics: PinholeIntrinsics,
+        extrinsic: Option<&Extrinsic>,
+        width: usize,
+        height: usize,
+        distortion: Option<&Distortion>,
+    ) -> Result<ImageProjection> {
+        if width == 0 || height == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "width/height必须大于0".to_string(),
+            ));
+        }
+
+        let (u, v, z) = self.project_pixels(intrinsics, extrinsic, distortion)?;
+
+        // 第一步：相机前方且落在图像内的候选点
+        let in_front = z.clone().greater_elem(1e-6);
+        let candidate = in_front
+            .clone()
+            .bool_and(u.clone().greater_equal_elem(0.0))
+            .bool_and(u.clone().lower_elem(width as f32))
+            .bool_and(v.clone().greater_equal_elem(0.0))
+            .bool_and(v.clone().lower_elem(height as f32));
+        let candidates = tensor::mask_to_indices(candidate);
+
+        // 第二步：回读后建立 z-buffer
+        let u_vec = tensor::tensor1_to_vec(&u);
+        let v_vec = tensor::tensor1_to_vec(&v);
+        let z_vec = tensor::tensor1_to_vec(&z);
+        let pixel_of = |idx: usize| v_vec[idx] as usize * width + u_vec[idx] as usize;
+
+        let mut zbuffer = vec![f32::INFINITY; width * height];
+        for &idx in &candidates {
+            let slot = &mut zbuffer[pixel_of(idx)];
+            if z_vec[idx] < *slot {
+                *slot = z_vec[idx];
+            }
+        }
+
+        let mut visible = vec![false; self.point_count()];
+        for &idx in &candidates {
+            visible[idx] = z_vec[idx] <= zbuffer[pixel_of(idx)] * (1.0 + ZBUFFER_TOLERANCE);
+        }
+
+        let behind = in_front.bool_not();
+        let pixels = Tensor::<Backend, 1>::stack::<2>(
+            vec![u.mask_fill(behind.clone(), -1.0), v.mask_fill(behind, -1.0)],
+            1,
+        );
+
+        Ok(ImageProjection { pixels, visible })
+    }
+
+    /// 从相机图像双线性采样颜色写入RGB通道
+    /// image: 颜色图（形状[H*W,3]，取值0-255），不可见点保留原颜色（无RGB时为0）
+    /// 返回每个点的可见性
+    pub fn colorize_from_image(
+        &mut self,
+        image: Tensor2,
+        width: usize,
+        height: usize,
+        intrinsics: PinholeIntrinsics,
+        extrinsic: Option<&Extrinsic>,
+        distortion: Option<&Distortion>,
+    ) -> Result<Vec<bool>> {
+        let [rows, cols] = image.dims();
+        if rows != width * height || cols != 3 {
+            return Err(PointCloudError::TensorShapeError(format!(
+                "图像形状[{},{}]与尺寸{}x{}不匹配",
+                rows, cols, height, width
+            )));
+        }
+
+        let projection = self.project_to_image(intrinsics, extrinsic, width, height, distortion)?;
+        let n = self.point_count();
+        let device = tensor::default_device();
+
+        // 第一步：双线性插值的四邻域索引与权重（坐标截断到图像内）
+        let u = tensor::xyz_column(&projection.pixels, 0).clamp(0.0, (width - 1) as f32);
+        let v = tensor::xyz_column(&projection.pixels, 1).clamp(0.0, (height - 1) as f32);
+        let u0 = u.clone().floor();
+        let v0 = v.clone().floor();
+        let du = u.sub(u0.clone()).reshape([n, 1]);
+        let dv = v.sub(v0.clone()).reshape([n, 1]);
+        let u1 = u0.clone().add_scalar(1.0).clamp_max((width - 1) as f32);
+        let v1 = v0.clone().add_scalar(1.0).clamp_max((height - 1) as f32);
+
+        let sample = |uu: &Tensor1, vv: &Tensor1| {
+            let idx = vv.clone().mul_scalar(width as f32).add(uu.clone()).int();
+            image.clone().select(0, idx)
+        };
+        let top = sample(&u0, &v0)
+            .mul(du.clone().neg().add_scalar(1.0))
+            .add(sample(&u1, &v0).mul(du.clone()));
+        let bottom = sample(&u0, &v1)
+            .mul(du.clone().neg().add_scalar(1.0))
+            .add(sample(&u1, &v1).mul(du));
+        let colors = top
+            .mul(dv.clone().neg().add_scalar(1.0))
+            .add(bottom.mul(dv))
+            .clamp(0.0, 255.0);
+
+        // 第二步：仅对可见点覆盖颜色
+        let mask = Tensor::<Backend, 1, Bool>::from_data(
+            TensorData::from(projection.visible.as_slice()),
+            &device,
+        );
+        let (r_mut, g_mut, b_mut) = self.rgb_channels_mut();
+        for (col, channel) in [r_mut, g_mut, b_mut].into_iter().enumerate() {
+            let old = channel
+                .take()
+                .unwrap_or_else(|| Tensor::<Backend, 1>::zeros([n], &device));
+            *channel = Some(old.mask_where(mask.clone(), tensor::xyz_column(&colors, col)));
+        }
+
+        Ok(projection.visible)
+    }
+
+    /// 计算每个点的像素坐标 (u, v) 与相机坐标系深度 z
+    fn project_pixels(
+        &self,
+        intrinsics: PinholeIntrinsics,
+        extrinsic: Option<&Extrinsic>,
+        distortion: Option<&Distortion>,
+    ) -> Result<(Tensor1, Tensor1, Tensor1)> {
+        // 世界坐标 = 局部坐标 + 原点，p_cam = R·p_local + (R·o + t)，平移部分按 f64 合并
+        let origin = self.origin();
+        let (rotation, translation) = match extrinsic {
+            Some(extrinsic) => (extrinsic.rotation, extrinsic.translation),
+            None => (
+                [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                [0.0; 3],
+            ),
+        };
+        let shift: Vec<f32> = (0..3)
+            .map(|i| {
+                let rotated: f64 = (0..3).map(|j| rotation[i][j] * origin[j]).sum();
+                (rotated + translation[i]) as f32
+            })
+            .collect();
+        let rotation: Vec<Vec<f32>> = rotation
+            .iter()
+            .map(|row| row.iter().map(|&v| v as f32).collect())
+            .collect();
+        let rotation_t = tensor::matrix_to_tensor(rotation)?.transpose();
+        let shift = tensor::tensor1_from_slice(&shift).reshape([1, 3]);
+        let camera_xyz = self.xyz_ref().clone().matmul(rotation_t).add(shift);
+
+        let z = tensor::xyz_column(&camera_xyz, 2);
+        let safe_z = z.clone().clamp_min(1e-6);
+        let mut xn = tensor::xyz_column(&camera_xyz, 0).div(safe_z.clone());
+        let mut yn = tensor::xyz_column(&camera_xyz, 1).div(safe_z);
+
+        if let Some(d) = distortion {
+            let r2 = xn.clone().powf_scalar(2.0).add(yn.clone().powf_scalar(2.0));
+            let radial = r2
+                .clone()
+                .mul_scalar(d.k3)
+                .add_scalar(d.k2)
+                .mul(r2.clone())
+                .add_scalar(d.k1)
+                .mul(r2.clone())
+                .add_scalar(1.0);
+            let xy = xn.clone().mul(yn.clone());
+            let xd = xn
+                .clone()
+                .mul(radial.clone())
+                .add(xy.clone().mul_scalar(2.0 * d.p1))
+                .add(
+                    r2.clone()
+                        .add(xn.clone().powf_scalar(2.0).mul_scalar(2.0))
+                        .mul_scalar(d.p2),
+                );
+            let yd = yn
+                .clone()
+                .mul(radial)
+                .add(
+                    r2.add(yn.clone().powf_scalar(2.0).mul_scalar(2.0))
+                        .mul_scalar(d.p1),
+                )
+                .add(xy.mul_scalar(2.0 * d.p2));
+            xn = xd;
+            yn = yd;
+        }
+
+        let u = xn.mul_scalar(intrinsics.fx).add_scalar(intrinsics.cx);
+        let v = yn.mul_scalar(intrinsics.fy).add_scalar(intrinsics.cy);
+        Ok((u, v, z))
+    }
+}
+
+/// 深度图反投影，返回点云与保留像素的线性索引
+fn back_project(
+    depth: Tensor2,
+    intrinsics: PinholeIntrinsics,
+    depth_scale: f32,
+    depth_trunc: f32,
+    extrinsic: Option<&Extrinsic>,
+) -> Result<(HighPerformancePointCloud, Tensor<Backend, 1, Int>)> {
+    if depth_scale <= 0.0 {
+        return Err(PointCloudError::InvalidParameter(
+            "depth_scale必须大于0".to_string(),
+        ));
+    }
+
+    let [height, width] = depth.dims();
+    let pixels = height * width;
+    let device = tensor::default_device();
+
+    // 第一步：有效深度像素筛选
+    let z = depth.reshape([pixels]).div_scalar(depth_scale);
+    let valid = z
+        .clone()
+        .greater_elem(0.0)
+        .bool_and(z.clone().lower_equal_elem(depth_trunc));
+    let kept = tensor::indices_to_tensor(&tensor::mask_to_indices(valid));
+    let z = z.select(0, kept.clone());
+
+    // 第二步：像素坐标 (u, v) 与针孔反投影
+    let pixel =
+        Tensor::<Backend, 1, Int>::arange(0..pixels as i64, &device).select(0, kept.clone());
+    let v = pixel.clone().div_scalar(width as i64).float();
+    let u = pixel.remainder_scalar(width as i64).float();
+    let x = u
+        .sub_scalar(intrinsics.cx)
+        .mul(z.clone())
+        .div_scalar(intrinsics.fx);
+    let y = v
+        .sub_scalar(intrinsics.cy)
+        .mul(z.clone())
+        .div_scalar(intrinsics.fy);
+    let xyz = Tensor::<Backend, 1>::stack::<2>(vec![x, y, z], 1);
+
+    let mut result = HighPerformancePointCloud::from_tensor_xyz(xyz)?;
+
+    // 第三步：相机坐标 -> 世界坐标
+    if let Some(extrinsic) = extrinsic {
+        let (rotation, translation) = extrinsic.inverse().to_vecs();
+        result = result.rigid_transform(rotation, translation)?;
+    }
+
+    Ok((result, kept))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_back_projection() {
+        // 2x2 深度图，其中一个像素无效
+        let depth = tensor::tensor2_from_slice(&[1000.0, 0.0, 2000.0, 1000.0], 2, 2).unwrap();
+        let intrinsics = PinholeIntrinsics {
+            fx: 1.0,
+            fy: 1.0,
+            cx: 0.0,
+            cy: 0.0,
+        };
+
+        let pc = HighPerformancePointCloud::from_depth_image(depth, intrinsics, 1000.0, 10.0, None)
+            .unwrap();
+        let xyz = pc.get_xyz();
+
+        assert_eq!(pc.point_count(), 3);
+        // 像素(u=0,v=1)深度2m -> (0, 2, 2)
+        assert!((xyz[1][1] - 2.0).abs() < 1e-5);
+        assert!((xyz[1][2] - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_project_zbuffer_occlusion() {
+        // 两点投影到同一像素，远处的点被遮挡
+        let xyz = vec![
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 3.0],
+            vec![0.0, 0.0, -1.0],
+        ];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let intrinsics = PinholeIntrinsics {
+            fx: 10.0,
+            fy: 10.0,
+            cx: 2.0,
+            cy: 2.0,
+        };
+
+        let projection = pc.project_to_image(intrinsics, None, 4, 4, None).unwrap();
+
+        assert_eq!(projection.visible, vec![true, false, false]);
+    }
+
+    #[test]
+    fn test_project_global_coordinates() {
+        // UTM 量级的点云与相机位姿，外参平移按 f64 与原点合并
+        let points = [
+            [500_000.0, 4_000_000.0, 10.0],
+            [500_001.0, 4_000_000.5, 10.0],
+        ];
+        let pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        let intrinsics = PinholeIntrinsics {
+            fx: 100.0,
+            fy: 100.0,
+            cx: 50.0,
+            cy: 50.0,
+        };
+        let extrinsic = Extrinsic {
+            rotation: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [-500_000.0, -4_000_000.0, 0.0],
+        };
+
+        let projection = pc
+            .project_to_image(intrinsics, Some(&extrinsic), 100, 100, None)
+            .unwrap();
+        let pixels = tensor::tensor2_to_flat(&projection.pixels);
+        assert_eq!(projection.visible, vec![true, true]);
+        assert!((pixels[0] - 50.0).abs() < 1e-3 && (pixels[1] - 50.0).abs() < 1e-3);
+        assert!((pixels[2] - 60.0).abs() < 1e-3 && (pixels[3] - 55.0).abs() < 1e-3);
+
+        // 反投影回世界坐标保持毫米精度
+        let depth = tensor::tensor2_from_slice(&[10.0], 1, 1).unwrap();
+        let center = PinholeIntrinsics {
+            cx: 0.0,
+            cy: 0.0,
+            ..intrinsics
+        };
+        let world = HighPerformancePointCloud::from_depth_image(
+            depth,
+            center,
+            1.0,
+            100.0,
+            Some(&extrinsic),
+        )
+        .unwrap();
+        let global = world.global_points();
+        assert!((global[0][0] - 500_000.0).abs() < 1e-3);
+        assert!((global[0][1] - 4_000_000.0).abs() < 1e-3);
+        assert!((global[0][2] - 10.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_extrinsic_inverse() {
+        let extrinsic = Extrinsic {
+            rotation: [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [1.0, 2.0, 3.0],
+        };
+        let inv = extrinsic.inverse();
+
+        // R^T·(-t)
+        assert!((inv.translation[0] + 2.0).abs() < 1e-6);
+        assert!((inv.translation[1] - 1.0).abs() < 1e-6);
+        assert!((inv.translation[2] + 3.0).abs() < 1e-6);
+    }
+}
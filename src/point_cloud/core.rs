@@ -1,7 +1,10 @@
+use crate::io::las_meta::LasMetadata;
+use crate::point_cloud::attribute_data::AttributeData;
+use crate::point_cloud::crs::Crs;
 use crate::traits::{PointCloudCore, PointCloudProperties};
 use crate::utils::error::Result;
 use crate::utils::tensor;
-use crate::utils::tensor::{Tensor1, Tensor2};
+use crate::utils::tensor::{IntTensor1, Tensor1, Tensor2};
 /// 点云核心Struct定义、基础生命周期方法
 use std::collections::HashMap;
 
@@ -9,9 +12,12 @@
 /// 所有字段私有，仅通过Trait方法/公有接口暴露批量操作
 #[derive(Clone)]
 pub struct HighPerformancePointCloud {
-    // 必选：XYZ三维坐标（形状[M,3]，M为点数）
+    // 必选：XYZ三维坐标（形状[M,3]，M为点数），相对 origin 的局部坐标
     xyz: Tensor2,
 
+    // 局部坐标原点（全局坐标 = xyz + origin，f64 保持地理坐标精度）
+    origin: [f64; 3],
+
     // 可选：强度值（形状[M,]）
     intensity: Option<Tensor1>,
 
@@ -20,8 +26,17 @@ pub struct HighPerformancePointCloud {
     rgb_g: Option<Tensor1>,
     rgb_b: Option<Tensor1>,
 
-    // 自定义属性字典
-    attributes: HashMap<String, Tensor1>,
+    // 自定义属性字典（float32 为张量，其他类型保存在主机内存）
+    attributes: HashMap<String, AttributeData>,
+
+    // 可选：GPS时间（形状[M,]，需要f64精度，保存在主机内存）
+    gps_time: Option<Vec<f64>>,
+
+    // 可选：LAS元数据（Extra Bytes 描述符等，读取LAS时填充）
+    las_metadata: Option<LasMetadata>,
+
+    // 可选：坐标参考系
+    crs: Option<Crs>,
 }
 
 impl HighPerformancePointCloud {
@@ -29,11 +44,15 @@ impl HighPerformancePointCloud {
     pub fn new() -> Self {
         Self {
             xyz: tensor::empty_xyz(),
+            origin: [0.0; 3],
             intensity: None,
             rgb_r: None,
             rgb_g: None,
             rgb_b: None,
             attributes: HashMap::new(),
+            gps_time: None,
+            las_metadata: None,
+            crs: None,
         }
     }
 
@@ -47,11 +66,15 @@ pub fn from_tensor_xyz(xyz: Tensor2) -> Result<Self> {
         }
         Ok(Self {
             xyz,
+            origin: [0.0; 3],
             intensity: None,
             rgb_r: None,
             rgb_g: None,
             rgb_b: None,
             attributes: HashMap::new(),
+            gps_time: None,
+            las_metadata: None,
+            crs: None,
         })
     }
 
@@ -66,14 +89,47 @@ pub fn from_xyz(xyz: Vec<Vec<f32>>) -> Result<Self> {
 
         Ok(Self {
             xyz,
+            origin: [0.0; 3],
             intensity: None,
             rgb_r: None,
             rgb_g: None,
             rgb_b: None,
             attributes: HashMap::new(),
+            gps_time: None,
+            las_metadata: None,
+            crs: None,
         })
     }
 
+    /// 按索引选取点子集（XYZ/intensity/RGB/自定义属性/GPS时间同步 gather）
+    pub fn select_points(&self, indices: IntTensor1) -> Self {
+        let gather = |t: &Tensor1| t.clone().select(0, indices.clone());
+        let gps_time = self.gps_time.as_ref().map(|times| {
+            indices
+                .clone()
+                .into_data()
+                .iter::<i64>()
+                .map(|idx| times[idx as usize])
+                .collect()
+        });
+        Self {
+            xyz: self.xyz.clone().select(0, indices.clone()),
+            origin: self.origin,
+            intensity: self.intensity.as_ref().map(gather),
+            rgb_r: self.rgb_r.as_ref().map(gather),
+            rgb_g: self.rgb_g.as_ref().map(gather),
+            rgb_b: self.rgb_b.as_ref().map(gather),
+            attributes: self
+                .attributes
+                .iter()
+                .map(|(name, data)| (name.clone(), data.select(&indices)))
+                .collect(),
+            gps_time,
+            las_metadata: self.las_metadata.clone(),
+            crs: self.crs.clone(),
+        }
+    }
+
     /// 获取内部XYZ的可变引用（仅内部使用）
     pub(crate) fn xyz_mut(&mut self) -> &mut Tensor2 {
         &mut self.xyz
@@ -84,6 +140,16 @@ pub(crate) fn xyz_ref(&self) -> &Tensor2 {
         &self.xyz
     }
 
+    /// 获取内部局部坐标原点的可变引用（仅内部使用）
+    pub(crate) fn origin_mut(&mut self) -> &mut [f64; 3] {
+        &mut self.origin
+    }
+
+    /// 局部坐标原点
+    pub fn origin(&self) -> [f64; 3] {
+        self.origin
+    }
+
     /// 获取内部intensity的可变引用（仅内部使用）
     pub(crate) fn intensity_mut(&mut self) -> &mut Option<Tensor1> {
         &mut self.intensity
@@ -117,15 +183,45 @@ pub(crate) fn rgb_channels_ref(
     }
 
     /// 获取内部属性字典的可变引用（仅内部使用）
-    pub(crate) fn attributes_mut(&mut self) -> &mut HashMap<String, Tensor1> {
+    pub(crate) fn attributes_mut(&mut self) -> &mut HashMap<String, AttributeData> {
         &mut self.attributes
     }
 
     /// 获取内部属性字典的不可变引用（仅内部使用）
-    pub(crate) fn attributes_ref(&self) -> &HashMap<String, Tensor1> {
+    pub(crate) fn attributes_ref(&self) -> &HashMap<String, AttributeData> {
         &self.attributes
     }
 
+    /// 获取内部GPS时间的可变引用（仅内部使用）
+    pub(crate) fn gps_time_mut(&mut self) -> &mut Option<Vec<f64>> {
+        &mut self.gps_time
+    }
+
+    /// 获取内部GPS时间的不可变引用（仅内部使用）
+    pub(crate) fn gps_time_ref(&self) -> Option<&[f64]> {
+        self.gps_time.as_deref()
+    }
+
+    /// 获取内部LAS元数据的可变引用（仅内部使用）
+    pub(crate) fn las_metadata_mut(&mut self) -> &mut Option<LasMetadata> {
+        &mut self.las_metadata
+    }
+
+    /// 获取内部LAS元数据的不可变引用（仅内部使用）
+    pub(crate) fn las_metadata_ref(&self) -> Option<&LasMetadata> {
+        self.las_metadata.as_ref()
+    }
+
+    /// 获取内部坐标参考系的可变引用（仅内部使用）
+    pub(crate) fn crs_mut(&mut self) -> &mut Option<Crs> {
+        &mut self.crs
+    }
+
+    /// 获取内部坐标参考系的不可变引用（仅内部使用）
+    pub(crate) fn crs_ref(&self) -> Option<&Crs> {
+        self.crs.as_ref()
+    }
+
     /// 内存占用估算（字节）
     pub fn memory_usage(&self) -> usize {
         let mut total = self.point_count() * 3 * std::mem::size_of::<f32>();
@@ -146,7 +242,11 @@ pub fn memory_usage(&self) -> usize {
         }
 
         for data in self.attributes.values() {
-            total += tensor::tensor1_len(data) * std::mem::size_of::<f32>();
+            total += data.byte_size();
+        }
+
+        if let Some(gps_time) = &self.gps_time {
+            total += gps_time.len() * std::mem::size_of::<f64>();
         }
 
         total
@@ -160,8 +260,8 @@ fn default() -> Self {
 }
 
 impl PointCloudCore for HighPerformancePointCloud {
-    fn get_xyz(&self) -> Vec<Vec<f32>> {
-        tensor::tensor2_to_vec(&self.xyz)
+    fn get_xyz(&self) -> Vec<[f64; 3]> {
+        self.global_points()
     }
 
     fn point_count(&self) -> usize {
@@ -196,7 +296,7 @@ fn attribute_names(&self) -> Vec<String> {
     }
 
     fn get_attribute(&self, name: &str) -> Option<Vec<f32>> {
-        self.attributes.get(name).map(tensor::tensor1_to_vec)
+        self.attributes.get(name).map(AttributeData::to_f32_vec)
     }
 }
 
@@ -225,14 +325,14 @@ fn add_attribute(&mut self, name: String, data: Vec<f32>) -> Result<()> {
         }
         tensor::validate_attribute_shape(&data, self.point_count())?;
         self.attributes
-            .insert(name, tensor::intensity_to_tensor(data));
+            .insert(name, AttributeData::F32(tensor::intensity_to_tensor(data)));
         Ok(())
     }
 
     fn set_attribute(&mut self, name: String, data: Vec<f32>) -> Result<()> {
         tensor::validate_attribute_shape(&data, self.point_count())?;
         self.attributes
-            .insert(name, tensor::intensity_to_tensor(data));
+            .insert(name, AttributeData::F32(tensor::intensity_to_tensor(data)));
         Ok(())
     }
 
@@ -0,0 +1,435 @@
+/// 坐标参考系：EPSG/WKT/ENU 描述与纯 Rust 重投影（WGS84 地理坐标、UTM、ECEF、ENU）
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::origin::auto_origin;
+use crate::utils::error::{PointCloudError, Result};
+use std::fmt;
+
+/// WGS84 椭球长半轴与扁率
+const WGS84_A: f64 = 6_378_137.0;
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+/// 第一偏心率平方
+const WGS84_E2: f64 = WGS84_F * (2.0 - WGS84_F);
+/// UTM 比例因子与东偏、南半球北偏
+const UTM_K0: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500_000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10_000_000.0;
+
+/// 坐标参考系
+#[derive(Clone, Debug, PartialEq)]
+pub enum Crs {
+    /// EPSG 代码
+    Epsg(u32),
+    /// OGC WKT 文本
+    Wkt(String),
+    /// 局部东北天（ENU）切平面，原点为 WGS84 经度、纬度（度）与椭球高
+    Enu { lon: f64, lat: f64, height: f64 },
+}
+
+impl Crs {
+    /// 解析坐标系描述："EPSG:32633"、"4326"、"ENU:经度,纬度[,椭球高]" 或 WKT 文本
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let upper = text.to_ascii_uppercase();
+        let code = upper.strip_prefix("EPSG:").unwrap_or(&upper);
+        if let Ok(code) = code.trim().parse::<u32>() {
+            return Ok(Crs::Epsg(code));
+        }
+        if let Some(params) = upper.strip_prefix("ENU:") {
+            let values = params
+                .split(',')
+                .map(|v| v.trim().parse::<f64>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map_err(|_| {
+                    PointCloudError::InvalidParameter(format!("无效的ENU原点: {}", text))
+                })?;
+            return match values.as_slice() {
+                [lon, lat] => Ok(Crs::Enu {
+                    lon: *lon,
+                    lat: *lat,
+                    height: 0.0,
+                }),
+                [lon, lat, height] => Ok(Crs::Enu {
+                    lon: *lon,
+                    lat: *lat,
+                    height: *height,
+                }),
+                _ => Err(PointCloudError::InvalidParameter(format!(
+                    "ENU原点需要经度、纬度与可选的椭球高: {}",
+                    text
+                ))),
+            };
+        }
+        let is_wkt = text.find('[').is_some_and(|pos| {
+            pos > 0
+                && text[..pos]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+        if is_wkt {
+            return Ok(Crs::Wkt(text.to_string()));
+        }
+        Err(PointCloudError::InvalidParameter(format!(
+            "无法解析的坐标系: {}",
+            text
+        )))
+    }
+
+    /// EPSG 代码，WKT 取根节点的 EPSG 标识
+    pub fn epsg(&self) -> Option<u32> {
+        match self {
+            Crs::Epsg(code) => Some(*code),
+            Crs::Wkt(wkt) => wkt_epsg(wkt),
+            Crs::Enu { .. } => None,
+        }
+    }
+
+    /// 重投影所需的坐标系类型
+    fn kind(&self) -> Result<CrsKind> {
+        if let Crs::Enu { lon, lat, height } = self {
+            return Ok(CrsKind::Enu {
+                origin: [*lon, *lat, *height],
+            });
+        }
+        self.epsg().and_then(CrsKind::from_epsg).ok_or_else(|| {
+            PointCloudError::InvalidParameter(format!(
+                "不支持重投影的坐标系: {}（支持 EPSG:4326/4979/4978、WGS84 UTM 与 ENU）",
+                self
+            ))
+        })
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crs::Epsg(code) => write!(f, "EPSG:{}", code),
+            Crs::Wkt(wkt) => write!(f, "{}", wkt),
+            Crs::Enu { lon, lat, height } => write!(f, "ENU:{},{},{}", lon, lat, height),
+        }
+    }
+}
+
+/// 从 WKT 中提取根节点的 EPSG 标识（WKT1 AUTHORITY / WKT2 ID，根节点标识位于最后）
+fn wkt_epsg(wkt: &str) -> Option<u32> {
+    let pos = wkt.rfind("\"EPSG\"")?;
+    let rest = wkt[pos + 6..].trim_start_matches([',', ' ', '"']);
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// 支持重投影的坐标系类型
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CrsKind {
+    /// WGS84 地理坐标：x 为经度、y 为纬度（度），z 为椭球高
+    Geographic,
+    /// WGS84 地心地固坐标
+    Ecef,
+    /// WGS84 UTM 分带
+    Utm { zone: u32, north: bool },
+    /// 东北天切平面，原点为经度、纬度、椭球高
+    Enu { origin: [f64; 3] },
+}
+
+impl CrsKind {
+    fn from_epsg(code: u32) -> Option<Self> {
+        match code {
+            4326 | 4979 => Some(CrsKind::Geographic),
+            4978 => Some(CrsKind::Ecef),
+            32601..=32660 => Some(CrsKind::Utm {
+                zone: code - 32600,
+                north: true,
+            }),
+            32701..=32760 => Some(CrsKind::Utm {
+                zone: code - 32700,
+                north: false,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 转为经度、纬度（度）与椭球高
+    fn to_geodetic(self, p: [f64; 3]) -> [f64; 3] {
+        match self {
+            CrsKind::Geographic => p,
+            CrsKind::Ecef => ecef_to_geodetic(p),
+            CrsKind::Utm { zone, north } => utm_to_geodetic(p, zone, north),
+            CrsKind::Enu { origin } => ecef_to_geodetic(enu_to_ecef(p, origin)),
+        }
+    }
+
+    /// 由经度、纬度（度）与椭球高转换
+    fn project_geodetic(self, g: [f64; 3]) -> [f64; 3] {
+        match self {
+            CrsKind::Geographic => g,
+            CrsKind::Ecef => geodetic_to_ecef(g),
+            CrsKind::Utm { zone, north } => geodetic_to_utm(g, zone, north),
+            CrsKind::Enu { origin } => ecef_to_enu(geodetic_to_ecef(g), origin),
+        }
+    }
+}
+
+fn geodetic_to_ecef([lon, lat, h]: [f64; 3]) -> [f64; 3] {
+    let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = lon.to_radians().sin_cos();
+    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+    [
+        (n + h) * cos_lat * cos_lon,
+        (n + h) * cos_lat * sin_lon,
+        (n * (1.0 - WGS84_E2) + h) * sin_lat,
+    ]
+}
+
+/// 迭代求解大地纬度，10 次迭代后误差远小于毫米
+fn ecef_to_geodetic([x, y, z]: [f64; 3]) -> [f64; 3] {
+    let p = x.hypot(y);
+    let lon = y.atan2(x);
+    let mut lat = z.atan2(p * (1.0 - WGS84_E2));
+    let mut h = 0.0;
+    for _ in 0..10 {
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+        h = if cos_lat.abs() > 1e-10 {
+            p / cos_lat - n
+        } else {
+            z.abs() - n * (1.0 - WGS84_E2)
+        };
+        lat = z.atan2(p * (1.0 - WGS84_E2 * n / (n + h)));
+    }
+    [lon.to_degrees(), lat.to_degrees(), h]
+}
+
+/// ECEF 到东北天的旋转矩阵（行依次为 E、N、U 方向）
+fn enu_rotation(origin: [f64; 3]) -> [[f64; 3]; 3] {
+    let (sin_lat, cos_lat) = origin[1].to_radians().sin_cos();
+    let (sin_lon, cos_lon) = origin[0].to_radians().sin_cos();
+    [
+        [-sin_lon, cos_lon, 0.0],
+        [-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat],
+        [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat],
+    ]
+}
+
+fn ecef_to_enu(p: [f64; 3], origin: [f64; 3]) -> [f64; 3] {
+    let o = geodetic_to_ecef(origin);
+    let d: [f64; 3] = std::array::from_fn(|k| p[k] - o[k]);
+    enu_rotation(origin).map(|row| row[0] * d[0] + row[1] * d[1] + row[2] * d[2])
+}
+
+fn enu_to_ecef(p: [f64; 3], origin: [f64; 3]) -> [f64; 3] {
+    let o = geodetic_to_ecef(origin);
+    let r = enu_rotation(origin);
+    std::array::from_fn(|k| o[k] + r[0][k] * p[0] + r[1][k] * p[1] + r[2][k] * p[2])
+}
+
+/// 横轴墨卡托 Krüger 级数系数（展开到 n³，分带内误差小于毫米）
+struct KruegerSeries {
+    /// 矫正的子午线弧长系数 A
+    radius: f64,
+    alpha: [f64; 3],
+    beta: [f64; 3],
+    delta: [f64; 3],
+    n: f64,
+}
+
+impl KruegerSeries {
+    fn wgs84() -> Self {
+        let n = WGS84_F / (2.0 - WGS84_F);
+        let (n2, n3) = (n * n, n * n * n);
+        Self {
+            radius: WGS84_A / (1.0 + n) * (1.0 + n2 / 4.0 + n2 * n2 / 64.0),
+            alpha: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0,
+                13.0 * n2 / 48.0 - 3.0 * n3 / 5.0,
+                61.0 * n3 / 240.0,
+            ],
+            beta: [
+                n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0,
+                n2 / 48.0 + n3 / 15.0,
+                17.0 * n3 / 480.0,
+            ],
+            delta: [
+                2.0 * n - 2.0 * n2 / 3.0 - 2.0 * n3,
+                7.0 * n2 / 3.0 - 8.0 * n3 / 5.0,
+                56.0 * n3 / 15.0,
+            ],
+            n,
+        }
+    }
+}
+
+/// 分带中央经线（度）
+fn utm_central_meridian(zone: u32) -> f64 {
+    zone as f64 * 6.0 - 183.0
+}
+
+fn geodetic_to_utm([lon, lat, h]: [f64; 3], zone: u32, north: bool) -> [f64; 3] {
+    let series = KruegerSeries::wgs84();
+    let c = 2.0 * series.n.sqrt() / (1.0 + series.n);
+    let phi = lat.to_radians();
+    let dlon = (lon - utm_central_meridian(zone)).to_radians();
+
+    let t = (phi.sin().atanh() - c * (c * phi.sin()).atanh()).sinh();
+    let xi_p = t.atan2(dlon.cos());
+    let eta_p = (dlon.sin() / (1.0 + t * t).sqrt()).atanh();
+
+    let (mut xi, mut eta) = (xi_p, eta_p);
+    for (j, alpha) in series.alpha.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi += alpha * (k * xi_p).sin() * (k * eta_p).cosh();
+        eta += alpha * (k * xi_p).cos() * (k * eta_p).sinh();
+    }
+
+    let false_northing = if north { 0.0 } else { UTM_FALSE_NORTHING_SOUTH };
+    [
+        UTM_FALSE_EASTING + UTM_K0 * series.radius * eta,
+        false_northing + UTM_K0 * series.radius * xi,
+        h,
+    ]
+}
+
+fn utm_to_geodetic([easting, northing, h]: [f64; 3], zone: u32, north: bool) -> [f64; 3] {
+    let series = KruegerSeries::wgs84();
+    let false_northing = if north { 0.0 } else { UTM_FALSE_NORTHING_SOUTH };
+    let xi = (northing - false_northing) / (UTM_K0 * series.radius);
+    let eta = (easting - UTM_FALSE_EASTING) / (UTM_K0 * series.radius);
+
+    let (mut xi_p, mut eta_p) = (xi, eta);
+    for (j, beta) in series.beta.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi_p -= beta * (k * xi).sin() * (k * eta).cosh();
+        eta_p -= beta * (k * xi).cos() * (k * eta).sinh();
+    }
+
+    let chi = (xi_p.sin() / eta_p.cosh()).asin();
+    let mut phi = chi;
+    for (j, delta) in series.delta.iter().enumerate() {
+        phi += delta * (2.0 * (j + 1) as f64 * chi).sin();
+    }
+    let dlon = eta_p.sinh().atan2(xi_p.cos());
+    [
+        utm_central_meridian(zone) + dlon.to_degrees(),
+        phi.to_degrees(),
+        h,
+    ]
+}
+
+impl HighPerformancePointCloud {
+    /// 坐标参考系
+    pub fn crs(&self) -> Option<&Crs> {
+        self.crs_ref()
+    }
+
+    /// 设置坐标参考系（只修改描述，不转换坐标）
+    pub fn set_crs(&mut self, crs: Option<Crs>) {
+        *self.crs_mut() = crs;
+    }
+
+    /// 重投影到目标坐标系，返回新点云，原点按新坐标的包围盒重新选择
+    /// 经纬度目标的水平轴总是取最小值向下取整作为原点，局部坐标保持在 1 度以内
+    pub fn reproject(&self, target: &Crs) -> Result<Self> {
+        let source = self
+            .crs()
+            .ok_or_else(|| PointCloudError::InvalidParameter("点云未设置坐标系".to_string()))?;
+        let (from, to) = (source.kind()?, target.kind()?);
+
+        let mut result = self.clone();
+        let points = self.global_points();
+        if from != to && !points.is_empty() {
+            let points: Vec<[f64; 3]> = points
+                .into_iter()
+                .map(|p| to.project_geodetic(from.to_geodetic(p)))
+                .collect();
+            let min: [f64; 3] =
+                std::array::from_fn(|k| points.iter().map(|p| p[k]).fold(f64::INFINITY, f64::min));
+            let max = std::array::from_fn(|k| {
+                points
+                    .iter()
+                    .map(|p| p[k])
+                    .fold(f64::NEG_INFINITY, f64::max)
+            });
+            let mut origin = auto_origin(min, max);
+            if to == CrsKind::Geographic {
+                // 度数虽小，f32 在数十度处只能保留约 1e-6 度（约 0.1 米）
+                origin[0] = min[0].floor();
+                origin[1] = min[1].floor();
+            }
+            let reprojected = Self::from_global_points(&points, Some(origin))?;
+            *result.xyz_mut() = reprojected.xyz_ref().clone();
+            *result.origin_mut() = reprojected.origin();
+        }
+        result.set_crs(Some(target.clone()));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crs() {
+        assert_eq!(Crs::parse("epsg:32633").unwrap(), Crs::Epsg(32633));
+        assert_eq!(Crs::parse("4326").unwrap(), Crs::Epsg(4326));
+        assert_eq!(
+            Crs::parse("ENU:116.3,39.9").unwrap(),
+            Crs::Enu {
+                lon: 116.3,
+                lat: 39.9,
+                height: 0.0
+            }
+        );
+        let wkt = r#"PROJCS["WGS 84 / UTM zone 33N",GEOGCS["WGS 84",AUTHORITY["EPSG","4326"]],AUTHORITY["EPSG","32633"]]"#;
+        assert_eq!(Crs::parse(wkt).unwrap().epsg(), Some(32633));
+        assert!(Crs::parse("not a crs").is_err());
+    }
+
+    #[test]
+    fn test_utm_roundtrip() {
+        // 中央经线上 45°N 的北坐标为子午线弧长乘以比例因子
+        let utm = geodetic_to_utm([9.0, 45.0, 0.0], 32, true);
+        assert!((utm[0] - 500_000.0).abs() < 1e-6);
+        assert!((utm[1] - 4_982_950.400).abs() < 1e-2);
+
+        let geodetic = [15.123_456_7, -33.765_432_1, 120.0];
+        let back = utm_to_geodetic(geodetic_to_utm(geodetic, 33, false), 33, false);
+        assert!((back[0] - geodetic[0]).abs() < 1e-9);
+        assert!((back[1] - geodetic[1]).abs() < 1e-9);
+        assert_eq!(back[2], 120.0);
+    }
+
+    #[test]
+    fn test_reproject_geographic_origin() {
+        let utm = [
+            [500_000.0, 4_982_950.4, 100.0],
+            [500_078.9, 4_983_061.5, 101.0],
+        ];
+        let mut pc = HighPerformancePointCloud::from_global_points(&utm, None).unwrap();
+        pc.set_crs(Some(Crs::Epsg(32633)));
+
+        let geographic = pc.reproject(&Crs::Epsg(4326)).unwrap();
+        assert_eq!(geographic.origin(), [15.0, 45.0, 0.0]);
+
+        // 经纬度中转后回到 UTM 保持毫米精度
+        let back = geographic.reproject(&Crs::Epsg(32633)).unwrap();
+        for (a, b) in back.global_points().iter().zip(&utm) {
+            for k in 0..3 {
+                assert!((a[k] - b[k]).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ecef_and_enu_roundtrip() {
+        let geodetic = [116.391, 39.907, 50.0];
+        let back = ecef_to_geodetic(geodetic_to_ecef(geodetic));
+        for (a, b) in back.iter().zip(&geodetic) {
+            assert!((a - b).abs() < 1e-8);
+        }
+
+        let origin = [116.39, 39.9, 0.0];
+        let enu = ecef_to_enu(geodetic_to_ecef(origin), origin);
+        assert!(enu.iter().all(|v| v.abs() < 1e-6));
+        let up = ecef_to_enu(geodetic_to_ecef([116.39, 39.9, 10.0]), origin);
+        assert!((up[2] - 10.0).abs() < 1e-6);
+    }
+}
@@ -1,5 +1,13 @@
+pub mod attribute_data;
 pub mod attributes;
+pub mod camera;
 /// 点云模块入口
 pub mod core;
+pub mod crs;
+pub mod octree;
+pub mod origin;
+pub mod projection;
+pub mod sampling;
 pub mod transform;
 pub mod voxel;
+pub mod voxel_grid;
@@ -0,0 +1,499 @@
+/// 八叉树：LOD 分层抽样、包围盒/视锥查询、节点统计
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+use std::collections::VecDeque;
+
+/// 八叉树节点
+/// 子节点序号按 (x << 2) | (y << 1) | z 排列，与 Potree 约定一致
+#[derive(Clone, Debug)]
+pub struct OctreeNode {
+    /// 节点深度（根节点为0）
+    pub depth: u32,
+    /// 节点立方体的最小角
+    pub min: [f32; 3],
+    /// 节点立方体的最大角
+    pub max: [f32; 3],
+    /// 父节点序号
+    pub parent: Option<usize>,
+    /// 8个子节点序号（空子节点为 None）
+    pub children: [Option<usize>; 8],
+    /// 节点内的点在 Octree::indices 中的范围 [start, end)
+    pub start: usize,
+    pub end: usize,
+    /// 节点内点的质心
+    pub centroid: [f32; 3],
+    /// 节点内点的实际包围盒
+    pub bounds_min: [f32; 3],
+    pub bounds_max: [f32; 3],
+    /// 代表点（最接近质心的点）
+    pub representative: usize,
+}
+
+impl OctreeNode {
+    /// 是否为叶节点
+    pub fn is_leaf(&self) -> bool {
+        self.children.iter().all(Option::is_none)
+    }
+
+    /// 节点内的点数
+    pub fn point_count(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// 视锥体（平面 ax + by + cz + d >= 0 为内侧）
+/// 平面保存在点云的局部坐标系中
+#[derive(Clone, Debug)]
+pub struct Frustum {
+    pub planes: Vec<[f32; 4]>,
+}
+
+impl Frustum {
+    /// 从全局坐标的平面列表创建
+    /// origin: 点云原点，平面按 f64 平移到局部坐标：d' = d + a·ox + b·oy + c·oz
+    pub fn from_planes(planes: Vec<[f64; 4]>, origin: [f64; 3]) -> Result<Self> {
+        if planes.is_empty() {
+            return Err(PointCloudError::InvalidParameter(
+                "视锥平面列表为空".to_string(),
+            ));
+        }
+        let planes = planes
+            .into_iter()
+            .map(|[a, b, c, d]| {
+                let d = d + a * origin[0] + b * origin[1] + c * origin[2];
+                [a as f32, b as f32, c as f32, d as f32]
+            })
+            .collect();
+        Ok(Self { planes })
+    }
+
+    /// 从 4x4 视图投影矩阵提取6个裁剪平面（OpenGL 裁剪空间约定，列向量）
+    /// 矩阵作用于全局坐标，origin 为点云原点
+    pub fn from_view_projection(matrix: &[Vec<f64>], origin: [f64; 3]) -> Result<Self> {
+        if matrix.len() != 4 || matrix.iter().any(|row| row.len() != 4) {
+            return Err(PointCloudError::InvalidParameter(
+                "视图投影矩阵必须是4x4".to_string(),
+            ));
+        }
+        let row = |i: usize| [matrix[i][0], matrix[i][1], matrix[i][2], matrix[i][3]];
+        let (r0, r1, r2, r3) = (row(0), row(1), row(2), row(3));
+        let add = |a: [f64; 4], b: [f64; 4]| -> [f64; 4] { std::array::from_fn(|k| a[k] + b[k]) };
+        let sub = |a: [f64; 4], b: [f64; 4]| -> [f64; 4] { std::array::from_fn(|k| a[k] - b[k]) };
+        let planes = vec![
+            add(r3, r0),
+            sub(r3, r0),
+            add(r3, r1),
+            sub(r3, r1),
+            add(r3, r2),
+            sub(r3, r2),
+        ];
+        Self::from_planes(planes, origin)
+    }
+
+    /// 点是否在视锥内
+    pub fn contains_point(&self, p: [f32; 3]) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3] >= 0.0)
+    }
+
+    /// 包围盒与视锥的关系：None 为完全在外，Some(true) 为完全在内，Some(false) 为相交
+    fn classify_box(&self, min: [f32; 3], max: [f32; 3]) -> Option<bool> {
+        let mut inside = true;
+        for plane in &self.planes {
+            let distance = |corner: [f32; 3]| {
+                plane[0] * corner[0] + plane[1] * corner[1] + plane[2] * corner[2] + plane[3]
+            };
+            // 沿平面法向最远/最近的角点
+            let positive: [f32; 3] =
+                std::array::from_fn(|k| if plane[k] >= 0.0 { max[k] } else { min[k] });
+            let negative: [f32; 3] =
+                std::array::from_fn(|k| if plane[k] >= 0.0 { min[k] } else { max[k] });
+            if distance(positive) < 0.0 {
+                return None;
+            }
+            if distance(negative) < 0.0 {
+                inside = false;
+            }
+        }
+        Some(inside)
+    }
+}
+
+/// 八叉树
+/// 点索引指向构建时的点云，点云修改后需要重新构建
+pub struct Octree {
+    nodes: Vec<OctreeNode>,
+    indices: Vec<usize>,
+    points: Vec<[f32; 3]>,
+    max_depth: u32,
+    leaf_size: usize,
+}
+
+impl Octree {
+    /// 从点云构建八叉树
+    /// max_depth: 最大深度；leaf_size: 节点点数不超过该值时不再细分
+    pub fn new(
+        cloud: &HighPerformancePointCloud,
+        max_depth: u32,
+        leaf_size: usize,
+    ) -> Result<Self> {
+        if cloud.point_count() == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "无法对空点云构建八叉树".to_string(),
+            ));
+        }
+        if leaf_size == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "leaf_size必须大于0".to_string(),
+            ));
+        }
+
+        let points = tensor::tensor2_to_points(cloud.xyz_ref());
+        let (root_min, root_max) = cube_bounds(&points);
+
+        let mut octree = Self {
+            nodes: Vec::new(),
+            indices: (0..points.len()).collect(),
+            points,
+            max_depth,
+            leaf_size,
+        };
+        octree.build(root_min, root_max);
+        Ok(octree)
+    }
+
+    /// 广度优先构建，节点按层序存储
+    fn build(&mut self, root_min: [f32; 3], root_max: [f32; 3]) {
+        let root = self.make_node(0, root_min, root_max, None, 0, self.indices.len());
+        self.nodes.push(root);
+
+        let mut queue = VecDeque::from([0usize]);
+        while let Some(node_id) = queue.pop_front() {
+            let node = &self.nodes[node_id];
+            if node.depth >= self.max_depth || node.point_count() <= self.leaf_size {
+                continue;
+            }
+            let (depth, min, max, start, end) =
+                (node.depth, node.min, node.max, node.start, node.end);
+            let center: [f32; 3] = std::array::from_fn(|k| (min[k] + max[k]) * 0.5);
+
+            // 按子节点序号对点索引做计数排序
+            let points = &self.points;
+            let octant_of = |idx: usize| {
+                let p = points[idx];
+                (usize::from(p[0] >= center[0]) << 2)
+                    | (usize::from(p[1] >= center[1]) << 1)
+                    | usize::from(p[2] >= center[2])
+            };
+            let mut counts = [0usize; 8];
+            for &idx in &self.indices[start..end] {
+                counts[octant_of(idx)] += 1;
+            }
+            let mut offsets = [0usize; 8];
+            for octant in 1..8 {
+                offsets[octant] = offsets[octant - 1] + counts[octant - 1];
+            }
+            let mut sorted = vec![0usize; end - start];
+            let mut cursor = offsets;
+            for &idx in &self.indices[start..end] {
+                let octant = octant_of(idx);
+                sorted[cursor[octant]] = idx;
+                cursor[octant] += 1;
+            }
+            self.indices[start..end].copy_from_slice(&sorted);
+
+            for (octant, &count) in counts.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                let child_min = std::array::from_fn(|k| {
+                    if (octant >> (2 - k)) & 1 == 1 {
+                        center[k]
+                    } else {
+                        min[k]
+                    }
+                });
+                let child_max = std::array::from_fn(|k| {
+                    if (octant >> (2 - k)) & 1 == 1 {
+                        max[k]
+                    } else {
+                        center[k]
+                    }
+                });
+                let child_start = start + offsets[octant];
+                let child = self.make_node(
+                    depth + 1,
+                    child_min,
+                    child_max,
+                    Some(node_id),
+                    child_start,
+                    child_start + count,
+                );
+                let child_id = self.nodes.len();
+                self.nodes.push(child);
+                self.nodes[node_id].children[octant] = Some(child_id);
+                queue.push_back(child_id);
+            }
+        }
+    }
+
+    /// 创建节点并计算统计量
+    fn make_node(
+        &self,
+        depth: u32,
+        min: [f32; 3],
+        max: [f32; 3],
+        parent: Option<usize>,
+        start: usize,
+        end: usize,
+    ) -> OctreeNode {
+        let members = &self.indices[start..end];
+        let mut sum = [0.0f64; 3];
+        let mut bounds_min = [f32::INFINITY; 3];
+        let mut bounds_max = [f32::NEG_INFINITY; 3];
+        for &idx in members {
+            for (k, &value) in self.points[idx].iter().enumerate() {
+                sum[k] += value as f64;
+                bounds_min[k] = bounds_min[k].min(value);
+                bounds_max[k] = bounds_max[k].max(value);
+            }
+        }
+        let count = members.len() as f64;
+        let centroid = sum.map(|value| (value / count) as f32);
+
+        let representative = members
+            .iter()
+            .copied()
+            .min_by(|&a, &b| {
+                squared_distance(self.points[a], centroid)
+                    .total_cmp(&squared_distance(self.points[b], centroid))
+            })
+            .unwrap_or(0);
+
+        OctreeNode {
+            depth,
+            min,
+            max,
+            parent,
+            children: [None; 8],
+            start,
+            end,
+            centroid,
+            bounds_min,
+            bounds_max,
+            representative,
+        }
+    }
+
+    /// 全部节点（按层序）
+    pub fn nodes(&self) -> &[OctreeNode] {
+        &self.nodes
+    }
+
+    /// 节点数量
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 实际最大深度
+    pub fn depth(&self) -> u32 {
+        self.nodes.iter().map(|node| node.depth).max().unwrap_or(0)
+    }
+
+    /// 构建参数：最大深度
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    /// 构建参数：叶节点点数上限
+    pub fn leaf_size(&self) -> usize {
+        self.leaf_size
+    }
+
+    /// 构建时的点数
+    pub fn point_count(&self) -> usize {
+        self.points.len()
+    }
+
+    /// 节点内的点索引
+    pub fn node_indices(&self, node: usize) -> &[usize] {
+        let node = &self.nodes[node];
+        &self.indices[node.start..node.end]
+    }
+
+    /// 第 level 层的 LOD 抽样点索引（升序）
+    /// 深度等于 level 的节点各取一个代表点；深度小于 level 的叶节点已无法细分，取全部点
+    pub fn level_indices(&self, level: u32) -> Vec<usize> {
+        let mut result = Vec::new();
+        for (node_id, node) in self.nodes.iter().enumerate() {
+            if node.depth == level {
+                result.push(node.representative);
+            } else if node.depth < level && node.is_leaf() {
+                result.extend_from_slice(self.node_indices(node_id));
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// 按 LOD 层级抽取点云
+    pub fn extract_level(
+        &self,
+        cloud: &HighPerformancePointCloud,
+        level: u32,
+    ) -> Result<HighPerformancePointCloud> {
+        self.check_cloud(cloud)?;
+        let indices = self.level_indices(level);
+        Ok(cloud.select_points(tensor::indices_to_tensor(&indices)))
+    }
+
+    /// 查询包围盒 [min, max] 内的点索引（升序）
+    pub fn query_box(&self, min: [f32; 3], max: [f32; 3]) -> Vec<usize> {
+        let inside_box = |p: [f32; 3]| (0..3).all(|k| p[k] >= min[k] && p[k] <= max[k]);
+        self.query(
+            |node| {
+                let outside = (0..3).any(|k| node.max[k] < min[k] || node.min[k] > max[k]);
+                if outside {
+                    return None;
+                }
+                Some((0..3).all(|k| node.min[k] >= min[k] && node.max[k] <= max[k]))
+            },
+            inside_box,
+        )
+    }
+
+    /// 查询视锥内的点索引（升序）
+    pub fn query_frustum(&self, frustum: &Frustum) -> Vec<usize> {
+        self.query(
+            |node| frustum.classify_box(node.min, node.max),
+            |p| frustum.contains_point(p),
+        )
+    }
+
+    /// 通用区域查询
+    /// classify: None 为节点完全在外，Some(true) 为完全在内，Some(false) 为相交
+    fn query<C, P>(&self, classify: C, contains: P) -> Vec<usize>
+    where
+        C: Fn(&OctreeNode) -> Option<bool>,
+        P: Fn([f32; 3]) -> bool,
+    {
+        let mut result = Vec::new();
+        let mut stack = vec![0usize];
+        while let Some(node_id) = stack.pop() {
+            let node = &self.nodes[node_id];
+            match classify(node) {
+                None => {}
+                Some(true) => result.extend_from_slice(self.node_indices(node_id)),
+                Some(false) if node.is_leaf() => result.extend(
+                    self.node_indices(node_id)
+                        .iter()
+                        .copied()
+                        .filter(|&idx| contains(self.points[idx])),
+                ),
+                Some(false) => stack.extend(node.children.iter().flatten()),
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    fn check_cloud(&self, cloud: &HighPerformancePointCloud) -> Result<()> {
+        if cloud.point_count() != self.points.len() {
+            return Err(PointCloudError::DimensionMismatch {
+                expected: self.points.len(),
+                actual: cloud.point_count(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 计算包含所有点的立方体包围盒
+fn cube_bounds(points: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for p in points {
+        for (k, &value) in p.iter().enumerate() {
+            min[k] = min[k].min(value);
+            max[k] = max[k].max(value);
+        }
+    }
+    let size = (0..3)
+        .map(|k| max[k] - min[k])
+        .fold(0.0f32, f32::max)
+        .max(1e-6);
+    (min, std::array::from_fn(|k| min[k] + size))
+}
+
+fn squared_distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|k| (a[k] - b[k]).powi(2)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_cloud() -> HighPerformancePointCloud {
+        let mut xyz = Vec::new();
+        for x in 0..8 {
+            for y in 0..8 {
+                for z in 0..8 {
+                    xyz.push(vec![x as f32, y as f32, z as f32]);
+                }
+            }
+        }
+        HighPerformancePointCloud::from_xyz(xyz).unwrap()
+    }
+
+    #[test]
+    fn test_octree_levels() {
+        let pc = grid_cloud();
+        let octree = Octree::new(&pc, 3, 1).unwrap();
+
+        assert_eq!(octree.nodes()[0].point_count(), 512);
+        assert_eq!(octree.level_indices(0).len(), 1);
+        assert_eq!(octree.level_indices(1).len(), 8);
+        assert_eq!(octree.level_indices(3).len(), 512);
+    }
+
+    #[test]
+    fn test_octree_box_query() {
+        let pc = grid_cloud();
+        let octree = Octree::new(&pc, 4, 4).unwrap();
+
+        let hits = octree.query_box([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+        assert_eq!(hits.len(), 8);
+    }
+
+    #[test]
+    fn test_frustum_from_identity() {
+        let identity: Vec<Vec<f64>> = (0..4)
+            .map(|i| (0..4).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+            .collect();
+        let frustum = Frustum::from_view_projection(&identity, [0.0; 3]).unwrap();
+
+        assert!(frustum.contains_point([0.5, 0.5, 0.5]));
+        assert!(!frustum.contains_point([2.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_frustum_global_coordinates() {
+        // 视图矩阵把以 (500000, 4000000, 0) 为中心的单位立方体映射到裁剪空间
+        let origin = [500_000.0, 4_000_000.0, 0.0];
+        let view: Vec<Vec<f64>> = vec![
+            vec![1.0, 0.0, 0.0, -500_000.5],
+            vec![0.0, 1.0, 0.0, -4_000_000.5],
+            vec![0.0, 0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+        ];
+        let frustum = Frustum::from_view_projection(&view, origin).unwrap();
+
+        // 局部坐标 (0.5, 0.5, 0) 即全局视锥中心
+        assert!(frustum.contains_point([0.5, 0.5, 0.0]));
+        assert!(frustum.contains_point([1.4, -0.4, 0.9]));
+        assert!(!frustum.contains_point([1.6, 0.5, 0.0]));
+    }
+}
@@ -0,0 +1,106 @@
+/// 局部坐标原点：张量保存 f32 局部坐标，IO 与 numpy 接口按 f64 全局坐标读写
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor;
+
+/// 坐标绝对值低于该值时 f32 精度足够（约 1mm），对应轴不设原点
+pub const LOCAL_COORD_LIMIT: f64 = 1.0e4;
+
+/// 按包围盒自动选择原点：大坐标轴取最小值向下取整，小坐标轴为 0
+pub fn auto_origin(min: [f64; 3], max: [f64; 3]) -> [f64; 3] {
+    std::array::from_fn(|k| {
+        if min[k].abs().max(max[k].abs()) < LOCAL_COORD_LIMIT {
+            0.0
+        } else {
+            min[k].floor()
+        }
+    })
+}
+
+impl HighPerformancePointCloud {
+    /// 从全局坐标创建点云
+    /// origin: 局部坐标原点，None 时按包围盒自动选择
+    pub fn from_global_points(points: &[[f64; 3]], origin: Option<[f64; 3]>) -> Result<Self> {
+        if points.is_empty() {
+            return Err("XYZ数据为空".into());
+        }
+        let origin = match origin {
+            Some(origin) => origin,
+            None => {
+                let min = std::array::from_fn(|k| {
+                    points.iter().map(|p| p[k]).fold(f64::INFINITY, f64::min)
+                });
+                let max = std::array::from_fn(|k| {
+                    points
+                        .iter()
+                        .map(|p| p[k])
+                        .fold(f64::NEG_INFINITY, f64::max)
+                });
+                auto_origin(min, max)
+            }
+        };
+
+        let local: Vec<f32> = points
+            .iter()
+            .flat_map(|p| [0, 1, 2].map(|k| (p[k] - origin[k]) as f32))
+            .collect();
+        let xyz = tensor::tensor2_from_slice(&local, points.len(), 3)?;
+        let mut result = Self::from_tensor_xyz(xyz)?;
+        *result.origin_mut() = origin;
+        Ok(result)
+    }
+
+    /// 全局坐标（局部坐标 + 原点）
+    pub fn global_points(&self) -> Vec<[f64; 3]> {
+        let origin = self.origin();
+        tensor::tensor2_to_points(self.xyz_ref())
+            .into_iter()
+            .map(|p| std::array::from_fn(|k| p[k] as f64 + origin[k]))
+            .collect()
+    }
+
+    /// 更换原点，全局坐标不变
+    pub fn set_origin(&mut self, origin: [f64; 3]) -> Result<()> {
+        if origin.iter().any(|v| !v.is_finite()) {
+            return Err(PointCloudError::InvalidParameter(
+                "origin必须为有限值".to_string(),
+            ));
+        }
+        if self.point_count() > 0 {
+            let points = self.global_points();
+            let local: Vec<f32> = points
+                .iter()
+                .flat_map(|p| [0, 1, 2].map(|k| (p[k] - origin[k]) as f32))
+                .collect();
+            *self.xyz_mut() = tensor::tensor2_from_slice(&local, points.len(), 3)?;
+        }
+        *self.origin_mut() = origin;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_points_keep_precision() {
+        let points = [
+            [512_345.678, 4_123_456.789, 12.345],
+            [512_346.001, 4_123_457.002, 13.0],
+        ];
+        let mut pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        assert_eq!(pc.origin(), [512_345.0, 4_123_456.0, 0.0]);
+
+        let restored = pc.global_points();
+        for (a, b) in restored.iter().zip(&points) {
+            for (x, y) in a.iter().zip(b) {
+                assert!((x - y).abs() < 1e-4);
+            }
+        }
+
+        pc.set_origin([512_000.0, 4_123_000.0, 0.0]).unwrap();
+        assert!((pc.global_points()[1][0] - 512_346.001).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,460 @@
+/// 投影：BEV 鸟瞰图与球面距离图像（感知模型输入）
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor::{self, Backend, Tensor2, Tensor3};
+use burn::tensor::{IndexingUpdateOp, Tensor, TensorData};
+
+/// 投影图像的像素数上限（H×W），防止极小分辨率导致超大分配
+pub const MAX_IMAGE_PIXELS: usize = 1 << 28;
+
+/// BEV 通道类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BevChannel {
+    /// 像素内最大高度（全局 z，空像素为 NaN）
+    Height,
+    /// 归一化点密度 min(1, ln(n+1)/ln(64))
+    Density,
+    /// 像素内平均强度（空像素为 NaN）
+    Intensity,
+}
+
+impl BevChannel {
+    /// 从通道名解析（height/density/intensity）
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "height" => Ok(BevChannel::Height),
+            "density" => Ok(BevChannel::Density),
+            "intensity" => Ok(BevChannel::Intensity),
+            _ => Err(PointCloudError::InvalidParameter(format!(
+                "未知的BEV通道: {}",
+                name
+            ))),
+        }
+    }
+}
+
+/// 球面距离图像
+/// 无效像素的 depth/intensity 为 -1，index_map 为 -1
+pub struct RangeImage {
+    /// 距离图像（形状[H,W]）
+    pub depth: Tensor2,
+    /// 强度图像（形状[H,W]，点云无intensity时为None）
+    pub intensity: Option<Tensor2>,
+    /// 像素 -> 点索引（行优先，长度H*W）
+    pub index_map: Vec<i64>,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl HighPerformancePointCloud {
+    /// 生成 BEV 伪图像，返回形状为[H,W,C]的张量
+    /// 行对应 x 轴（x_range），列对应 y 轴（y_range），范围为左闭右开的全局坐标
+    /// height 通道为全局高度，height/intensity 通道的空像素为 NaN，density 为 0
+    pub fn to_bev(
+        &self,
+        x_range: (f64, f64),
+        y_range: (f64, f64),
+        resolution: f32,
+        channels: &[BevChannel],
+    ) -> Result<Tensor3> {
+        if !resolution.is_finite() || resolution <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "resolution必须为大于0的有限值".to_string(),
+            ));
+        }
+        if x_range.1 <= x_range.0 || y_range.1 <= y_range.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "x_range/y_range必须满足 min < max".to_string(),
+            ));
+        }
+        if channels.is_empty() {
+            return Err(PointCloudError::InvalidParameter(
+                "BEV通道列表为空".to_string(),
+            ));
+        }
+        if channels.contains(&BevChannel::Intensity) && !self.has_intensity() {
+            return Err(PointCloudError::InvalidParameter(
+                "点云没有intensity，无法生成intensity通道".to_string(),
+            ));
+        }
+
+        // 先按 f64 检查像素数，避免转换 usize 时溢出
+        let rows = ((x_range.1 - x_range.0) / resolution as f64).ceil();
+        let cols = ((y_range.1 - y_range.0) / resolution as f64).ceil();
+        if rows.is_nan() || cols.is_nan() || rows * cols > MAX_IMAGE_PIXELS as f64 {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "BEV像素数{}超过上限{}，请增大resolution或缩小范围",
+                rows * cols,
+                MAX_IMAGE_PIXELS
+            )));
+        }
+        let height = rows as usize;
+        let width = cols as usize;
+        let pixels = height * width;
+        let device = tensor::default_device();
+
+        // 全局范围按 f64 减去原点后转为局部坐标
+        let origin = self.origin();
+        let x_range = (
+            (x_range.0 - origin[0]) as f32,
+            (x_range.1 - origin[0]) as f32,
+        );
+        let y_range = (
+            (y_range.0 - origin[1]) as f32,
+            (y_range.1 - origin[1]) as f32,
+        );
+
+        // 第一步：在张量上筛选范围内的点
+        let xyz = self.xyz_ref();
+        let x = tensor::xyz_column(xyz, 0);
+        let y = tensor::xyz_column(xyz, 1);
+        let mask = x
+            .clone()
+            .greater_equal_elem(x_range.0)
+            .bool_and(x.lower_elem(x_range.1))
+            .bool_and(y.clone().greater_equal_elem(y_range.0))
+            .bool_and(y.lower_elem(y_range.1));
+        let kept = tensor::mask_to_indices(mask);
+
+        if kept.is_empty() {
+            let planes = channels
+                .iter()
+                .map(|channel| match channel {
+                    BevChannel::Density => Tensor::<Backend, 2>::zeros([height, width], &device),
+                    _ => Tensor::<Backend, 2>::full([height, width], f32::NAN, &device),
+                })
+                .collect();
+            return Ok(Tensor::<Backend, 2>::stack::<3>(planes, 2));
+        }
+
+        // 第二步：计算每个点的像素线性索引 row * W + col
+        let kept_idx = tensor::indices_to_tensor(&kept);
+        let kept_xyz = xyz.clone().select(0, kept_idx.clone());
+        let rows = tensor::xyz_column(&kept_xyz, 0)
+            .sub_scalar(x_range.0)
+            .div_scalar(resolution)
+            .floor()
+            .clamp(0.0, (height - 1) as f32);
+        let cols = tensor::xyz_column(&kept_xyz, 1)
+            .sub_scalar(y_range.0)
+            .div_scalar(resolution)
+            .floor()
+            .clamp(0.0, (width - 1) as f32);
+        // 在整数域计算线性索引，f32 只能精确表示 2^24 以内的像素
+        let flat = rows.int().mul_scalar(width as i64).add(cols.int());
+
+        // 第三步：scatter-add 统计每个像素的点数
+        let ones = Tensor::<Backend, 1>::ones([kept.len()], &device);
+        let counts = Tensor::<Backend, 1>::zeros([pixels], &device).select_assign(
+            0,
+            flat.clone(),
+            ones,
+            IndexingUpdateOp::Add,
+        );
+
+        let mut planes: Vec<Tensor2> = Vec::with_capacity(channels.len());
+        for channel in channels {
+            let plane = match channel {
+                BevChannel::Height => {
+                    // burn 无 scatter-max，回读像素索引后归约
+                    let flat_vec = tensor::int_tensor1_to_vec(&flat);
+                    let z_vec = tensor::tensor1_to_vec(&tensor::xyz_column(&kept_xyz, 2));
+                    let mut max_z = vec![f32::NEG_INFINITY; pixels];
+                    for (&pixel, &z) in flat_vec.iter().zip(z_vec.iter()) {
+                        let slot = &mut max_z[pixel as usize];
+                        if z > *slot {
+                            *slot = z;
+                        }
+                    }
+                    for value in max_z.iter_mut() {
+                        *value = if value.is_finite() {
+                            (*value as f64 + origin[2]) as f32
+                        } else {
+                            f32::NAN
+                        };
+                    }
+                    tensor::tensor1_from_slice(&max_z)
+                }
+                BevChannel::Density => counts
+                    .clone()
+                    .add_scalar(1.0)
+                    .log()
+                    .div_scalar(64f32.ln())
+                    .clamp_max(1.0),
+                BevChannel::Intensity => {
+                    let intensity = self
+                        .intensity_ref()
+                        .expect("intensity已检查存在")
+                        .clone()
+                        .select(0, kept_idx.clone());
+                    let sums = Tensor::<Backend, 1>::zeros([pixels], &device).select_assign(
+                        0,
+                        flat.clone(),
+                        intensity,
+                        IndexingUpdateOp::Add,
+                    );
+                    // 空像素为 0/0，得到 NaN
+                    sums.div(counts.clone())
+                }
+            };
+            planes.push(plane.reshape([height, width]));
+        }
+
+        Ok(Tensor::<Backend, 2>::stack::<3>(planes, 2))
+    }
+
+    /// 生成球面距离图像
+    /// h_fov/v_fov: 水平/垂直视场角（度，min < max）
+    /// sensor: 传感器的全局坐标，距离与角度相对该位置计算，None 时取点云原点
+    /// 同一像素内保留距离最近的点
+    pub fn to_range_image(
+        &self,
+        h_fov: (f32, f32),
+        v_fov: (f32, f32),
+        width: usize,
+        height: usize,
+        sensor: Option<[f64; 3]>,
+    ) -> Result<RangeImage> {
+        if width == 0 || height == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "width/height必须大于0".to_string(),
+            ));
+        }
+        let pixels = match width.checked_mul(height) {
+            Some(pixels) if pixels <= MAX_IMAGE_PIXELS => pixels,
+            _ => {
+                return Err(PointCloudError::InvalidParameter(format!(
+                    "距离图像像素数超过上限{}",
+                    MAX_IMAGE_PIXELS
+                )))
+            }
+        };
+        if h_fov.1 <= h_fov.0 || v_fov.1 <= v_fov.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "h_fov/v_fov必须满足 min < max".to_string(),
+            ));
+        }
+
+        let (h_min, h_max) = (h_fov.0.to_radians(), h_fov.1.to_radians());
+        let (v_min, v_max) = (v_fov.0.to_radians(), v_fov.1.to_radians());
+
+        // 第一步：在张量上计算球面坐标与像素坐标
+        // 相对传感器的坐标 = 局部坐标 + (原点 - 传感器)，偏移按 f64 计算
+        let origin = self.origin();
+        let sensor = sensor.unwrap_or(origin);
+        let offset: Vec<f32> = (0..3).map(|k| (origin[k] - sensor[k]) as f32).collect();
+        let xyz = self
+            .xyz_ref()
+            .clone()
+            .add(tensor::tensor1_from_slice(&offset).reshape([1, 3]));
+        let xyz = &xyz;
+        let n = self.point_count();
+        let x = tensor::xyz_column(xyz, 0);
+        let y = tensor::xyz_column(xyz, 1);
+        let z = tensor::xyz_column(xyz, 2);
+        let range = xyz.clone().powf_scalar(2.0).sum_dim(1).sqrt().reshape([n]);
+        let yaw = y.atan2(x);
+        let pitch = z.div(range.clone().clamp_min(1e-6)).clamp(-1.0, 1.0).asin();
+        let u = yaw
+            .neg()
+            .add_scalar(h_max)
+            .div_scalar(h_max - h_min)
+            .mul_scalar(width as f32)
+            .floor();
+        let v = pitch
+            .neg()
+            .add_scalar(v_max)
+            .div_scalar(v_max - v_min)
+            .mul_scalar(height as f32)
+            .floor();
+
+        let mask = u
+            .clone()
+            .greater_equal_elem(0.0)
+            .bool_and(u.clone().lower_elem(width as f32))
+            .bool_and(v.clone().greater_equal_elem(0.0))
+            .bool_and(v.clone().lower_elem(height as f32))
+            .bool_and(range.clone().greater_elem(0.0));
+        let flat = v.int().mul_scalar(width as i64).add(u.int());
+
+        // 第二步：回读并按最近距离写入像素（z-buffer）
+        let kept = tensor::mask_to_indices(mask);
+        let flat_vec = tensor::int_tensor1_to_vec(&flat);
+        let range_vec = tensor::tensor1_to_vec(&range);
+        let intensity_vec = self.intensity_ref().map(tensor::tensor1_to_vec);
+
+        let mut depth = vec![-1.0f32; pixels];
+        let mut index_map = vec![-1i64; pixels];
+        for &idx in &kept {
+            let pixel = flat_vec[idx] as usize;
+            let r = range_vec[idx];
+            if index_map[pixel] < 0 || r < depth[pixel] {
+                depth[pixel] = r;
+                index_map[pixel] = idx as i64;
+            }
+        }
+
+        let intensity = intensity_vec.map(|values| {
+            let image: Vec<f32> = index_map
+                .iter()
+                .map(|&idx| if idx < 0 { -1.0 } else { values[idx as usize] })
+                .collect();
+            Tensor::<Backend, 1>::from_data(
+                TensorData::from(image.as_slice()),
+                &tensor::default_device(),
+            )
+            .reshape([height, width])
+        });
+
+        Ok(RangeImage {
+            depth: tensor::tensor2_from_slice(&depth, height, width)?,
+            intensity,
+            index_map,
+            height,
+            width,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bev_density() {
+        let xyz = vec![
+            vec![0.5, 0.5, 1.0],
+            vec![0.6, 0.6, 2.0],
+            vec![1.5, 0.5, 3.0],
+            vec![5.0, 5.0, 4.0],
+        ];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let bev = pc
+            .to_bev(
+                (0.0, 2.0),
+                (0.0, 2.0),
+                1.0,
+                &[BevChannel::Height, BevChannel::Density],
+            )
+            .unwrap();
+
+        assert_eq!(bev.dims(), [2, 2, 2]);
+        let values = bev.into_data().to_vec::<f32>().unwrap();
+        // 像素(0,0)最大高度为2.0
+        assert!((values[0] - 2.0).abs() < 1e-5);
+        // 像素(1,0)高度为3.0
+        assert!((values[4] - 3.0).abs() < 1e-5);
+        // 像素(1,1)无点：高度为 NaN，密度为 0
+        assert!(values[6].is_nan());
+        assert_eq!(values[7], 0.0);
+    }
+
+    #[test]
+    fn test_bev_empty_and_pixel_limit() {
+        let pc = HighPerformancePointCloud::from_xyz(vec![vec![0.5, 0.5, 1.0]]).unwrap();
+        let bev = pc
+            .to_bev(
+                (10.0, 11.0),
+                (10.0, 11.0),
+                1.0,
+                &[BevChannel::Height, BevChannel::Density],
+            )
+            .unwrap();
+        let values = bev.into_data().to_vec::<f32>().unwrap();
+        assert!(values[0].is_nan());
+        assert_eq!(values[1], 0.0);
+
+        assert!(pc
+            .to_bev((0.0, 1e6), (0.0, 1e6), 1e-3, &[BevChannel::Height])
+            .is_err());
+        assert!(pc
+            .to_bev((0.0, 1.0), (0.0, 1.0), f32::NAN, &[BevChannel::Height])
+            .is_err());
+    }
+
+    #[test]
+    fn test_bev_large_image_index() {
+        // 4097x4097 像素超过 f32 精确表示的整数范围（2^24），末尾像素的线性索引必须准确
+        let xyz = vec![vec![4096.5, 4095.5, 1.0], vec![4096.5, 4096.5, 2.0]];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let bev = pc
+            .to_bev((0.0, 4097.0), (0.0, 4097.0), 1.0, &[BevChannel::Height])
+            .unwrap();
+
+        let values = bev.into_data().to_vec::<f32>().unwrap();
+        let last = 4097 * 4097 - 1;
+        assert!((values[last] - 2.0).abs() < 1e-5);
+        assert!((values[last - 1] - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_range_image_keeps_nearest() {
+        let xyz = vec![vec![10.0, 0.0, 0.0], vec![5.0, 0.0, 0.0]];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let image = pc
+            .to_range_image((-180.0, 180.0), (-10.0, 10.0), 8, 4, None)
+            .unwrap();
+
+        let hits: Vec<i64> = image
+            .index_map
+            .iter()
+            .copied()
+            .filter(|&idx| idx >= 0)
+            .collect();
+        assert_eq!(hits, vec![1]);
+    }
+
+    #[test]
+    fn test_range_image_default_sensor() {
+        // 未指定传感器时取点云原点，UTM 点云不会因远离 (0,0,0) 而落在同一像素
+        let points = [[500_010.0, 4_000_005.0, 0.0], [500_005.0, 4_000_010.0, 0.0]];
+        let pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        let image = pc
+            .to_range_image((-180.0, 180.0), (-10.0, 10.0), 64, 4, None)
+            .unwrap();
+        let hits = image.index_map.iter().filter(|&&idx| idx >= 0).count();
+        assert_eq!(hits, 2);
+        assert!(pc
+            .to_range_image((-180.0, 180.0), (-10.0, 10.0), usize::MAX, 2, None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_global_coordinates() {
+        // UTM 量级的点云，BEV 范围与传感器位置均为全局坐标
+        let points = [
+            [500_000.5, 4_000_000.5, 12.0],
+            [500_001.5, 4_000_000.5, 13.0],
+            [500_010.0, 4_000_000.0, 11.0],
+        ];
+        let pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        let bev = pc
+            .to_bev(
+                (500_000.0, 500_002.0),
+                (4_000_000.0, 4_000_001.0),
+                1.0,
+                &[BevChannel::Height],
+            )
+            .unwrap();
+        assert_eq!(bev.dims(), [2, 1, 1]);
+        let values = bev.into_data().to_vec::<f32>().unwrap();
+        assert!((values[0] - 12.0).abs() < 1e-4);
+        assert!((values[1] - 13.0).abs() < 1e-4);
+
+        let image = pc
+            .to_range_image(
+                (-180.0, 180.0),
+                (-10.0, 10.0),
+                8,
+                4,
+                Some([500_000.0, 4_000_000.0, 12.0]),
+            )
+            .unwrap();
+        let nearest = image.index_map.iter().position(|&idx| idx == 0).unwrap();
+        let expected = 0.5f32.sqrt();
+        assert!(
+            (image.depth.into_data().to_vec::<f32>().unwrap()[nearest] - expected).abs() < 1e-4
+        );
+    }
+}
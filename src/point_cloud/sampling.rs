@@ -0,0 +1,249 @@
+/// 非体素采样：最远点采样（FPS）、随机采样、均匀采样、泊松圆盘采样
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::traits::PointCloudCore;
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::tensor::{self, Backend};
+use burn::tensor::{Int, Tensor};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+/// 解析随机种子，None 时生成随机种子
+pub fn resolve_seed(seed: Option<u64>) -> u64 {
+    seed.unwrap_or_else(rand::random)
+}
+
+/// 由基础种子与子流编号派生独立的随机数生成器
+pub fn derive_rng(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+impl HighPerformancePointCloud {
+    /// 最远点采样
+    /// n: 采样点数；start_index: 起始点索引
+    /// 全程使用 burn 张量运算（有 GPU 时运行在 Wgpu 后端），返回采样后的点云与原始索引
+    /// 不同位置的点少于 n 个时提前停止，返回的点数少于 n，索引不重复
+    pub fn farthest_point_sampling(
+        &self,
+        n: usize,
+        start_index: usize,
+    ) -> Result<(Self, Vec<usize>)> {
+        let count = self.point_count();
+        if n == 0 || n > count {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "采样点数必须在[1, {}]范围内，实际为{}",
+                count, n
+            )));
+        }
+        if start_index >= count {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "start_index {}超出点数{}",
+                start_index, count
+            )));
+        }
+
+        let xyz = self.xyz_ref().clone();
+        let mut min_dist =
+            Tensor::<Backend, 1>::full([count], f32::INFINITY, &tensor::default_device());
+        let mut current = tensor::indices_to_tensor(&[start_index]);
+        let mut selected = Vec::with_capacity(n);
+        let mut gaps = Vec::with_capacity(n);
+
+        // 索引与每轮的最大距离始终保留在设备上，避免每轮回读同步
+        for i in 0..n {
+            selected.push(current.clone());
+            if i + 1 == n {
+                break;
+            }
+            let point = xyz.clone().select(0, current);
+            let dist = xyz
+                .clone()
+                .sub(point)
+                .powf_scalar(2.0)
+                .sum_dim(1)
+                .reshape([count]);
+            min_dist = min_dist.min_pair(dist);
+            gaps.push(min_dist.clone().max());
+            current = min_dist.clone().argmax(0);
+        }
+
+        // 最大距离为 0 说明剩余点都与已选点重合，此后的 argmax 会重复选点
+        let mut keep = n;
+        if !gaps.is_empty() {
+            let gaps = tensor::tensor1_to_vec(&Tensor::cat(gaps, 0));
+            if let Some(pos) = gaps.iter().position(|&gap| gap <= 0.0) {
+                keep = pos + 1;
+            }
+        }
+        let indices = Tensor::cat(selected, 0).slice([0..keep]);
+        let result = self.select_points(indices.clone());
+        let indices = tensor::int_tensor1_to_vec(&indices)
+            .into_iter()
+            .map(|idx| idx as usize)
+            .collect();
+        Ok((result, indices))
+    }
+
+    /// 随机采样指定点数（不放回，保持原始点顺序）
+    pub fn random_sample(&self, count: usize, seed: Option<u64>) -> Result<Self> {
+        let total = self.point_count();
+        if count > total {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "采样点数{}超出点数{}",
+                count, total
+            )));
+        }
+
+        let mut rng = StdRng::seed_from_u64(resolve_seed(seed));
+        let mut indices = rand::seq::index::sample(&mut rng, total, count).into_vec();
+        indices.sort_unstable();
+        Ok(self.select_points(tensor::indices_to_tensor(&indices)))
+    }
+
+    /// 按比例随机采样（ratio 取值 (0, 1]），点云非空时至少保留 1 个点
+    pub fn random_sample_ratio(&self, ratio: f32, seed: Option<u64>) -> Result<Self> {
+        if !(ratio > 0.0 && ratio <= 1.0) {
+            return Err(PointCloudError::InvalidParameter(format!(
+                "ratio必须在(0, 1]范围内，实际为{}",
+                ratio
+            )));
+        }
+        let total = self.point_count();
+        let count = (total as f64 * ratio as f64).round() as usize;
+        // 小比例不能四舍五入为 0 而静默返回空点云
+        self.random_sample(count.clamp(total.min(1), total), seed)
+    }
+
+    /// 均匀采样：每隔 k 个点取一个（索引 0, k, 2k, ...）
+    pub fn uniform_sample(&self, every_k: usize) -> Result<Self> {
+        if every_k == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "every_k必须大于0".to_string(),
+            ));
+        }
+        let total = self.point_count() as i64;
+        let indices =
+            Tensor::<Backend, 1, Int>::arange_step(0..total, every_k, &tensor::default_device());
+        Ok(self.select_points(indices))
+    }
+
+    /// 泊松圆盘采样：按随机顺序接受点，保证任意两采样点距离不小于 min_distance
+    /// 使用边长为 min_distance 的哈希网格，只检查相邻27个网格
+    pub fn poisson_disk_sample(&self, min_distance: f32, seed: Option<u64>) -> Result<Self> {
+        if min_distance <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "min_distance必须大于0".to_string(),
+            ));
+        }
+
+        let xyz = tensor::tensor2_to_vec(self.xyz_ref());
+        let mut order: Vec<usize> = (0..xyz.len()).collect();
+        order.shuffle(&mut StdRng::seed_from_u64(resolve_seed(seed)));
+
+        let min_dist_sq = min_distance * min_distance;
+        let cell_of = |p: &[f32]| {
+            (
+                (p[0] / min_distance).floor() as i64,
+                (p[1] / min_distance).floor() as i64,
+                (p[2] / min_distance).floor() as i64,
+            )
+        };
+
+        let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+        let mut accepted = Vec::new();
+        for idx in order {
+            let point = &xyz[idx];
+            let (cx, cy, cz) = cell_of(point);
+            let mut conflict = false;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let Some(members) = grid.get(&(cx + dx, cy + dy, cz + dz)) else {
+                            continue;
+                        };
+                        for &other in members {
+                            let dist_sq: f32 =
+                                (0..3).map(|i| (point[i] - xyz[other][i]).powi(2)).sum();
+                            if dist_sq < min_dist_sq {
+                                conflict = true;
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+            if !conflict {
+                grid.entry((cx, cy, cz)).or_default().push(idx);
+                accepted.push(idx);
+            }
+        }
+
+        accepted.sort_unstable();
+        Ok(self.select_points(tensor::indices_to_tensor(&accepted)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_farthest_point_sampling() {
+        let xyz = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![0.1, 0.0, 0.0],
+            vec![10.0, 0.0, 0.0],
+            vec![5.0, 0.0, 0.0],
+        ];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+
+        let (sampled, indices) = pc.farthest_point_sampling(3, 0).unwrap();
+
+        assert_eq!(indices, vec![0, 2, 3]);
+        assert_eq!(sampled.point_count(), 3);
+    }
+
+    #[test]
+    fn test_farthest_point_sampling_stops_at_distinct_points() {
+        let xyz = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+        ];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+
+        let (sampled, indices) = pc.farthest_point_sampling(4, 0).unwrap();
+
+        assert_eq!(indices, vec![0, 1]);
+        assert_eq!(sampled.point_count(), 2);
+    }
+
+    #[test]
+    fn test_random_sample_small_ratio() {
+        let xyz: Vec<Vec<f32>> = (0..5).map(|i| vec![i as f32, 0.0, 0.0]).collect();
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+
+        assert_eq!(
+            pc.random_sample_ratio(0.01, Some(0)).unwrap().point_count(),
+            1
+        );
+        assert_eq!(
+            pc.random_sample_ratio(1.0, Some(0)).unwrap().point_count(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_poisson_disk_min_distance() {
+        let xyz: Vec<Vec<f32>> = (0..50).map(|i| vec![i as f32 * 0.1, 0.0, 0.0]).collect();
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+
+        let sampled = pc.poisson_disk_sample(0.35, Some(1)).unwrap();
+        let mut xs: Vec<f64> = sampled.get_xyz().iter().map(|p| p[0]).collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        assert!(xs.windows(2).all(|w| w[1] - w[0] >= 0.35 - 1e-5));
+    }
+}
@@ -1,54 +1,25 @@
 /// 坐标变换：矩阵乘法批量实现XYZ空间变换
 use crate::point_cloud::core::HighPerformancePointCloud;
-use crate::traits::CoordinateTransform;
-use crate::utils::tensor::Backend;
+use crate::point_cloud::origin::LOCAL_COORD_LIMIT;
+use crate::traits::{CoordinateTransform, PointCloudCore};
 use crate::utils::{error::Result, tensor};
-use burn::tensor::{Tensor, TensorData};
 
 impl CoordinateTransform for HighPerformancePointCloud {
-    fn transform(&self, matrix: Vec<Vec<f32>>) -> Result<Self> {
-        let (rows, cols) = tensor::validate_matrix_shape(&matrix)?;
-
-        let matrix_tensor = tensor::matrix_to_tensor(matrix)?;
-        let matrix_t = matrix_tensor.clone().transpose();
-
-        let mut result = self.clone();
-        let xyz = self.xyz_ref().clone();
-
-        match (rows, cols) {
-            (3, 3) => {
-                // 3x3变换矩阵（旋转/缩放）
-                let new_xyz = xyz.matmul(matrix_t);
-                *result.xyz_mut() = new_xyz;
-            }
-            (4, 4) => {
-                // 4x4变换矩阵（齐次坐标，旋转+平移）
-                let xyz_vec = tensor::tensor2_to_vec(&xyz);
-                let mut homo_vec = Vec::with_capacity(xyz_vec.len());
-                for point in xyz_vec {
-                    homo_vec.push(vec![point[0], point[1], point[2], 1.0]);
-                }
-                let homo_tensor = tensor::vec2_to_tensor(homo_vec)?;
-                let new_homo = homo_tensor.matmul(matrix_t);
-                let new_homo_vec = tensor::tensor2_to_vec(&new_homo);
-
-                let mut new_xyz_vec = Vec::with_capacity(new_homo_vec.len());
-                for row in new_homo_vec {
-                    let w = row[3];
-                    new_xyz_vec.push(vec![row[0] / w, row[1] / w, row[2] / w]);
-                }
-                let new_xyz = tensor::xyz_to_tensor(new_xyz_vec)?;
-                *result.xyz_mut() = new_xyz;
-            }
-            _ => {
-                return Err("矩阵维度不支持".into());
-            }
+    fn transform(&self, matrix: Vec<Vec<f64>>) -> Result<Self> {
+        let (rows, _) = tensor::validate_matrix_shape(&matrix)?;
+        let linear = std::array::from_fn(|i| std::array::from_fn(|j| matrix[i][j]));
+        if rows == 3 {
+            // 3x3变换矩阵（旋转/缩放）
+            return self.affine_transform(linear, [0.0; 3]);
         }
-
-        Ok(result)
+        if matrix[3] == [0.0, 0.0, 0.0, 1.0] {
+            // 4x4仿射矩阵（旋转+平移）
+            return self.affine_transform(linear, std::array::from_fn(|i| matrix[i][3]));
+        }
+        self.projective_transform(&matrix)
     }
 
-    fn rigid_transform(&self, rotation: Vec<Vec<f32>>, translation: Vec<f32>) -> Result<Self> {
+    fn rigid_transform(&self, rotation: Vec<Vec<f64>>, translation: Vec<f64>) -> Result<Self> {
         if translation.len() != 3 {
             return Err("平移向量必须为3维".into());
         }
@@ -58,21 +29,53 @@ fn rigid_transform(&self, rotation: Vec<Vec<f32>>, translation: Vec<f32>) -> Res
             return Err("旋转矩阵必须为3x3".into());
         }
 
-        let rotation_tensor = tensor::matrix_to_tensor(rotation)?;
-        let rotation_t = rotation_tensor.transpose();
+        let linear = std::array::from_fn(|i| std::array::from_fn(|j| rotation[i][j]));
+        self.affine_transform(linear, [translation[0], translation[1], translation[2]])
+    }
+}
 
-        let translation_data = TensorData::from(translation.as_slice());
-        let translation_tensor =
-            Tensor::<Backend, 1>::from_data(translation_data, &tensor::default_device())
-                .reshape([1, 3]);
+impl HighPerformancePointCloud {
+    /// 仿射变换 p' = A·p + t：线性部分作用于 f32 局部坐标，原点与平移按 f64 合并
+    /// 变换后原点中绝对值小于 1e4 的轴并入局部坐标，其余轴保留为新原点
+    fn affine_transform(&self, linear: [[f64; 3]; 3], translation: [f64; 3]) -> Result<Self> {
+        let origin = self.origin();
+        let moved: [f64; 3] = std::array::from_fn(|i| {
+            (0..3).map(|j| linear[i][j] * origin[j]).sum::<f64>() + translation[i]
+        });
+        let new_origin = moved.map(|v| if v.abs() < LOCAL_COORD_LIMIT { 0.0 } else { v });
+        let shift: Vec<f32> = (0..3).map(|k| (moved[k] - new_origin[k]) as f32).collect();
+
+        let linear: Vec<Vec<f32>> = linear
+            .iter()
+            .map(|row| row.iter().map(|&v| v as f32).collect())
+            .collect();
+        let linear_t = tensor::matrix_to_tensor(linear)?.transpose();
+        let shift = tensor::tensor1_from_slice(&shift).reshape([1, 3]);
 
         let mut result = self.clone();
+        *result.xyz_mut() = self.xyz_ref().clone().matmul(linear_t).add(shift);
+        *result.origin_mut() = new_origin;
+        Ok(result)
+    }
 
-        let xyz = self.xyz_ref().clone();
-        let rotated = xyz.matmul(rotation_t);
-        let translated = rotated + translation_tensor;
-        *result.xyz_mut() = translated;
-
+    /// 非仿射的 4x4 矩阵：在主机端按 f64 全局坐标计算齐次除法，原点保持不变
+    fn projective_transform(&self, matrix: &[Vec<f64>]) -> Result<Self> {
+        let mut result = self.clone();
+        if self.point_count() == 0 {
+            return Ok(result);
+        }
+        let points: Vec<[f64; 3]> = self
+            .global_points()
+            .iter()
+            .map(|p| {
+                let h = [p[0], p[1], p[2], 1.0];
+                let row = |i: usize| (0..4).map(|j| matrix[i][j] * h[j]).sum::<f64>();
+                let w = row(3);
+                std::array::from_fn(|i| row(i) / w)
+            })
+            .collect();
+        let transformed = Self::from_global_points(&points, Some(self.origin()))?;
+        *result.xyz_mut() = transformed.xyz_ref().clone();
         Ok(result)
     }
 }
@@ -121,4 +124,43 @@ fn test_rigid_transform() {
         assert!((xyz_result[0][1] - 2.0).abs() < 1e-5);
         assert!((xyz_result[0][2] - 3.0).abs() < 1e-5);
     }
+
+    #[test]
+    fn test_rigid_transform_global_translation() {
+        let pc = HighPerformancePointCloud::from_xyz(vec![vec![1.0, 2.0, 3.0]]).unwrap();
+        let rotation = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+
+        // f32 无法表示 500000.123，平移必须按 f64 合并到原点
+        let result = pc
+            .rigid_transform(rotation, vec![500_000.123, 4_000_000.456, 0.5])
+            .unwrap();
+        assert_eq!(result.origin(), [500_000.123, 4_000_000.456, 0.0]);
+        let global = result.global_points();
+        assert!((global[0][0] - 500_001.123).abs() < 1e-6);
+        assert!((global[0][1] - 4_000_002.456).abs() < 1e-6);
+        assert!((global[0][2] - 3.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_transform_with_origin() {
+        let points = [[500_000.5, 4_000_000.25, 10.0]];
+        let pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+
+        // 平移 (1, 2, 3)
+        let matrix = vec![
+            vec![1.0, 0.0, 0.0, 1.0],
+            vec![0.0, 1.0, 0.0, 2.0],
+            vec![0.0, 0.0, 1.0, 3.0],
+            vec![0.0, 0.0, 0.0, 1.0],
+        ];
+        let result = pc.transform(matrix).unwrap();
+        let global = result.global_points();
+        assert!((global[0][0] - 500_001.5).abs() < 1e-6);
+        assert!((global[0][1] - 4_000_002.25).abs() < 1e-6);
+        assert!((global[0][2] - 13.0).abs() < 1e-6);
+    }
 }
@@ -1,21 +1,39 @@
-/// 体素下采样：反射分组、2种采样策略实现
+/// 体素下采样：张量化体素分组、2种采样策略实现、聚合模式（均值/中位数/最大值/众数）
+use crate::point_cloud::attribute_data::ComponentValues;
 use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::sampling;
 use crate::traits::{DownsampleStrategy, PointCloudCore, PointCloudProperties, VoxelDownsample};
 use crate::utils::error::{PointCloudError, Result};
 use crate::utils::reflect;
 use crate::utils::tensor;
+use rand::Rng;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// 随机采样策略
-pub struct RandomSampleStrategy;
+/// 每个体素的随机数由种子与体素首点索引派生，结果与体素遍历顺序无关、可复现
+pub struct RandomSampleStrategy {
+    seed: u64,
+}
+
+impl RandomSampleStrategy {
+    /// 创建随机采样策略，seed 为 None 时使用随机种子
+    pub fn new(seed: Option<u64>) -> Self {
+        Self {
+            seed: sampling::resolve_seed(seed),
+        }
+    }
+}
 
 impl DownsampleStrategy for RandomSampleStrategy {
-    fn select_representative(&self, indices: Vec<usize>, _xyz: &[Vec<f32>]) -> Result<usize> {
+    fn select_representative(&self, indices: &[usize], _xyz: &[[f32; 3]]) -> Result<usize> {
         if indices.is_empty() {
             return Err("体素内无点".into());
         }
 
-        // 简单随机选择第一个点（生产环境可使用rand crate）
-        Ok(indices[indices.len() / 2])
+        let mut rng = sampling::derive_rng(self.seed, indices[0] as u64);
+        Ok(indices[rng.random_range(0..indices.len())])
     }
 }
 
@@ -23,114 +41,265 @@ fn select_representative(&self, indices: Vec<usize>, _xyz: &[Vec<f32>]) -> Resul
 pub struct CentroidSampleStrategy;
 
 impl DownsampleStrategy for CentroidSampleStrategy {
-    fn select_representative(&self, indices: Vec<usize>, xyz: &[Vec<f32>]) -> Result<usize> {
-        reflect::find_closest_to_centroid(&indices, xyz)
+    fn select_representative(&self, indices: &[usize], xyz: &[[f32; 3]]) -> Result<usize> {
+        reflect::find_closest_to_centroid(indices, xyz)
     }
 }
 
-impl VoxelDownsample for HighPerformancePointCloud {
-    fn voxel_downsample(
+/// 体素聚合方式
+/// 每个体素输出一个新点：xyz 取体素内质心，intensity/RGB/GPS时间/自定义属性按聚合方式归约
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoxelAggregation {
+    /// 平均值
+    Mean,
+    /// 下中位数（结果总是体素内已有的值）
+    Median,
+    /// 最大值
+    Max,
+    /// 众数（适用于分类等整数型属性，并列时取较小值）
+    Mode,
+}
+
+/// 可在体素内归约的数值：浮点按 f64，整数与布尔按 i128 在原值上比较与累加
+trait Reducible: Copy + Send + Sync {
+    /// 均值（整数四舍五入，远离零）
+    fn mean(values: &[Self]) -> Self;
+    fn order(&self, other: &Self) -> Ordering;
+}
+
+impl Reducible for f64 {
+    fn mean(values: &[Self]) -> Self {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+
+    fn order(&self, other: &Self) -> Ordering {
+        self.total_cmp(other)
+    }
+}
+
+impl Reducible for i128 {
+    fn mean(values: &[Self]) -> Self {
+        // u64/i64 之和在 i128 中不会溢出
+        let sum: i128 = values.iter().sum();
+        let count = values.len() as i128;
+        let (quotient, remainder) = (sum / count, sum % count);
+        if 2 * remainder.abs() >= count {
+            quotient + sum.signum()
+        } else {
+            quotient
+        }
+    }
+
+    fn order(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
+impl VoxelAggregation {
+    /// 对体素内的值进行归约，中位数、最大值与众数总是体素内已有的值
+    fn reduce<T: Reducible>(self, values: &[T], indices: &[usize]) -> T {
+        let mut picked: Vec<T> = indices.iter().map(|&idx| values[idx]).collect();
+        match self {
+            VoxelAggregation::Mean => T::mean(&picked),
+            VoxelAggregation::Median => {
+                picked.sort_unstable_by(T::order);
+                picked[(picked.len() - 1) / 2]
+            }
+            VoxelAggregation::Max => picked
+                .into_iter()
+                .max_by(T::order)
+                .expect("体素内至少有一个点"),
+            VoxelAggregation::Mode => {
+                // 排序后统计最长的相等段，并列时保留先出现的较小值
+                picked.sort_unstable_by(T::order);
+                let (mut best, mut best_count) = (picked[0], 0);
+                for run in picked.chunk_by(|a, b| a.order(b) == Ordering::Equal) {
+                    if run.len() > best_count {
+                        (best, best_count) = (run[0], run.len());
+                    }
+                }
+                best
+            }
+        }
+    }
+}
+
+/// 按体素分组并行归约一个通道
+fn reduce_channel<T: Reducible>(
+    values: &[T],
+    groups: &[&[usize]],
+    aggregation: VoxelAggregation,
+) -> Vec<T> {
+    groups
+        .par_iter()
+        .map(|indices| aggregation.reduce(values, indices))
+        .collect()
+}
+
+/// 将归约后的颜色值转换为 u8
+fn reduce_rgb_channel(
+    channel: &tensor::Tensor1,
+    groups: &[&[usize]],
+    aggregation: VoxelAggregation,
+) -> Vec<u8> {
+    let values: Vec<f64> = tensor::tensor1_to_vec(channel)
+        .into_iter()
+        .map(f64::from)
+        .collect();
+    reduce_channel(&values, groups, aggregation)
+        .into_iter()
+        .map(|value| value.round().clamp(0.0, 255.0) as u8)
+        .collect()
+}
+
+impl HighPerformancePointCloud {
+    /// 聚合体素下采样
+    /// aggregation: 默认聚合方式
+    /// overrides: 按通道覆盖聚合方式，键为 "intensity"、"rgb"、"gps_time" 或自定义属性名
+    /// 结果保留原点、坐标系与 LAS 元数据
+    pub fn voxel_downsample_aggregate(
         &self,
         voxel_size: f32,
-        _strategy: Box<dyn DownsampleStrategy>,
+        aggregation: VoxelAggregation,
+        overrides: &HashMap<String, VoxelAggregation>,
     ) -> Result<Self> {
-        // 检查点数是否为0
         if self.point_count() == 0 {
             return Err(PointCloudError::InvalidParameter(
                 "无法对空点云进行下采样".to_string(),
             ));
         }
 
-        if voxel_size <= 0.0 {
+        if !voxel_size.is_finite() || voxel_size <= 0.0 {
             return Err(PointCloudError::InvalidParameter(
-                "voxel_size必须大于0".to_string(),
+                "voxel_size必须为大于0的有限值".to_string(),
             ));
         }
 
-        let xyz_ref = tensor::tensor2_to_vec(self.xyz_ref());
-
-        // 第一步：通过反射分组
-        let voxel_groups = reflect::group_points_by_voxel(&xyz_ref, voxel_size)?;
-
-        // 第二步：对每个体素应用采样策略
-        let mut selected_indices = Vec::new();
-
-        for (_, indices) in voxel_groups.iter() {
-            if !indices.is_empty() {
-                let selected = _strategy.select_representative(indices.clone(), &xyz_ref)?;
-                selected_indices.push(selected);
+        for name in overrides.keys() {
+            let known = match name.as_str() {
+                "intensity" => self.has_intensity(),
+                "rgb" => self.has_rgb(),
+                "gps_time" => self.gps_time_ref().is_some(),
+                _ => self.attributes_ref().contains_key(name),
+            };
+            if !known {
+                return Err(PointCloudError::InvalidParameter(format!(
+                    "聚合覆盖的通道不存在: {}",
+                    name
+                )));
             }
         }
+        let aggregation_of = |name: &str| overrides.get(name).copied().unwrap_or(aggregation);
 
-        // 对索引排序以保持原始顺序
-        selected_indices.sort_unstable();
-
-        // 第三步：构建下采样后的点云
-        let mut new_xyz = Vec::new();
-        let mut new_intensity = None;
-        let mut new_rgb: Option<(Vec<u8>, Vec<u8>, Vec<u8>)> = None;
-        let mut new_attributes = std::collections::HashMap::new();
+        // 第一步：分组，并按体素首点索引排序以保持原始顺序
+        // 体素边界与全局网格对齐，与点云原点无关
+        let (grid_origin, _) = reflect::align_grid_origin(self.origin(), [0.0; 3], voxel_size);
+        let voxel_groups = reflect::group_points_by_voxel(self.xyz_ref(), voxel_size, grid_origin)?;
+        let mut groups: Vec<&[usize]> = voxel_groups.iter().collect();
+        groups.par_sort_unstable_by_key(|indices| indices[0]);
 
-        for &idx in &selected_indices {
-            new_xyz.push(xyz_ref[idx].clone());
-        }
+        // 第二步：体素质心作为新点坐标
+        let points = tensor::tensor2_to_points(self.xyz_ref());
+        let centroids = groups
+            .par_iter()
+            .map(|indices| reflect::compute_voxel_centroid(indices, &points))
+            .collect::<Result<Vec<_>>>()?;
+        let flat: Vec<f32> = centroids.into_iter().flatten().collect();
+        let new_xyz = tensor::tensor2_from_slice(&flat, groups.len(), 3)?;
+        let mut result = HighPerformancePointCloud::from_tensor_xyz(new_xyz)?;
+        *result.origin_mut() = self.origin();
+        result.set_crs(self.crs().cloned());
+        *result.las_metadata_mut() = self.las_metadata_ref().cloned();
 
-        // 下采样intensity
+        // 第三步：归约各通道
         if let Some(intensity) = self.intensity_ref() {
-            let intensity_vec = tensor::tensor1_to_vec(intensity);
-            let mut new_int = Vec::new();
-            for &idx in &selected_indices {
-                new_int.push(intensity_vec[idx]);
-            }
-            new_intensity = Some(new_int);
+            let values: Vec<f64> = tensor::tensor1_to_vec(intensity)
+                .into_iter()
+                .map(f64::from)
+                .collect();
+            let reduced = reduce_channel(&values, &groups, aggregation_of("intensity"));
+            result.set_intensity(reduced.into_iter().map(|v| v as f32).collect())?;
         }
 
-        // 下采样RGB（3个独立通道）
         let (r_ref, g_ref, b_ref) = self.rgb_channels_ref();
         if let (Some(r), Some(g), Some(b)) = (r_ref, g_ref, b_ref) {
-            let r_vec = tensor::tensor1_to_u8_vec(r);
-            let g_vec = tensor::tensor1_to_u8_vec(g);
-            let b_vec = tensor::tensor1_to_u8_vec(b);
-
-            let mut new_r = Vec::new();
-            let mut new_g = Vec::new();
-            let mut new_b = Vec::new();
-            for &idx in &selected_indices {
-                new_r.push(r_vec[idx]);
-                new_g.push(g_vec[idx]);
-                new_b.push(b_vec[idx]);
-            }
-            new_rgb = Some((new_r, new_g, new_b));
+            let rgb_aggregation = aggregation_of("rgb");
+            result.set_rgb(
+                reduce_rgb_channel(r, &groups, rgb_aggregation),
+                reduce_rgb_channel(g, &groups, rgb_aggregation),
+                reduce_rgb_channel(b, &groups, rgb_aggregation),
+            )?;
         }
 
-        // 下采样自定义属性
-        for (name, data) in self.attributes_ref() {
-            let data_vec = tensor::tensor1_to_vec(data);
-            let mut new_data = Vec::new();
-            for &idx in &selected_indices {
-                new_data.push(data_vec[idx]);
-            }
-            new_attributes.insert(name.clone(), new_data);
+        // GPS 时间按 f64 归约，不经 f32 张量以保留精度
+        if let Some(gps_time) = self.gps_time_ref() {
+            *result.gps_time_mut() = Some(reduce_channel(
+                gps_time,
+                &groups,
+                aggregation_of("gps_time"),
+            ));
         }
 
-        // 构造新点云
-        let mut result = HighPerformancePointCloud::from_xyz(new_xyz)?;
-        if let Some(intensity) = new_intensity {
-            result.set_intensity(intensity)?;
+        // 属性按原类型写回，整数在原值上归约（均值四舍五入），多维属性逐分量归约
+        for (name, data) in self.attributes_ref() {
+            let aggregation = aggregation_of(name);
+            let reduced = data.map_components(|values| match values {
+                ComponentValues::Float(values) => {
+                    ComponentValues::Float(reduce_channel(&values, &groups, aggregation))
+                }
+                ComponentValues::Integer(values) => {
+                    ComponentValues::Integer(reduce_channel(&values, &groups, aggregation))
+                }
+            })?;
+            result.set_attribute_data(name.clone(), reduced)?;
         }
-        if let Some((r, g, b)) = new_rgb {
-            result.set_rgb(r, g, b)?;
+
+        Ok(result)
+    }
+}
+
+impl VoxelDownsample for HighPerformancePointCloud {
+    fn voxel_downsample(
+        &self,
+        voxel_size: f32,
+        strategy: Box<dyn DownsampleStrategy>,
+    ) -> Result<Self> {
+        // 检查点数是否为0
+        if self.point_count() == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "无法对空点云进行下采样".to_string(),
+            ));
         }
-        for (name, data) in new_attributes {
-            result.set_attribute(name, data)?;
+
+        if !voxel_size.is_finite() || voxel_size <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "voxel_size必须为大于0的有限值".to_string(),
+            ));
         }
 
-        Ok(result)
+        // 第一步：张量化体素分组
+        // 体素边界与全局网格对齐，与点云原点无关
+        let (grid_origin, _) = reflect::align_grid_origin(self.origin(), [0.0; 3], voxel_size);
+        let voxel_groups = reflect::group_points_by_voxel(self.xyz_ref(), voxel_size, grid_origin)?;
+        let points = tensor::tensor2_to_points(self.xyz_ref());
+
+        // 第二步：并行对每个体素应用采样策略
+        let mut selected_indices = voxel_groups
+            .par_iter()
+            .map(|indices| strategy.select_representative(indices, &points))
+            .collect::<Result<Vec<_>>>()?;
+
+        // 对索引排序以保持原始顺序
+        selected_indices.par_sort_unstable();
+
+        // 第三步：在张量上 gather 所有通道
+        Ok(self.select_points(tensor::indices_to_tensor(&selected_indices)))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::point_cloud::attribute_data::AttributeData;
     use crate::traits::PointCloudCore;
 
     use super::*;
@@ -151,4 +320,160 @@ fn test_voxel_downsample() {
         // 应该得到2个点（每个体素1个代表点）
         assert!(downsampled.point_count() <= pc.point_count());
     }
+
+    #[test]
+    fn test_random_strategy_seeded() {
+        let xyz: Vec<[f32; 3]> = (0..100).map(|i| [i as f32 * 0.01, 0.0, 0.0]).collect();
+        let indices: Vec<usize> = (0..100).collect();
+
+        let a = RandomSampleStrategy::new(Some(7));
+        let b = RandomSampleStrategy::new(Some(7));
+        let picked_a = a.select_representative(&indices, &xyz).unwrap();
+        let picked_b = b.select_representative(&indices, &xyz).unwrap();
+
+        assert_eq!(picked_a, picked_b);
+    }
+
+    #[test]
+    fn test_voxel_aggregate_mean_and_mode() {
+        let xyz = vec![
+            vec![0.1, 0.1, 0.1],
+            vec![0.3, 0.3, 0.3],
+            vec![0.5, 0.5, 0.5],
+            vec![1.5, 1.5, 1.5],
+        ];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        pc.set_intensity(vec![1.0, 2.0, 6.0, 4.0]).unwrap();
+        pc.add_attribute("classification".to_string(), vec![2.0, 5.0, 5.0, 1.0])
+            .unwrap();
+
+        let mut overrides = HashMap::new();
+        overrides.insert("classification".to_string(), VoxelAggregation::Mode);
+        let result = pc
+            .voxel_downsample_aggregate(1.0, VoxelAggregation::Mean, &overrides)
+            .unwrap();
+
+        assert_eq!(result.point_count(), 2);
+        let xyz = tensor::tensor2_to_vec(result.xyz_ref());
+        assert!((xyz[0][0] - 0.3).abs() < 1e-5);
+        let intensity = tensor::tensor1_to_vec(result.intensity_ref().unwrap());
+        assert!((intensity[0] - 3.0).abs() < 1e-5);
+        let classification = result.get_attribute("classification").unwrap();
+        assert_eq!(classification, vec![5.0, 1.0]);
+    }
+
+    #[test]
+    fn test_voxel_aggregate_keeps_attribute_type() {
+        let xyz = vec![vec![0.1, 0.1, 0.1], vec![0.2, 0.2, 0.2]];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        pc.set_attribute_data("label".to_string(), AttributeData::from_vec(vec![3u16, 4]))
+            .unwrap();
+
+        let result = pc
+            .voxel_downsample_aggregate(1.0, VoxelAggregation::Mean, &HashMap::new())
+            .unwrap();
+        match result.attribute("label").unwrap() {
+            AttributeData::U16(values) => assert_eq!(values, &vec![4]),
+            _ => panic!("类型应保持为 uint16"),
+        }
+    }
+
+    #[test]
+    fn test_voxel_aggregate_vector_attribute() {
+        let xyz = vec![
+            vec![0.1, 0.1, 0.1],
+            vec![0.2, 0.2, 0.2],
+            vec![2.5, 2.5, 2.5],
+        ];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let flat = [1.0, 10.0, 3.0, 20.0, 5.0, 30.0];
+        let embedding = tensor::tensor2_from_slice(&flat, 3, 2).unwrap();
+        pc.set_attribute_data("embedding".to_string(), AttributeData::Vector(embedding))
+            .unwrap();
+
+        let result = pc
+            .voxel_downsample_aggregate(1.0, VoxelAggregation::Mean, &HashMap::new())
+            .unwrap();
+        let embedding = result.attribute("embedding").unwrap();
+        assert_eq!(embedding.width(), 2);
+        assert_eq!(embedding.to_f32_vec(), vec![2.0, 15.0, 5.0, 30.0]);
+    }
+
+    #[test]
+    fn test_voxel_grid_aligned_to_global() {
+        // 原点 500005.25 不是 0.5 的整数倍，体素边界仍应位于 0.5 的整数倍上
+        let points = [[500_005.3, 0.0, 0.0], [500_005.2, 0.0, 0.0]];
+        let pc =
+            HighPerformancePointCloud::from_global_points(&points, Some([500_005.25, 0.0, 0.0]))
+                .unwrap();
+        let result = pc
+            .voxel_downsample_aggregate(0.5, VoxelAggregation::Mean, &HashMap::new())
+            .unwrap();
+        // 两点同属全局体素 [500005.0, 500005.5)
+        assert_eq!(result.point_count(), 1);
+    }
+
+    #[test]
+    fn test_voxel_aggregate_keeps_gps_time_and_las_metadata() {
+        let xyz = vec![
+            vec![0.1, 0.1, 0.1],
+            vec![0.2, 0.2, 0.2],
+            vec![2.5, 2.5, 2.5],
+        ];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        pc.set_gps_time(vec![1.3e9, 1.3e9 + 0.5, 1.3e9 + 2.0])
+            .unwrap();
+        *pc.las_metadata_mut() = Some(Default::default());
+
+        let result = pc
+            .voxel_downsample_aggregate(1.0, VoxelAggregation::Mean, &HashMap::new())
+            .unwrap();
+        assert_eq!(result.gps_time().unwrap(), &[1.3e9 + 0.25, 1.3e9 + 2.0]);
+        assert!(result.las_metadata_ref().is_some());
+    }
+
+    #[test]
+    fn test_voxel_aggregate_median_max() {
+        let values = vec![4.0, 1.0, 3.0, 2.0];
+        let indices = vec![0, 1, 2, 3];
+
+        assert_eq!(VoxelAggregation::Median.reduce(&values, &indices), 2.0);
+        assert_eq!(VoxelAggregation::Max.reduce(&values, &indices), 4.0);
+    }
+
+    #[test]
+    fn test_voxel_aggregate_large_integers() {
+        // 超过 2^53 的整数经 f64 会被舍入，需在原值上归约
+        let xyz = vec![
+            vec![0.1, 0.1, 0.1],
+            vec![0.2, 0.2, 0.2],
+            vec![0.3, 0.3, 0.3],
+        ];
+        let mut pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let ids = vec![u64::MAX, u64::MAX - 1, u64::MAX - 1];
+        pc.set_attribute_data("id".to_string(), AttributeData::from_vec(ids))
+            .unwrap();
+
+        let expected = [
+            (VoxelAggregation::Mean, u64::MAX - 1),
+            (VoxelAggregation::Median, u64::MAX - 1),
+            (VoxelAggregation::Max, u64::MAX),
+            (VoxelAggregation::Mode, u64::MAX - 1),
+        ];
+        for (aggregation, value) in expected {
+            let result = pc
+                .voxel_downsample_aggregate(1.0, aggregation, &HashMap::new())
+                .unwrap();
+            match result.attribute("id").unwrap() {
+                AttributeData::U64(values) => assert_eq!(values, &vec![value]),
+                _ => panic!("类型应保持为 uint64"),
+            }
+        }
+
+        let values: Vec<i128> = vec![i64::MIN.into(), (i64::MIN + 1).into()];
+        assert_eq!(
+            VoxelAggregation::Mean.reduce(&values, &[0, 1]),
+            i128::from(i64::MIN)
+        );
+    }
 }
@@ -0,0 +1,292 @@
+/// 体素网格：持久化体素索引、计数/质心/协方差统计、点-体素查找与邻域查询
+use crate::point_cloud::core::HighPerformancePointCloud;
+use crate::point_cloud::crs::Crs;
+use crate::traits::{PointCloudCore, PointCloudProperties};
+use crate::utils::error::{PointCloudError, Result};
+use crate::utils::reflect::{self, VoxelGroups};
+use crate::utils::tensor;
+use rayon::prelude::*;
+
+/// 体素网格
+/// 点索引指向构建时的点云，点云修改后需要重新构建
+/// 网格在点云的局部坐标系中划分，cloud_origin 为点云的局部坐标原点
+/// origin 为对齐到全局网格的局部网格节点，shift 为该节点相对网格原点的体素坐标
+/// 对外的网格原点、体素坐标、质心与查询位置均为全局坐标
+pub struct VoxelGrid {
+    voxel_size: f32,
+    origin: [f32; 3],
+    shift: [i64; 3],
+    grid_origin: [f64; 3],
+    cloud_origin: [f64; 3],
+    crs: Option<Crs>,
+    groups: VoxelGroups,
+    centroids: Vec<[f32; 3]>,
+    covariances: Vec<[[f32; 3]; 3]>,
+}
+
+impl VoxelGrid {
+    /// 从点云构建体素网格
+    /// origin: 网格原点的全局坐标，None 时为 (0,0,0)（与 voxel_downsample 的体素划分一致）
+    pub fn new(
+        cloud: &HighPerformancePointCloud,
+        voxel_size: f32,
+        origin: Option<[f64; 3]>,
+    ) -> Result<Self> {
+        if cloud.point_count() == 0 {
+            return Err(PointCloudError::InvalidParameter(
+                "无法对空点云构建体素网格".to_string(),
+            ));
+        }
+        if !voxel_size.is_finite() || voxel_size <= 0.0 {
+            return Err(PointCloudError::InvalidParameter(
+                "voxel_size必须为大于0的有限值".to_string(),
+            ));
+        }
+
+        let cloud_origin = cloud.origin();
+        let grid_origin = origin.unwrap_or([0.0; 3]);
+        let (origin, shift) = reflect::align_grid_origin(cloud_origin, grid_origin, voxel_size);
+        let groups = reflect::group_points_by_voxel(cloud.xyz_ref(), voxel_size, origin)?;
+        let points = tensor::tensor2_to_points(cloud.xyz_ref());
+
+        let (centroids, covariances) = groups
+            .par_iter()
+            .map(|indices| voxel_statistics(indices, &points))
+            .unzip();
+
+        Ok(Self {
+            voxel_size,
+            origin,
+            shift,
+            grid_origin,
+            cloud_origin,
+            crs: cloud.crs().cloned(),
+            groups,
+            centroids,
+            covariances,
+        })
+    }
+
+    /// 体素边长
+    pub fn voxel_size(&self) -> f32 {
+        self.voxel_size
+    }
+
+    /// 网格原点（全局坐标）
+    pub fn origin(&self) -> [f64; 3] {
+        self.grid_origin
+    }
+
+    /// 非空体素数量
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// 构建时的点数
+    pub fn point_count(&self) -> usize {
+        self.groups.point_voxel.len()
+    }
+
+    /// 体素内的点索引（升序）
+    pub fn point_indices(&self, voxel: usize) -> &[usize] {
+        self.groups.group(voxel)
+    }
+
+    /// 每个体素的点数
+    pub fn counts(&self) -> Vec<usize> {
+        self.groups.iter().map(|indices| indices.len()).collect()
+    }
+
+    /// 每个体素的点质心（全局坐标）
+    pub fn centroids(&self) -> Vec<[f64; 3]> {
+        self.centroids.iter().map(|&c| self.to_global(c)).collect()
+    }
+
+    /// 每个体素的点协方差（无偏估计，单点体素为零矩阵）
+    pub fn covariances(&self) -> &[[[f32; 3]; 3]] {
+        &self.covariances
+    }
+
+    /// 体素的整数坐标（相对网格原点）
+    pub fn voxel_coord(&self, voxel: usize) -> [i64; 3] {
+        let local = self.groups.unpack(self.groups.keys[voxel]);
+        std::array::from_fn(|axis| local[axis] + self.shift[axis])
+    }
+
+    /// 体素中心的空间坐标（局部坐标）
+    pub fn voxel_center(&self, voxel: usize) -> [f32; 3] {
+        let coord = self.groups.unpack(self.groups.keys[voxel]);
+        std::array::from_fn(|axis| self.origin[axis] + (coord[axis] as f32 + 0.5) * self.voxel_size)
+    }
+
+    /// 按整数坐标（相对网格原点）查找体素
+    pub fn find_voxel(&self, coord: [i64; 3]) -> Option<usize> {
+        let local = std::array::from_fn(|axis| coord[axis] - self.shift[axis]);
+        self.groups
+            .pack(local)
+            .and_then(|key| self.groups.find(key))
+    }
+
+    /// 每个构建点所属的体素
+    pub fn point_voxels(&self) -> &[usize] {
+        &self.groups.point_voxel
+    }
+
+    /// 查找任意全局位置所在的体素（体素为空时返回 None）
+    /// 先按 f64 减去点云原点，再与构建时相同地按 f32 局部坐标划分
+    pub fn voxel_at(&self, position: [f64; 3]) -> Option<usize> {
+        let coord = std::array::from_fn(|axis| {
+            let local = (position[axis] - self.cloud_origin[axis]) as f32;
+            ((local - self.origin[axis]) / self.voxel_size).floor() as i64 + self.shift[axis]
+        });
+        self.find_voxel(coord)
+    }
+
+    /// 批量查找全局位置所在的体素
+    pub fn lookup(&self, positions: &[[f64; 3]]) -> Vec<Option<usize>> {
+        positions
+            .par_iter()
+            .map(|&position| self.voxel_at(position))
+            .collect()
+    }
+
+    /// 查询邻域内的非空体素（切比雪夫距离不超过 radius，不含自身）
+    /// radius=1 时为 26 邻域
+    pub fn neighbors(&self, voxel: usize, radius: u32) -> Vec<usize> {
+        let center = self.voxel_coord(voxel);
+        let r = radius as i64;
+        let mut result = Vec::new();
+        for dx in -r..=r {
+            for dy in -r..=r {
+                for dz in -r..=r {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    let coord = [center[0] + dx, center[1] + dy, center[2] + dz];
+                    if let Some(neighbor) = self.find_voxel(coord) {
+                        result.push(neighbor);
+                    }
+                }
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// 局部坐标转换为全局坐标
+    fn to_global(&self, local: [f32; 3]) -> [f64; 3] {
+        std::array::from_fn(|axis| local[axis] as f64 + self.cloud_origin[axis])
+    }
+
+    /// 导出体素中心为点云，附带 "point_count" 属性，沿用构建点云的坐标系
+    pub fn to_point_cloud(&self) -> Result<HighPerformancePointCloud> {
+        let flat: Vec<f32> = (0..self.len())
+            .flat_map(|voxel| self.voxel_center(voxel))
+            .collect();
+        let xyz = tensor::tensor2_from_slice(&flat, self.len(), 3)?;
+        let mut cloud = HighPerformancePointCloud::from_tensor_xyz(xyz)?;
+        *cloud.origin_mut() = self.cloud_origin;
+        cloud.set_crs(self.crs.clone());
+        let counts = self.counts().into_iter().map(|c| c as f32).collect();
+        cloud.set_attribute("point_count".to_string(), counts)?;
+        Ok(cloud)
+    }
+}
+
+/// 计算体素内点的质心与协方差
+fn voxel_statistics(indices: &[usize], points: &[[f32; 3]]) -> ([f32; 3], [[f32; 3]; 3]) {
+    let count = indices.len() as f64;
+    let mut mean = [0.0f64; 3];
+    for &idx in indices {
+        for (sum, &value) in mean.iter_mut().zip(&points[idx]) {
+            *sum += value as f64;
+        }
+    }
+    for value in mean.iter_mut() {
+        *value /= count;
+    }
+
+    let mut cov = [[0.0f64; 3]; 3];
+    for &idx in indices {
+        let d: [f64; 3] = std::array::from_fn(|axis| points[idx][axis] as f64 - mean[axis]);
+        for (row, &di) in cov.iter_mut().zip(&d) {
+            for (cell, &dj) in row.iter_mut().zip(&d) {
+                *cell += di * dj;
+            }
+        }
+    }
+    let denom = if indices.len() > 1 { count - 1.0 } else { 1.0 };
+
+    (
+        mean.map(|value| value as f32),
+        cov.map(|row| row.map(|value| (value / denom) as f32)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voxel_grid_statistics() {
+        let xyz = vec![
+            vec![0.2, 0.5, 0.5],
+            vec![0.4, 0.5, 0.5],
+            vec![1.5, 0.5, 0.5],
+            vec![5.5, 5.5, 5.5],
+        ];
+        let pc = HighPerformancePointCloud::from_xyz(xyz).unwrap();
+        let grid = VoxelGrid::new(&pc, 1.0, None).unwrap();
+
+        assert_eq!(grid.len(), 3);
+        let voxel = grid.point_voxels()[0];
+        assert_eq!(grid.point_indices(voxel), &[0, 1]);
+        assert!((grid.centroids()[voxel][0] - 0.3).abs() < 1e-5);
+        assert!((grid.covariances()[voxel][0][0] - 0.02).abs() < 1e-5);
+        assert_eq!(grid.voxel_at([0.9, 0.1, 0.1]), Some(voxel));
+        assert_eq!(grid.voxel_at([2.5, 0.5, 0.5]), None);
+
+        let neighbors = grid.neighbors(voxel, 1);
+        assert_eq!(neighbors, vec![grid.point_voxels()[2]]);
+
+        let centers = grid.to_point_cloud().unwrap();
+        assert_eq!(centers.point_count(), 3);
+
+        assert!(VoxelGrid::new(&pc, f32::NAN, None).is_err());
+        assert!(VoxelGrid::new(&pc, f32::INFINITY, None).is_err());
+    }
+
+    #[test]
+    fn test_voxel_grid_global_coordinates() {
+        // UTM 量级的点云，质心与查询位置均为全局坐标
+        let points = [
+            [500_000.25, 4_000_000.5, 10.5],
+            [500_000.75, 4_000_000.5, 10.5],
+            [500_003.5, 4_000_000.5, 10.5],
+        ];
+        let mut pc = HighPerformancePointCloud::from_global_points(&points, None).unwrap();
+        pc.set_crs(Some(Crs::Epsg(32633)));
+        let grid = VoxelGrid::new(&pc, 1.0, Some([500_000.0, 4_000_000.0, 0.0])).unwrap();
+
+        assert_eq!(grid.origin(), [500_000.0, 4_000_000.0, 0.0]);
+        let voxel = grid.point_voxels()[0];
+        let centroid = grid.centroids()[voxel];
+        assert!((centroid[0] - 500_000.5).abs() < 1e-3);
+        assert!((centroid[1] - 4_000_000.5).abs() < 1e-3);
+        assert_eq!(grid.voxel_at([500_000.9, 4_000_000.1, 10.0]), Some(voxel));
+        assert_eq!(grid.voxel_at([500_001.1, 4_000_000.1, 10.0]), None);
+        assert_eq!(grid.voxel_coord(voxel), [0, 0, 10]);
+        assert_eq!(grid.find_voxel([0, 0, 10]), Some(voxel));
+        assert_eq!(
+            grid.to_point_cloud().unwrap().crs(),
+            Some(&Crs::Epsg(32633))
+        );
+
+        // 默认网格原点为 (0,0,0)，点云原点不是体素边长整数倍时边界仍对齐全局网格
+        let grid = VoxelGrid::new(&pc, 0.3, None).unwrap();
+        assert_eq!(grid.origin(), [0.0; 3]);
+        let coord = grid.voxel_coord(grid.point_voxels()[0]);
+        let expected = (500_000.25 / 0.3f32 as f64).floor() as i64;
+        assert_eq!(coord[0], expected);
+    }
+}
@@ -7,7 +7,7 @@ pub trait DownsampleStrategy: Send + Sync {
     /// indices: 体素内点的索引
     /// xyz: 全部点云的XYZ坐标
     /// 返回选中点的索引
-    fn select_representative(&self, indices: Vec<usize>, xyz: &[Vec<f32>]) -> Result<usize>;
+    fn select_representative(&self, indices: &[usize], xyz: &[[f32; 3]]) -> Result<usize>;
 }
 
 /// 体素下采样Trait：定义下采样核心接口
@@ -4,8 +4,8 @@
 
 /// 点云核心能力Trait
 pub trait PointCloudCore {
-    /// 获取点云的XYZ坐标（形状[M,3]）
-    fn get_xyz(&self) -> Vec<Vec<f32>>;
+    /// 获取点云的XYZ全局坐标（局部坐标加原点，按 f64 计算）
+    fn get_xyz(&self) -> Vec<[f64; 3]>;
 
     /// 获取点数
     fn point_count(&self) -> usize;
@@ -1,69 +1,203 @@
-use crate::utils::error::Result;
+use crate::utils::error::{PointCloudError, Result};
 /// 反射工具：实现下采样的批量grouping逻辑
-use std::collections::HashMap;
+/// 体素坐标在 burn 张量上计算，打包为 u64 键后并行排序分组
+use crate::utils::tensor::{Backend, Tensor2};
+use burn::tensor::{Int, Tensor};
+use rayon::prelude::*;
 
-/// 通过反射和量化实现体素分组
-/// 将点云按照给定的voxel_size进行分组
+/// 体素分组结果（CSR 布局）
+/// 第 i 个体素的点索引为 indices[offsets[i]..offsets[i + 1]]，组内索引升序
+pub struct VoxelGroups {
+    /// 每个体素的打包键（升序）
+    pub keys: Vec<u64>,
+    /// 按体素排序后的点索引
+    pub indices: Vec<usize>,
+    /// 每个体素在 indices 中的起始偏移（长度为体素数+1）
+    pub offsets: Vec<usize>,
+    /// 每个点所属的体素序号
+    pub point_voxel: Vec<usize>,
+    /// 体素坐标的最小值（打包时的偏移）
+    pub min_coord: [i64; 3],
+    /// 各轴占用的位数
+    pub bits: [u32; 3],
+}
+
+impl VoxelGroups {
+    /// 体素数量
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// 第 i 个体素内的点索引
+    pub fn group(&self, voxel: usize) -> &[usize] {
+        &self.indices[self.offsets[voxel]..self.offsets[voxel + 1]]
+    }
+
+    /// 按体素顺序遍历点索引
+    pub fn iter(&self) -> impl Iterator<Item = &[usize]> {
+        (0..self.len()).map(move |voxel| self.group(voxel))
+    }
+
+    /// 并行遍历体素内的点索引
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = &[usize]> {
+        (0..self.len())
+            .into_par_iter()
+            .map(move |voxel| self.group(voxel))
+    }
+
+    /// 将整数体素坐标打包为键，超出范围时返回 None
+    pub fn pack(&self, coord: [i64; 3]) -> Option<u64> {
+        let mut key = 0u64;
+        for ((&value, &min), &bits) in coord.iter().zip(&self.min_coord).zip(&self.bits) {
+            let offset = value - min;
+            if offset < 0 || offset >> bits != 0 {
+                return None;
+            }
+            key = (key << bits) | offset as u64;
+        }
+        Some(key)
+    }
+
+    /// 将键解包为整数体素坐标
+    pub fn unpack(&self, key: u64) -> [i64; 3] {
+        let mut coord = [0i64; 3];
+        let mut rest = key;
+        for axis in (0..3).rev() {
+            let mask = (1u64 << self.bits[axis]) - 1;
+            coord[axis] = (rest & mask) as i64 + self.min_coord[axis];
+            rest >>= self.bits[axis];
+        }
+        coord
+    }
+
+    /// 查找键对应的体素序号
+    pub fn find(&self, key: u64) -> Option<usize> {
+        self.keys.binary_search(&key).ok()
+    }
+}
+
+/// 在张量上计算每个点的整数体素坐标，返回行优先的 [N*3] 数组
+pub fn voxel_coordinates(xyz: &Tensor2, voxel_size: f32, origin: [f32; 3]) -> Vec<i64> {
+    let device = xyz.device();
+    let origin = Tensor::<Backend, 1>::from_floats(origin, &device).reshape([1, 3]);
+    let coords: Tensor<Backend, 2, Int> =
+        xyz.clone().sub(origin).div_scalar(voxel_size).floor().int();
+    coords.into_data().iter::<i64>().collect()
+}
+
+/// 将全局网格原点对齐到点云的局部坐标系
+/// 按 f64 把点云原点相对网格原点的偏移取整为 voxel_size 的整数倍，体素边界因此与全局网格对齐
+/// 返回局部坐标中的网格节点（各分量在 (-voxel_size, 0] 内）及该节点相对网格原点的体素坐标
+pub fn align_grid_origin(
+    cloud_origin: [f64; 3],
+    grid_origin: [f64; 3],
+    voxel_size: f32,
+) -> ([f32; 3], [i64; 3]) {
+    let size = voxel_size as f64;
+    let steps: [f64; 3] =
+        std::array::from_fn(|axis| ((cloud_origin[axis] - grid_origin[axis]) / size).floor());
+    let local = std::array::from_fn(|axis| {
+        (grid_origin[axis] + steps[axis] * size - cloud_origin[axis]) as f32
+    });
+    (local, steps.map(|step| step as i64))
+}
+
+/// 通过量化实现体素分组
+/// 将点云按照给定的voxel_size与原点进行分组
 pub fn group_points_by_voxel(
-    xyz: &[Vec<f32>],
+    xyz: &Tensor2,
     voxel_size: f32,
-) -> Result<HashMap<String, Vec<usize>>> {
-    if xyz.is_empty() {
+    origin: [f32; 3],
+) -> Result<VoxelGroups> {
+    let n = xyz.dims()[0];
+    if n == 0 {
         return Err("点云数据为空".into());
     }
 
-    if voxel_size <= 0.0 {
-        return Err("voxel_size必须大于0".into());
+    if !voxel_size.is_finite() || voxel_size <= 0.0 {
+        return Err("voxel_size必须为大于0的有限值".into());
     }
 
-    let mut voxel_groups: HashMap<String, Vec<usize>> = HashMap::new();
-
-    for (idx, point) in xyz.iter().enumerate() {
-        if point.len() != 3 {
-            return Err(format!("点{}的维度不是3", idx).into());
+    // 第一步：体素坐标，并按最小值平移为非负偏移
+    let coords = voxel_coordinates(xyz, voxel_size, origin);
+    let mut min_coord = [i64::MAX; 3];
+    let mut max_coord = [i64::MIN; 3];
+    for point in coords.chunks_exact(3) {
+        for (axis, &value) in point.iter().enumerate() {
+            min_coord[axis] = min_coord[axis].min(value);
+            max_coord[axis] = max_coord[axis].max(value);
         }
+    }
+
+    let bits: [u32; 3] = std::array::from_fn(|axis| {
+        let extent = (max_coord[axis] - min_coord[axis]) as u64;
+        (64 - extent.leading_zeros()).max(1)
+    });
+    if bits.iter().sum::<u32>() > 64 {
+        return Err(PointCloudError::InvalidParameter(
+            "体素坐标范围过大，无法打包为64位键，请增大voxel_size".to_string(),
+        ));
+    }
 
-        // 计算点所属的体素坐标
-        let voxel_x = (point[0] / voxel_size).floor() as i32;
-        let voxel_y = (point[1] / voxel_size).floor() as i32;
-        let voxel_z = (point[2] / voxel_size).floor() as i32;
+    let mut groups = VoxelGroups {
+        keys: Vec::new(),
+        indices: Vec::with_capacity(n),
+        offsets: Vec::new(),
+        point_voxel: vec![0; n],
+        min_coord,
+        bits,
+    };
 
-        // 生成体素键
-        let voxel_key = format!("{}_{}_{}", voxel_x, voxel_y, voxel_z);
+    // 第二步：并行打包键并排序（键相同时按点索引升序）
+    let mut pairs: Vec<(u64, usize)> = coords
+        .par_chunks_exact(3)
+        .enumerate()
+        .map(|(idx, point)| {
+            let key = groups
+                .pack([point[0], point[1], point[2]])
+                .expect("体素坐标已在打包范围内");
+            (key, idx)
+        })
+        .collect();
+    pairs.par_sort_unstable();
 
-        // 将点索引添加到对应体素组
-        voxel_groups.entry(voxel_key).or_default().push(idx);
+    // 第三步：扫描有序键生成 CSR 分组
+    for (pos, &(key, idx)) in pairs.iter().enumerate() {
+        if groups.keys.last() != Some(&key) {
+            groups.keys.push(key);
+            groups.offsets.push(pos);
+        }
+        groups.point_voxel[idx] = groups.keys.len() - 1;
+        groups.indices.push(idx);
     }
+    groups.offsets.push(n);
 
-    Ok(voxel_groups)
+    Ok(groups)
 }
 
 /// 获取体素内点的平均坐标（用于CENTROID策略）
-pub fn compute_voxel_centroid(indices: &[usize], xyz: &[Vec<f32>]) -> Result<Vec<f32>> {
+pub fn compute_voxel_centroid(indices: &[usize], xyz: &[[f32; 3]]) -> Result<[f32; 3]> {
     if indices.is_empty() {
         return Err("体素内无点".into());
     }
 
-    let mut centroid = vec![0.0; 3];
+    let mut centroid = [0.0f64; 3];
 
     for &idx in indices {
-        if idx >= xyz.len() {
-            return Err(format!("点索引{}超出范围", idx).into());
-        }
-        for j in 0..3 {
-            centroid[j] += xyz[idx][j];
+        let point = xyz
+            .get(idx)
+            .ok_or_else(|| PointCloudError::from(format!("点索引{}超出范围", idx)))?;
+        for (sum, &value) in centroid.iter_mut().zip(point) {
+            *sum += value as f64;
         }
     }
 
-    for value in centroid.iter_mut() {
-        *value /= indices.len() as f32;
-    }
-
-    Ok(centroid)
+    let count = indices.len() as f64;
+    Ok(centroid.map(|value| (value / count) as f32))
 }
 
 /// 找到最接近体素中心的点
-pub fn find_closest_to_centroid(indices: &[usize], xyz: &[Vec<f32>]) -> Result<usize> {
+pub fn find_closest_to_centroid(indices: &[usize], xyz: &[[f32; 3]]) -> Result<usize> {
     if indices.is_empty() {
         return Err("体素内无点".into());
     }
@@ -74,10 +208,11 @@ pub fn find_closest_to_centroid(indices: &[usize], xyz: &[Vec<f32>]) -> Result<u
 
     for &idx in indices {
         let point = &xyz[idx];
-        let dist = (0..3)
-            .map(|i| (point[i] - centroid[i]).powi(2))
-            .sum::<f32>()
-            .sqrt();
+        let dist: f32 = point
+            .iter()
+            .zip(&centroid)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum();
 
         if dist < min_dist {
             min_dist = dist;
@@ -87,3 +222,41 @@ pub fn find_closest_to_centroid(indices: &[usize], xyz: &[Vec<f32>]) -> Result<u
 
     Ok(closest_idx)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tensor;
+
+    #[test]
+    fn test_group_points_by_voxel() {
+        let xyz = tensor::xyz_to_tensor(vec![
+            vec![0.1, 0.1, 0.1],
+            vec![-0.5, 0.2, 0.2],
+            vec![0.9, 0.9, 0.9],
+            vec![-0.2, 0.3, 0.1],
+        ])
+        .unwrap();
+        let groups = group_points_by_voxel(&xyz, 1.0, [0.0; 3]).unwrap();
+
+        assert_eq!(groups.len(), 2);
+        let mut sets: Vec<Vec<usize>> = groups.iter().map(|g| g.to_vec()).collect();
+        sets.sort();
+        assert_eq!(sets, vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(groups.point_voxel[0], groups.point_voxel[2]);
+
+        let key = groups.keys[groups.point_voxel[1]];
+        assert_eq!(groups.unpack(key), [-1, 0, 0]);
+        assert_eq!(groups.pack([-1, 0, 0]), Some(key));
+    }
+
+    #[test]
+    fn test_align_grid_origin() {
+        let (local, shift) = align_grid_origin([500_005.25, 0.0, -1.2], [0.0; 3], 0.5);
+        // 500005.0 与 -1.5 为不超过点云原点的最近网格节点
+        assert_eq!(local[0], -0.25);
+        assert_eq!(local[1], 0.0);
+        assert!((local[2] + 0.3).abs() < 1e-6);
+        assert_eq!(shift, [1_000_010, 0, -3]);
+    }
+}
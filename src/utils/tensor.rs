@@ -1,11 +1,14 @@
 use crate::utils::error::{PointCloudError, Result};
-use burn::backend::ndarray::NdArrayDevice;
+use burn::backend::ir::HandleKind;
+use burn::backend::ndarray::{NdArrayDevice, NdArrayTensor};
+use burn::backend::router::duo::Handle;
+use burn::backend::router::{DirectByteChannel, RunnerChannel};
 use burn::backend::wgpu::WgpuDevice;
 /// burn张量工具函数：类型转换、维度检查等
 use burn::backend::{NdArray, Router, Wgpu};
 use burn::prelude::DeviceOps;
 use burn::tensor::backend::Backend as BackendTrait;
-use burn::tensor::{Tensor, TensorData};
+use burn::tensor::{Int, Tensor, TensorData, TensorPrimitive};
 
 // Router backend: automatically selects GPU (Wgpu) or CPU (NdArray) at runtime
 pub type Backend = Router<(Wgpu, NdArray)>;
@@ -54,6 +57,8 @@ pub fn empty_xyz() -> Tensor2 {
 
 pub type Tensor1 = Tensor<Backend, 1>;
 pub type Tensor2 = Tensor<Backend, 2>;
+pub type Tensor3 = Tensor<Backend, 3>;
+pub type IntTensor1 = Tensor<Backend, 1, Int>;
 // ============ 从 slice 创建 Tensor（避免 Vec 复制）============
 
 /// 从 &[f32] 创建 Tensor1
@@ -78,6 +83,42 @@ pub fn tensor2_from_slice(data: &[f32], rows: usize, cols: usize) -> Result<Tens
     Ok(tensor)
 }
 
+/// 从点索引创建 Int 索引张量（用于 select/select_assign）
+pub fn indices_to_tensor(indices: &[usize]) -> IntTensor1 {
+    let data: Vec<i64> = indices.iter().map(|&i| i as i64).collect();
+    let len = data.len();
+    Tensor::<Backend, 1, Int>::from_data(TensorData::new(data, [len]), &default_device())
+}
+
+/// Int Tensor1 -> Vec<i64>
+pub fn int_tensor1_to_vec(tensor: &IntTensor1) -> Vec<i64> {
+    let data: TensorData = tensor.to_data();
+    data.iter::<i64>().collect()
+}
+
+/// Bool Tensor1 -> 为 true 的位置索引
+pub fn mask_to_indices(mask: Tensor<Backend, 1, burn::tensor::Bool>) -> Vec<usize> {
+    let data: TensorData = mask.into_data();
+    data.iter::<bool>()
+        .enumerate()
+        .filter_map(|(idx, keep)| keep.then_some(idx))
+        .collect()
+}
+
+/// 取 XYZ 的某一列作为 Tensor1（0=x, 1=y, 2=z）
+pub fn xyz_column(xyz: &Tensor2, col: usize) -> Tensor1 {
+    tensor2_column(xyz, col)
+}
+
+/// 取 Tensor2 的某一列作为 Tensor1
+pub fn tensor2_column(tensor: &Tensor2, col: usize) -> Tensor1 {
+    let rows = tensor2_rows(tensor);
+    tensor
+        .clone()
+        .slice([0..rows, col..col + 1])
+        .reshape([rows])
+}
+
 // /// 从 flat &[f32] 创建 XYZ Tensor2，形状为 [N, 3]
 // pub fn xyz_from_slice(data: &[f32]) -> Result<Tensor2> {
 //     if !data.len().is_multiple_of(3) {
@@ -160,7 +201,7 @@ pub fn validate_attribute_shape(attribute: &[f32], point_count: usize) -> Result
 }
 
 /// 检查矩阵维度（3x3或4x4）
-pub fn validate_matrix_shape(matrix: &[Vec<f32>]) -> Result<(usize, usize)> {
+pub fn validate_matrix_shape<T>(matrix: &[Vec<T>]) -> Result<(usize, usize)> {
     if matrix.is_empty() {
         return Err(PointCloudError::MatrixError("矩阵为空".to_string()));
     }
@@ -231,26 +272,71 @@ pub fn tensor2_cols(tensor: &Tensor2) -> usize {
     shape.dims[1]
 }
 
+/// 回读张量数据：into_vec 直接接管 TensorData 的缓冲区，不再复制一次
 pub fn tensor1_to_vec(tensor: &Tensor1) -> Vec<f32> {
-    let data: TensorData = tensor.to_data();
-    data.to_vec::<f32>()
+    tensor
+        .clone()
+        .into_data()
+        .into_vec::<f32>()
         .expect("Failed to convert tensor data to Vec<f32>")
 }
 
 pub fn tensor2_to_vec(tensor: &Tensor2) -> Vec<Vec<f32>> {
-    let data: TensorData = tensor.to_data();
-    let shape = &data.shape;
-    let rows = shape[0];
-    let cols = shape[1];
-    let flat: Vec<f32> = data
+    let cols = tensor2_cols(tensor);
+    tensor2_to_flat(tensor)
+        .chunks(cols.max(1))
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
+
+/// Tensor2 按行展开为 Vec<f32>
+pub fn tensor2_to_flat(tensor: &Tensor2) -> Vec<f32> {
+    tensor
+        .clone()
+        .into_data()
+        .into_vec::<f32>()
+        .expect("Failed to convert tensor data to Vec<f32>")
+}
+
+/// 从行优先的 TensorData 创建 Tensor2（float32 数据直接移交，其他类型先转换）
+pub fn tensor2_from_data(data: TensorData) -> Result<Tensor2> {
+    if data.shape.len() != 2 {
+        return Err(PointCloudError::TensorShapeError(format!(
+            "期望二维数据，实际为{}维",
+            data.shape.len()
+        )));
+    }
+    Ok(Tensor::<Backend, 2>::from_data(
+        data.convert::<f32>(),
+        &default_device(),
+    ))
+}
+
+/// 一次性回读 [N,3] 张量为定长点数组（避免逐行分配 Vec）
+pub fn tensor2_to_points(tensor: &Tensor2) -> Vec<[f32; 3]> {
+    let flat: Vec<f32> = tensor
+        .to_data()
         .to_vec::<f32>()
         .expect("Failed to convert tensor data to Vec<f32>");
-    flat.chunks(cols)
-        .take(rows)
-        .map(|chunk| chunk.to_vec())
+    flat.chunks_exact(3)
+        .map(|chunk| [chunk[0], chunk[1], chunk[2]])
         .collect()
 }
 
+/// NdArray 后端上浮点张量的底层存储：只增加引用计数，不复制数据；其他后端返回 None
+pub fn ndarray_storage<const D: usize>(tensor: &Tensor<Backend, D>) -> Option<NdArrayTensor> {
+    let TensorPrimitive::Float(primitive) = tensor.clone().into_primitive() else {
+        return None;
+    };
+    // 克隆后引用计数至少为 2，按只读状态取句柄，路由器中的原句柄保留
+    let client = primitive.client.clone();
+    let ir = primitive.into_ir();
+    match <DirectByteChannel<(Wgpu, NdArray)> as RunnerChannel>::get_tensor_handle(&ir, &client) {
+        Handle::B2(HandleKind::Float(storage)) => Some(storage),
+        _ => None,
+    }
+}
+
 pub fn matrix_to_tensor(matrix: Vec<Vec<f32>>) -> Result<Tensor2> {
     let (rows, cols) = validate_matrix_shape(&matrix)?;
     let tensor = vec2_to_tensor(matrix)?;
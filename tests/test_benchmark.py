@@ -312,6 +312,59 @@ def test_voxel_downsample_performance(
         assert "d2" in downsampled.attribute_names()
 
 
+class TestBenchmarkVoxelGrouping:
+    """体素分组性能基准测试（各下采样策略共用同一张量化分组）"""
+
+    @pytest.mark.parametrize(
+        "num_points",
+        [
+            pytest.param(10_000_000, id="10M"),
+            pytest.param(100_000_000, id="100M", marks=pytest.mark.slow),
+        ],
+    )
+    @pytest.mark.parametrize("voxel_size", [pytest.param(0.15, id="voxel_0.15")])
+    @pytest.mark.parametrize(
+        "strategy",
+        [
+            pytest.param(DownsampleStrategy.RANDOM, id="random"),
+            pytest.param(DownsampleStrategy.CENTROID, id="centroid"),
+            pytest.param(DownsampleStrategy.MEAN, id="mean"),
+        ],
+    )
+    def test_voxel_grouping_throughput(
+        self,
+        num_points: int,
+        voxel_size: float,
+        strategy: int,
+        benchmark_data,
+        voxel_baseline,
+    ):
+        """测试不同策略下的体素分组吞吐量"""
+        data = benchmark_data["data"]
+        pc = PointCloud.from_xyz(data["xyz"])
+        pc.set_intensity(data["intensity"])
+        pc.add_attribute("d1", data["d1"])
+
+        t0 = time.perf_counter()
+        downsampled = pc.voxel_downsample(voxel_size, strategy, seed=0)
+        elapsed = time.perf_counter() - t0
+
+        throughput = num_points / elapsed / 1_000_000
+        logger.success(
+            f"体素分组完成! "
+            f"strategy={strategy}, "
+            f"输入={num_points:,}, "
+            f"输出={downsampled.point_count():,}, "
+            f"耗时={elapsed:.3f}s "
+            f"(numpy baseline {voxel_baseline['baseline_time']:.3f}s), "
+            f"吞吐量={throughput:.1f}M pts/s"
+        )
+
+        assert downsampled.point_count() == voxel_baseline["unique_voxels"]
+        assert downsampled.has_intensity()
+        assert "d1" in downsampled.attribute_names()
+
+
 class TestBenchmarkSummary:
     """生成完整的性能报告"""
 
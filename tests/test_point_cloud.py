@@ -4,12 +4,58 @@
 覆盖核心功能、边界场景、异常场景
 """
 
+import json
 import math
 
 import numpy as np
 import pytest
 
-from pcl_rustic import DownsampleStrategy, PointCloud
+from pcl_rustic import (
+    DownsampleStrategy,
+    LasWriter,
+    Octree,
+    PointCloud,
+    VoxelGrid,
+)
+
+
+@pytest.fixture
+def sample_cloud():
+    """带强度、颜色、标量与多维属性的随机点云及其坐标"""
+    rng = np.random.default_rng(0)
+    xyz = rng.uniform(0, 30, size=(500, 3)).astype(np.float32)
+    pc = PointCloud.from_xyz(xyz)
+    pc.set_intensity(rng.uniform(0, 1, 500).astype(np.float32))
+    pc.set_rgb(*(rng.integers(0, 256, 500).astype(np.uint8) for _ in range(3)))
+    pc.add_attribute("label", rng.integers(0, 5, 500).astype(np.uint16))
+    pc.add_attribute("normal", rng.normal(size=(500, 3)).astype(np.float32))
+    return pc, xyz
+
+
+@pytest.fixture
+def voxel_xyz():
+    """三个点的坐标，前两点落在同一个 1m 体素内"""
+    return np.array([[0, 0, 0], [0.2, 0, 0], [5, 5, 5]], dtype=np.float32)
+
+
+@pytest.fixture
+def typed_cloud(voxel_xyz):
+    """带 uint32、bool、int64 与 float64 属性的点云"""
+    pc = PointCloud.from_xyz(voxel_xyz)
+    pc.add_attribute("label", np.array([3, 4, 70000], dtype=np.uint32))
+    pc.add_attribute("flag", np.array([True, False, True]))
+    pc.add_attribute("id", np.array([2**40, -1, 7], dtype=np.int64))
+    pc.add_attribute("value", np.array([0.1, 0.2, 0.3], dtype=np.float64))
+    return pc
+
+
+@pytest.fixture
+def vector_cloud(voxel_xyz):
+    """带 [3,4] 多维属性 feat 的点云及其属性值"""
+    features = np.arange(12, dtype=np.float32).reshape(3, 4)
+    pc = PointCloud.from_xyz(voxel_xyz)
+    pc.set_attribute("feat", features)
+    return pc, features
 
 
 class TestPointCloudLifecycle:
@@ -187,6 +233,303 @@ def test_invalid_matrix_dimension(self):
             pc.transform([[1.0, 0.0], [0.0, 1.0]])
 
 
+class TestGlobalCoordinates:
+    """局部坐标原点测试"""
+
+    UTM = np.array(
+        [[512345.678, 4123456.789, 12.345], [512350.001, 4123460.002, 13.5]],
+        dtype=np.float64,
+    )
+
+    def test_float64_input_sets_origin(self):
+        """测试 float64 大坐标自动设置原点并保持毫米精度"""
+        pc = PointCloud.from_xyz(self.UTM)
+        assert pc.origin() == (512345.0, 4123456.0, 0.0)
+        xyz = pc.get_xyz()
+        assert xyz.dtype == np.float64
+        np.testing.assert_allclose(xyz, self.UTM, rtol=0, atol=1e-4)
+
+    def test_small_coordinates_return_float64(self):
+        """测试小坐标不设置原点，get_xyz 仍返回 float64"""
+        pc = PointCloud.from_xyz(np.random.rand(4, 3).astype(np.float32))
+        assert pc.origin() == (0.0, 0.0, 0.0)
+        assert pc.get_xyz().dtype == np.float64
+        assert pc.to_dict()["xyz"].dtype == np.float64
+
+    def test_set_origin_keeps_global(self):
+        """测试更换原点后全局坐标不变"""
+        pc = PointCloud.from_xyz(self.UTM)
+        pc.set_origin((512000.0, 4123000.0, 0.0))
+        np.testing.assert_allclose(pc.get_xyz(), self.UTM, rtol=0, atol=1e-4)
+
+    def test_rigid_transform_global(self):
+        """测试刚体变换的平移以 float64 作用于全局坐标"""
+        local = np.array([[0.0, 0.0, 0.0], [1.25, -2.5, 3.0]])
+        shift = np.array([500000.123, 4000000.456, 1.0])
+        rotation = np.eye(3)
+        moved = PointCloud.from_xyz(local).rigid_transform(rotation, shift)
+        np.testing.assert_allclose(moved.get_xyz(), local + shift, rtol=0, atol=1e-3)
+        back = moved.rigid_transform(rotation, -shift)
+        np.testing.assert_allclose(back.get_xyz(), local, rtol=0, atol=1e-3)
+
+    def test_project_to_image_global(self):
+        """测试相机外参平移为 UTM 量级时投影保持像素精度"""
+        xyz = np.array([[500000.0, 4000000.0, 10.0], [500001.0, 4000000.5, 10.0]])
+        extrinsic = np.eye(4)
+        extrinsic[:3, 3] = [-500000.0, -4000000.0, 0.0]
+        intrinsics = [[100.0, 0.0, 50.0], [0.0, 100.0, 50.0], [0.0, 0.0, 1.0]]
+        pixels, visible = PointCloud.from_xyz(xyz).project_to_image(
+            intrinsics, extrinsic.tolist(), 100, 100
+        )
+        assert visible.all()
+        np.testing.assert_allclose(pixels, [[50.0, 50.0], [60.0, 55.0]], atol=1e-3)
+
+    def test_bev_global_ranges(self):
+        """测试 BEV 范围按全局坐标给出，高度通道为全局高度"""
+        xyz = np.array([[500000.5, 4000000.5, 12.0], [500001.5, 4000000.5, 13.0]])
+        bev = PointCloud.from_xyz(xyz).to_bev(
+            (500000.0, 500002.0), (4000000.0, 4000001.0), 1.0, ["height"]
+        )
+        assert bev.shape == (2, 1, 1)
+        np.testing.assert_allclose(bev[:, 0, 0], [12.0, 13.0], atol=1e-4)
+
+    def test_range_image_sensor(self):
+        """测试距离图像相对传感器的全局位置计算"""
+        xyz = np.array([[500010.0, 4000000.0, 0.0], [500005.0, 4000000.0, 0.0]])
+        image = PointCloud.from_xyz(xyz).to_range_image(
+            (-180.0, 180.0), (-10.0, 10.0), 8, 4, sensor=(500000.0, 4000000.0, 0.0)
+        )
+        hits = image["index_map"][image["index_map"] >= 0]
+        np.testing.assert_array_equal(hits, [1])
+        assert abs(image["depth"].max() - 5.0) < 1e-4
+
+    def test_range_image_default_sensor(self):
+        """测试未指定传感器时取点云原点"""
+        xyz = np.array([[500010.0, 4000005.0, 0.0], [500005.0, 4000010.0, 0.0]])
+        image = PointCloud.from_xyz(xyz).to_range_image(
+            (-180.0, 180.0), (-10.0, 10.0), 64, 4
+        )
+        assert (image["index_map"] >= 0).sum() == 2
+
+    def test_voxel_downsample_global_grid(self):
+        """测试体素边界对齐全局网格而非点云原点"""
+        xyz = np.array([[500005.3, 0.0, 0.0], [500005.2, 0.0, 0.0]])
+        pc = PointCloud.from_xyz(xyz)
+        pc.set_origin((500005.25, 0.0, 0.0))
+        assert pc.voxel_downsample(0.5, DownsampleStrategy.MEAN).point_count() == 1
+
+    def test_voxel_grid_global(self):
+        """测试体素网格的原点、质心与查找均为全局坐标"""
+        xyz = np.array(
+            [[500000.25, 4000000.5, 10.5], [500000.75, 4000000.5, 10.5]],
+        )
+        grid = VoxelGrid(PointCloud.from_xyz(xyz), 1.0, (500000.0, 4000000.0, 0.0))
+        assert grid.origin == (500000.0, 4000000.0, 0.0)
+        np.testing.assert_allclose(
+            grid.centroids(), [[500000.5, 4000000.5, 10.5]], atol=1e-3
+        )
+        voxels = grid.lookup(np.array([[500000.9, 4000000.1, 10.0], [500001.1, 0, 0]]))
+        np.testing.assert_array_equal(voxels, [0, -1])
+
+    def test_octree_global(self):
+        """测试八叉树视锥查询与节点统计使用全局坐标"""
+        xyz = np.array([[500000.0, 4000000.0, 0.0], [500010.0, 4000000.0, 0.0]])
+        octree = Octree(PointCloud.from_xyz(xyz))
+        view = np.eye(4)
+        view[:3, 3] = [-500000.0, -4000000.0, 0.0]
+        hits = octree.query_frustum(view_projection=view.tolist())
+        np.testing.assert_array_equal(hits, [0])
+        hits = octree.query_frustum(planes=[[-1.0, 0.0, 0.0, 500005.0]])
+        np.testing.assert_array_equal(hits, [0])
+        stats = octree.node_stats()
+        np.testing.assert_allclose(stats["min"][0], xyz[0], atol=1e-3)
+        np.testing.assert_allclose(stats["max"][0], xyz[1], atol=1e-3)
+
+    def test_las_roundtrip_precision(self, tmp_path):
+        """测试大坐标写出 LAS 再读回保持毫米精度"""
+        path = str(tmp_path / "utm.las")
+        PointCloud.from_xyz(self.UTM).to_las(path)
+        loaded = PointCloud.from_las(path)
+        np.testing.assert_allclose(loaded.get_xyz(), self.UTM, rtol=0, atol=1e-3)
+
+
+class TestCrs:
+    """坐标参考系与重投影测试"""
+
+    LONLAT = np.array([[15.0, 45.0, 100.0], [15.001, 45.001, 101.0]])
+
+    def test_reproject_utm_roundtrip(self):
+        """测试经纬度与 UTM 互转往返一致"""
+        pc = PointCloud.from_xyz(self.LONLAT)
+        assert pc.crs() is None
+        pc.set_crs("EPSG:4326")
+        utm = pc.reproject("EPSG:32633")
+        assert utm.crs() == "EPSG:32633"
+        # 中央经线上 45°N 的北坐标
+        np.testing.assert_allclose(
+            utm.get_xyz()[0], [500000.0, 4982950.4, 100.0], rtol=0, atol=0.01
+        )
+        lonlat = utm.reproject("EPSG:4326")
+        # 经纬度以整度为原点，局部坐标在 1 度以内
+        assert lonlat.origin() == (15.0, 45.0, 0.0)
+        np.testing.assert_allclose(
+            lonlat.get_xyz()[:, :2], pc.get_xyz()[:, :2], rtol=0, atol=1e-8
+        )
+        # 经纬度中转后回到 UTM 保持毫米精度
+        back = lonlat.reproject("EPSG:32633").get_xyz()
+        np.testing.assert_allclose(back, utm.get_xyz(), rtol=0, atol=1e-3)
+
+    def test_reproject_enu(self):
+        """测试以自身为原点的 ENU 切平面坐标为零"""
+        pc = PointCloud.from_xyz(self.LONLAT[:1])
+        pc.set_crs("EPSG:4326")
+        enu = pc.reproject("ENU:15,45,100")
+        np.testing.assert_allclose(enu.get_xyz(), [[0.0, 0.0, 0.0]], atol=1e-6)
+
+    def test_las_roundtrip_crs(self, tmp_path):
+        """测试 EPSG 坐标系写入 LAS GeoKeys 后读回"""
+        path = str(tmp_path / "crs.las")
+        pc = PointCloud.from_xyz(self.LONLAT)
+        pc.set_crs("4326")
+        pc.reproject("EPSG:32633").to_las(path)
+        assert PointCloud.from_las(path).crs() == "EPSG:32633"
+
+    def test_parquet_roundtrip_crs(self, tmp_path):
+        """测试坐标系写入 Parquet 键值元数据后读回"""
+        path = str(tmp_path / "crs.parquet")
+        pc = PointCloud.from_xyz(self.LONLAT)
+        pc.set_crs("EPSG:4326")
+        pc.to_parquet(path)
+        assert PointCloud.from_parquet(path).crs() == "EPSG:4326"
+        PointCloud.from_xyz(self.LONLAT).to_parquet(path)
+        assert PointCloud.from_parquet(path).crs() is None
+
+    def test_unsupported_crs(self):
+        """测试无法解析或不支持重投影的坐标系"""
+        pc = PointCloud.from_xyz(self.LONLAT)
+        with pytest.raises(ValueError):
+            pc.reproject("EPSG:32633")
+        with pytest.raises(ValueError):
+            pc.set_crs("not a crs")
+        pc.set_crs("EPSG:2056")
+        with pytest.raises(ValueError):
+            pc.reproject("EPSG:4326")
+
+
+class TestTypedAttributes:
+    """类型化自定义属性测试"""
+
+    def test_get_attribute_keeps_dtype(self, typed_cloud):
+        """测试属性按写入dtype返回"""
+        pc = typed_cloud
+        assert pc.get_attribute("label").dtype == np.uint32
+        assert pc.get_attribute("flag").dtype == np.bool_
+        np.testing.assert_array_equal(pc.get_attribute("id"), [2**40, -1, 7])
+        np.testing.assert_array_equal(pc.get_attribute("value"), [0.1, 0.2, 0.3])
+        assert pc.to_dict()["id"].dtype == np.int64
+        with pytest.raises(TypeError):
+            pc.set_attribute("bad", np.zeros(3, dtype=np.complex64))
+
+    def test_parquet_roundtrip_keeps_dtype(self, typed_cloud, tmp_path):
+        """测试Parquet按attributes列表保持dtype"""
+        path = str(tmp_path / "typed.parquet")
+        names = ["label", "flag", "id", "value"]
+        typed_cloud.to_parquet(path, attributes=names)
+        loaded = PointCloud.from_parquet(path, attributes=names)
+        for name in names:
+            expected = typed_cloud.get_attribute(name)
+            actual = loaded.get_attribute(name)
+            assert actual.dtype == expected.dtype
+            np.testing.assert_array_equal(actual, expected)
+
+    def test_las_roundtrip_keeps_dtype(self, typed_cloud, tmp_path):
+        """测试LAS标准字段与Extra Bytes的类型"""
+        path = str(tmp_path / "typed.las")
+        pc = typed_cloud
+        pc.add_attribute("classification", np.array([2, 6, 9], dtype=np.uint8))
+        pc.add_attribute("synthetic", np.array([False, True, False]))
+        pc.to_las(path)
+        loaded = PointCloud.from_las(path)
+        assert loaded.get_attribute("classification").dtype == np.uint8
+        assert loaded.get_attribute("synthetic").dtype == np.bool_
+        assert loaded.get_attribute("label").dtype == np.uint32
+        np.testing.assert_array_equal(loaded.get_attribute("id"), [2**40, -1, 7])
+        # bool 扩展属性以 uint8 写出
+        np.testing.assert_array_equal(loaded.get_attribute("flag"), [1, 0, 1])
+
+    def test_voxel_aggregate_keeps_dtype(self, typed_cloud):
+        """测试体素聚合保持整数类型"""
+        result = typed_cloud.voxel_downsample(1.0, DownsampleStrategy.MEAN)
+        label = result.get_attribute("label")
+        assert label.dtype == np.uint32
+        assert sorted(label.tolist()) == [4, 70000]
+
+    def test_voxel_aggregate_large_uint64(self, voxel_xyz):
+        """测试超过 2^53 的 uint64 属性在原值上取最大值与中位数"""
+        pc = PointCloud.from_xyz(voxel_xyz[:2])
+        ids = np.array([2**64 - 1, 2**64 - 2], dtype=np.uint64)
+        pc.add_attribute("id", ids)
+        result = pc.voxel_downsample(1.0, DownsampleStrategy.MAX)
+        assert result.get_attribute("id").tolist() == [2**64 - 1]
+        result = pc.voxel_downsample(1.0, DownsampleStrategy.MEDIAN)
+        assert result.get_attribute("id").tolist() == [2**64 - 2]
+
+
+class TestVectorAttributes:
+    """多维属性测试"""
+
+    def test_get_attribute_shape(self, vector_cloud):
+        """测试多维属性保持[M,D]形状"""
+        pc, features = vector_cloud
+        np.testing.assert_array_equal(pc.get_attribute("feat"), features)
+        assert pc.to_dict()["feat"].shape == (3, 4)
+        with pytest.raises(ValueError):
+            pc.set_attribute("bad", np.zeros((2, 4), dtype=np.float32))
+        with pytest.raises(TypeError):
+            pc.set_attribute("bad", np.zeros((3, 4), dtype=np.int32))
+
+    def test_voxel_downsample_gathers_rows(self, vector_cloud):
+        """测试体素下采样按行选取与逐分量聚合"""
+        pc, features = vector_cloud
+        mean = pc.voxel_downsample(1.0, DownsampleStrategy.MEAN).get_attribute("feat")
+        np.testing.assert_allclose(mean, [[2, 3, 4, 5], [8, 9, 10, 11]])
+        picked = pc.voxel_downsample(1.0, DownsampleStrategy.RANDOM, seed=1)
+        feat = picked.get_attribute("feat")
+        assert feat.shape == (2, 4)
+        rows = {tuple(row) for row in feat.tolist()}
+        assert tuple(features[2].tolist()) in rows
+
+    def test_parquet_list_column(self, vector_cloud, tmp_path):
+        """测试Parquet以列表列读写多维属性"""
+        pc, features = vector_cloud
+        path = str(tmp_path / "feat.parquet")
+        pc.to_parquet(path, attributes=["feat"])
+        loaded = PointCloud.from_parquet(path, attributes=["feat"])
+        np.testing.assert_array_equal(loaded.get_attribute("feat"), features)
+
+    def test_csv_suffixed_columns(self, vector_cloud, tmp_path):
+        """测试CSV以分量列读写多维属性"""
+        pc, features = vector_cloud
+        path = str(tmp_path / "feat.csv")
+        pc.to_csv(path, attributes=["feat"])
+        with open(path) as f:
+            header = f.readline().strip().split(",")
+        assert header[-4:] == ["feat_0", "feat_1", "feat_2", "feat_3"]
+        loaded = PointCloud.from_csv(path, attributes=["feat"])
+        np.testing.assert_array_equal(loaded.get_attribute("feat"), features)
+
+    def test_las_component_extra_bytes(self, vector_cloud, tmp_path):
+        """测试LAS将多维属性拆分为分量Extra Bytes"""
+        pc, features = vector_cloud
+        path = str(tmp_path / "feat.las")
+        pc.to_las(path)
+        loaded = PointCloud.from_las(path)
+        for k in range(4):
+            np.testing.assert_array_equal(
+                loaded.get_attribute(f"feat_{k}"), features[:, k]
+            )
+
+
 class TestVoxelDownsample:
     """体素下采样测试"""
 
@@ -239,6 +582,71 @@ def test_downsample_with_intensity(self):
         assert result_intensity is not None
         assert len(result_intensity) == downsampled.point_count()
 
+    def test_random_downsample_seeded(self):
+        """测试随机采样可通过种子复现"""
+        xyz = np.random.rand(1000, 3).astype(np.float32)
+        pc = PointCloud.from_xyz(xyz)
+
+        a = pc.voxel_downsample(0.5, DownsampleStrategy.RANDOM, seed=3)
+        b = pc.voxel_downsample(0.5, DownsampleStrategy.RANDOM, seed=3)
+
+        np.testing.assert_array_equal(a.get_xyz(), b.get_xyz())
+
+    def test_mean_downsample_aggregates(self):
+        """测试 MEAN 聚合输出质心并平均各通道"""
+        xyz = np.array(
+            [[0.1, 0.1, 0.1], [0.3, 0.3, 0.3], [1.5, 1.5, 1.5]], dtype=np.float32
+        )
+        pc = PointCloud.from_xyz(xyz)
+        pc.set_intensity(np.array([10.0, 30.0, 50.0], dtype=np.float32))
+        pc.set_rgb(
+            np.array([0, 100, 255], dtype=np.uint8),
+            np.array([0, 100, 255], dtype=np.uint8),
+            np.array([0, 100, 255], dtype=np.uint8),
+        )
+
+        downsampled = pc.voxel_downsample(1.0, DownsampleStrategy.MEAN)
+
+        assert downsampled.point_count() == 2
+        np.testing.assert_allclose(downsampled.get_xyz()[0], [0.2, 0.2, 0.2], atol=1e-5)
+        np.testing.assert_allclose(downsampled.get_intensity(), [20.0, 50.0])
+        r, _, _ = downsampled.get_rgb()
+        np.testing.assert_array_equal(r, [50, 255])
+
+    def test_mode_override_for_classification(self):
+        """测试按属性覆盖聚合方式（分类取众数）"""
+        xyz = np.array(
+            [[0.1, 0.1, 0.1], [0.2, 0.2, 0.2], [0.3, 0.3, 0.3]], dtype=np.float32
+        )
+        pc = PointCloud.from_xyz(xyz)
+        pc.add_attribute(
+            "classification", np.array([2.0, 6.0, 6.0], dtype=np.float32)
+        )
+        pc.add_attribute("score", np.array([1.0, 2.0, 9.0], dtype=np.float32))
+
+        downsampled = pc.voxel_downsample(
+            1.0,
+            DownsampleStrategy.MAX,
+            attribute_modes={"classification": DownsampleStrategy.MODE},
+        )
+
+        classification = downsampled.get_attribute("classification")
+        np.testing.assert_array_equal(classification, [6.0])
+        np.testing.assert_array_equal(downsampled.get_attribute("score"), [9.0])
+
+    def test_attribute_modes_requires_aggregation(self):
+        """测试非聚合策略不接受 attribute_modes"""
+        xyz = np.array([[0.1, 0.1, 0.1]], dtype=np.float32)
+        pc = PointCloud.from_xyz(xyz)
+        pc.add_attribute("score", np.array([1.0], dtype=np.float32))
+
+        with pytest.raises(ValueError):
+            pc.voxel_downsample(
+                1.0,
+                DownsampleStrategy.CENTROID,
+                attribute_modes={"score": DownsampleStrategy.MEAN},
+            )
+
     def test_invalid_voxel_size(self):
         """测试无效 voxel_size"""
         xyz = np.array([[0.1, 0.1, 0.1]], dtype=np.float32)
@@ -248,6 +656,921 @@ def test_invalid_voxel_size(self):
             pc.voxel_downsample(-1.0, DownsampleStrategy.RANDOM)
 
 
+class TestVoxelGrid:
+    """体素网格测试"""
+
+    def _grid(self):
+        xyz = np.array(
+            [
+                [0.2, 0.5, 0.5],
+                [0.4, 0.5, 0.5],
+                [1.5, 0.5, 0.5],
+                [5.5, 5.5, 5.5],
+            ],
+            dtype=np.float32,
+        )
+        return VoxelGrid(PointCloud.from_xyz(xyz), 1.0)
+
+    def test_statistics(self):
+        """测试计数、质心与协方差"""
+        grid = self._grid()
+        voxel = grid.point_voxels()[0]
+
+        assert len(grid) == 3
+        assert grid.counts().sum() == 4
+        np.testing.assert_array_equal(grid.point_indices(voxel), [0, 1])
+        np.testing.assert_allclose(grid.centroids()[voxel], [0.3, 0.5, 0.5])
+        assert grid.covariances().shape == (3, 3, 3)
+        np.testing.assert_allclose(grid.covariances()[voxel][0, 0], 0.02, atol=1e-6)
+
+    def test_lookup_and_neighbors(self):
+        """测试位置查找与邻域查询"""
+        grid = self._grid()
+        voxels = grid.point_voxels()
+
+        found = grid.lookup(np.array([[0.9, 0.1, 0.1], [3.0, 3.0, 3.0]], np.float32))
+        assert found[0] == voxels[0]
+        assert found[1] == -1
+        np.testing.assert_array_equal(grid.neighbors(int(voxels[0])), [voxels[2]])
+
+    def test_to_point_cloud(self):
+        """测试导出体素中心"""
+        grid = self._grid()
+        centers = grid.to_point_cloud()
+
+        assert centers.point_count() == 3
+        assert "point_count" in centers.attribute_names()
+        xyz = centers.get_xyz()
+        assert np.any(np.all(np.isclose(xyz, [0.5, 0.5, 0.5]), axis=1))
+
+    def test_invalid_voxel_size_and_crs(self):
+        """测试非法体素边长报错，导出的体素中心沿用坐标系"""
+        pc = PointCloud.from_xyz(np.zeros((2, 3), dtype=np.float32))
+        for size in (0.0, float("nan"), float("inf")):
+            with pytest.raises(ValueError):
+                VoxelGrid(pc, size)
+        pc.set_crs("EPSG:32633")
+        assert VoxelGrid(pc, 1.0).to_point_cloud().crs() == "EPSG:32633"
+
+
+class TestOctree:
+    """八叉树测试"""
+
+    def _cloud(self):
+        grid = np.stack(np.meshgrid(*[np.arange(8)] * 3, indexing="ij"), axis=-1)
+        return PointCloud.from_xyz(grid.reshape(-1, 3).astype(np.float32))
+
+    def test_levels(self):
+        """测试 LOD 分层抽样"""
+        octree = Octree(self._cloud(), max_depth=3, leaf_size=1)
+
+        assert len(octree.level_indices(0)) == 1
+        assert octree.extract_level(1).point_count() == 8
+        assert octree.extract_level(3).point_count() == 512
+
+    def test_box_and_frustum_query(self):
+        """测试包围盒与视锥查询"""
+        octree = Octree(self._cloud(), max_depth=4, leaf_size=4)
+
+        assert len(octree.query_box((0.0, 0.0, 0.0), (1.0, 1.0, 1.0))) == 8
+
+        # 平面 x <= 2.5 与 x >= 0
+        planes = [[-1.0, 0.0, 0.0, 2.5], [1.0, 0.0, 0.0, 0.0]]
+        hits = octree.query_frustum(planes=planes)
+        assert len(hits) == 3 * 64
+
+        with pytest.raises(ValueError):
+            octree.query_frustum()
+
+    def test_node_stats(self):
+        """测试节点统计"""
+        octree = Octree(self._cloud(), max_depth=2, leaf_size=1)
+        stats = octree.node_stats()
+
+        assert stats["count"][0] == 512
+        assert stats["parent"][0] == -1
+        assert stats["centroid"].shape == (octree.node_count(), 3)
+        np.testing.assert_allclose(stats["centroid"][0], [3.5, 3.5, 3.5])
+
+    def test_references_cloud(self):
+        """测试八叉树引用构建时的点云而不复制"""
+        pc = self._cloud()
+        octree = Octree(pc, max_depth=1, leaf_size=1)
+        pc.set_intensity(np.ones(512, dtype=np.float32))
+
+        assert octree.extract_level(1).has_intensity()
+
+
+class TestFarthestPointSampling:
+    """最远点采样测试"""
+
+    def test_fps_indices(self):
+        """测试采样顺序与属性同步"""
+        xyz = np.array(
+            [[0.0, 0.0, 0.0], [0.1, 0.0, 0.0], [10.0, 0.0, 0.0], [5.0, 0.0, 0.0]],
+            dtype=np.float32,
+        )
+        pc = PointCloud.from_xyz(xyz)
+        pc.set_intensity(np.array([1.0, 2.0, 3.0, 4.0], dtype=np.float32))
+
+        sampled, indices = pc.farthest_point_sampling(3)
+
+        np.testing.assert_array_equal(indices, [0, 2, 3])
+        assert sampled.point_count() == 3
+        np.testing.assert_array_almost_equal(sampled.get_intensity(), [1.0, 3.0, 4.0])
+
+    def test_fps_start_index(self):
+        """测试指定起始点"""
+        xyz = np.random.randn(100, 3).astype(np.float32)
+        pc = PointCloud.from_xyz(xyz)
+
+        _, indices = pc.farthest_point_sampling(10, start_index=42)
+
+        assert indices[0] == 42
+        assert len(set(indices.tolist())) == 10
+
+    def test_fps_too_many_points(self):
+        """测试采样数超过点数"""
+        pc = PointCloud.from_xyz(np.zeros((5, 3), dtype=np.float32))
+        with pytest.raises(ValueError):
+            pc.farthest_point_sampling(6)
+
+
+class TestSampling:
+    """随机 / 均匀 / 泊松圆盘采样测试"""
+
+    def test_random_sample_count(self):
+        """测试按点数随机采样并可复现"""
+        xyz = np.random.randn(100, 3).astype(np.float32)
+        pc = PointCloud.from_xyz(xyz)
+
+        a = pc.random_sample(n=10, seed=1)
+        b = pc.random_sample(n=10, seed=1)
+
+        assert a.point_count() == 10
+        np.testing.assert_array_equal(a.get_xyz(), b.get_xyz())
+
+    def test_random_sample_ratio(self):
+        """测试按比例随机采样"""
+        pc = PointCloud.from_xyz(np.random.randn(200, 3).astype(np.float32))
+
+        assert pc.random_sample(ratio=0.25, seed=0).point_count() == 50
+
+    def test_random_sample_requires_one_argument(self):
+        """测试 n 与 ratio 必须且只能指定一个"""
+        pc = PointCloud.from_xyz(np.random.randn(10, 3).astype(np.float32))
+        with pytest.raises(ValueError):
+            pc.random_sample()
+        with pytest.raises(ValueError):
+            pc.random_sample(n=2, ratio=0.5)
+
+    def test_uniform_sample(self):
+        """测试每隔 k 个点采样"""
+        xyz = np.arange(30, dtype=np.float32).reshape(10, 3)
+        pc = PointCloud.from_xyz(xyz)
+
+        sampled = pc.uniform_sample(3)
+
+        np.testing.assert_array_almost_equal(sampled.get_xyz(), xyz[::3])
+
+    def test_poisson_disk_sample(self):
+        """测试泊松圆盘采样的最小间距"""
+        xyz = np.random.rand(500, 3).astype(np.float32)
+        pc = PointCloud.from_xyz(xyz)
+
+        sampled = pc.poisson_disk_sample(0.2, seed=5).get_xyz()
+
+        diff = sampled[:, None, :] - sampled[None, :, :]
+        dist = np.sqrt((diff**2).sum(-1))
+        np.fill_diagonal(dist, np.inf)
+        assert dist.min() >= 0.2 - 1e-5
+
+
+class TestProjection:
+    """BEV / 距离图像投影测试"""
+
+    def test_bev_shape_and_height(self):
+        """测试 BEV 形状与最大高度通道"""
+        xyz = np.array(
+            [[0.5, 0.5, 1.0], [0.6, 0.6, 2.0], [1.5, 0.5, 3.0], [5.0, 5.0, 4.0]],
+            dtype=np.float32,
+        )
+        pc = PointCloud.from_xyz(xyz)
+
+        bev = pc.to_bev((0.0, 2.0), (0.0, 2.0), 1.0, ["height", "density"])
+
+        assert bev.shape == (2, 2, 2)
+        assert abs(bev[0, 0, 0] - 2.0) < 1e-5
+        assert abs(bev[1, 0, 0] - 3.0) < 1e-5
+        assert np.isnan(bev[0, 1, 0])
+        assert bev[0, 1, 1] == 0.0
+
+    def test_bev_pixel_limit(self):
+        """测试极小分辨率超过像素上限时报错"""
+        pc = PointCloud.from_xyz(np.zeros((1, 3), dtype=np.float32))
+        with pytest.raises(ValueError):
+            pc.to_bev((0.0, 1e6), (0.0, 1e6), 1e-3, ["height"])
+
+    def test_bev_unknown_channel(self):
+        """测试未知通道名"""
+        pc = PointCloud.from_xyz(np.zeros((1, 3), dtype=np.float32))
+        with pytest.raises(ValueError):
+            pc.to_bev((0.0, 1.0), (0.0, 1.0), 0.5, ["rgb"])
+
+    def test_range_image_nearest_point(self):
+        """测试距离图像保留最近点"""
+        xyz = np.array([[10.0, 0.0, 0.0], [5.0, 0.0, 0.0]], dtype=np.float32)
+        pc = PointCloud.from_xyz(xyz)
+        pc.set_intensity(np.array([0.2, 0.8], dtype=np.float32))
+
+        image = pc.to_range_image((-180.0, 180.0), (-10.0, 10.0), 8, 4)
+
+        assert image["depth"].shape == (4, 8)
+        hits = image["index_map"][image["index_map"] >= 0]
+        np.testing.assert_array_equal(hits, [1])
+        assert abs(image["depth"].max() - 5.0) < 1e-4
+        assert abs(image["intensity"].max() - 0.8) < 1e-5
+
+
+class TestDepthBackProjection:
+    """深度图 / RGB-D 反投影测试"""
+
+    K = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+
+    def test_from_depth_image(self):
+        """测试深度图反投影并丢弃无效像素"""
+        depth = np.array([[1000, 0], [2000, 1000]], dtype=np.uint16)
+
+        pc = PointCloud.from_depth_image(depth, self.K, depth_scale=1000.0)
+
+        assert pc.point_count() == 3
+        xyz = pc.get_xyz()
+        np.testing.assert_array_almost_equal(xyz[1], [0.0, 2.0, 2.0])
+
+    def test_depth_trunc(self):
+        """测试截断深度"""
+        depth = np.array([[1.0, 5.0]], dtype=np.float32)
+
+        pc = PointCloud.from_depth_image(
+            depth, self.K, depth_scale=1.0, depth_trunc=3.0
+        )
+
+        assert pc.point_count() == 1
+
+    def test_from_depth_image_with_extrinsic(self):
+        """测试外参（世界->相机）反变换到世界坐标"""
+        depth = np.array([[1.0]], dtype=np.float32)
+        extrinsic = [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, -1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+
+        pc = PointCloud.from_depth_image(
+            depth, self.K, depth_scale=1.0, extrinsic=extrinsic
+        )
+
+        np.testing.assert_array_almost_equal(pc.get_xyz()[0], [0.0, 0.0, 2.0])
+
+    def test_from_rgbd(self):
+        """测试 RGB-D 反投影携带颜色"""
+        color = np.zeros((2, 2, 3), dtype=np.uint8)
+        color[1, 0] = [10, 20, 30]
+        depth = np.array([[1000, 0], [2000, 1000]], dtype=np.uint16)
+
+        pc = PointCloud.from_rgbd(color, depth, self.K)
+
+        assert pc.has_rgb()
+        r, g, b = pc.get_rgb()
+        assert (r[1], g[1], b[1]) == (10, 20, 30)
+
+    def test_rgbd_shape_mismatch(self):
+        """测试颜色图与深度图尺寸不一致"""
+        color = np.zeros((3, 2, 3), dtype=np.uint8)
+        depth = np.ones((2, 2), dtype=np.float32)
+
+        with pytest.raises(ValueError):
+            PointCloud.from_rgbd(color, depth, self.K)
+
+
+class TestImageProjection:
+    """点云投影到图像与着色测试"""
+
+    K = [[10.0, 0.0, 2.0], [0.0, 10.0, 2.0], [0.0, 0.0, 1.0]]
+
+    def test_project_visibility(self):
+        """测试 z-buffer 遮挡与相机后方的点"""
+        xyz = np.array(
+            [[0.0, 0.0, 1.0], [0.0, 0.0, 3.0], [0.0, 0.0, -1.0], [10.0, 0.0, 1.0]],
+            dtype=np.float32,
+        )
+        pc = PointCloud.from_xyz(xyz)
+
+        pixels, visible = pc.project_to_image(self.K, None, 4, 4)
+
+        assert pixels.shape == (4, 2)
+        np.testing.assert_array_almost_equal(pixels[0], [2.0, 2.0])
+        np.testing.assert_array_almost_equal(pixels[2], [-1.0, -1.0])
+        np.testing.assert_array_equal(visible, [True, False, False, False])
+
+    def test_invalid_distortion(self):
+        """测试非法畸变系数个数"""
+        pc = PointCloud.from_xyz(np.array([[0.0, 0.0, 1.0]], dtype=np.float32))
+        with pytest.raises(ValueError):
+            pc.project_to_image(self.K, None, 4, 4, [0.1, 0.2])
+
+    def test_colorize_from_image(self):
+        """测试从图像着色，只修改可见点"""
+        xyz = np.array([[0.0, 0.0, 1.0], [0.0, 0.0, -1.0]], dtype=np.float32)
+        pc = PointCloud.from_xyz(xyz)
+        image = np.full((4, 4, 3), 100, dtype=np.uint8)
+
+        visible = pc.colorize_from_image(image, self.K)
+
+        np.testing.assert_array_equal(visible, [True, False])
+        r, g, b = pc.get_rgb()
+        assert (r[0], g[0], b[0]) == (100, 100, 100)
+        assert r[1] == 0
+
+
+class TestTableAttributes:
+    """表格IO全部属性读写测试"""
+
+    def test_write_all_read_all(self, tmp_path, sample_cloud):
+        """测试默认写出全部属性并按全部模式读回"""
+        pc, _ = sample_cloud
+        path = str(tmp_path / "all.parquet")
+        pc.to_parquet(path)
+        assert PointCloud.from_parquet(path).attribute_names() == []
+        loaded = PointCloud.from_parquet(path, all_attributes=True)
+        assert sorted(loaded.attribute_names()) == ["label", "normal"]
+        assert loaded.get_attribute("label").dtype == np.uint16
+        assert loaded.has_intensity()
+
+    def test_exclude_attributes(self, tmp_path, sample_cloud):
+        """测试读写时排除属性"""
+        pc, _ = sample_cloud
+        pc.add_attribute("id", np.arange(500, dtype=np.int64) + 100)
+        path = str(tmp_path / "exclude.csv")
+        pc.to_csv(path, exclude_attributes=["normal"])
+        with open(path) as f:
+            header = f.readline().strip().split(",")
+        assert "normal_0" not in header and "label" in header
+        loaded = PointCloud.from_csv(
+            path, all_attributes=True, exclude_attributes=["id"]
+        )
+        assert loaded.attribute_names() == ["label"]
+
+    def test_non_numeric_columns_ignored(self, tmp_path):
+        """测试全部模式忽略字符串列"""
+        path = tmp_path / "mixed.csv"
+        path.write_text("x,y,z,name,class\n0,0,0,a,1\n1,1,1,b,2\n")
+        loaded = PointCloud.from_csv(str(path), all_attributes=True)
+        assert loaded.attribute_names() == ["class"]
+        np.testing.assert_array_equal(loaded.get_attribute("class"), [1, 2])
+
+
+class TestParquetScan:
+    """Parquet 延迟扫描测试"""
+
+    def write_tiles(self, tmp_path):
+        root = tmp_path / "lake"
+        root.mkdir()
+        for k in range(2):
+            xyz = np.stack(
+                [np.arange(10) + 10 * k, np.zeros(10), np.arange(10)], axis=1
+            ).astype(np.float32)
+            pc = PointCloud.from_xyz(xyz)
+            pc.add_attribute("label", (np.arange(10) % 3).astype(np.int32))
+            pc.add_attribute("score", np.arange(10, dtype=np.float32))
+            pc.to_parquet(str(root / f"tile_{k}.parquet"))
+        return root
+
+    def test_scan_directory_bbox(self, tmp_path):
+        """测试目录扫描与包围盒过滤"""
+        root = self.write_tiles(tmp_path)
+        pc = PointCloud.scan_parquet(str(root), bbox=(5, -1, 14, 1))
+        x = np.sort(pc.get_xyz()[:, 0])
+        np.testing.assert_array_equal(x, np.arange(5, 15))
+        assert pc.attribute_names() == []
+        pc = PointCloud.scan_parquet(str(root), bbox=(0, -1, 0, 19, 1, 2))
+        assert pc.point_count() == 6
+        empty = PointCloud.scan_parquet(str(root), bbox=(100, 100, 200, 200))
+        assert empty.point_count() == 0
+
+    def test_scan_filters_and_projection(self, tmp_path):
+        """测试属性过滤、列投影与行数限制"""
+        root = self.write_tiles(tmp_path)
+        pattern = str(root / "tile_*.parquet")
+        pc = PointCloud.scan_parquet(
+            pattern, filters={"label": [0], "score": (None, 5)}, attributes=["label"]
+        )
+        assert pc.attribute_names() == ["label"]
+        assert pc.point_count() == 4
+        assert set(pc.get_attribute("label").tolist()) == {0}
+        limited = PointCloud.scan_parquet(pattern, limit=3, all_attributes=True)
+        assert limited.point_count() == 3
+        assert sorted(limited.attribute_names()) == ["label", "score"]
+
+    def test_scan_invalid_arguments(self, tmp_path):
+        """测试非法的包围盒与过滤条件"""
+        root = self.write_tiles(tmp_path)
+        with pytest.raises(ValueError):
+            PointCloud.scan_parquet(str(root), bbox=(0, 0, 1))
+        with pytest.raises(TypeError):
+            PointCloud.scan_parquet(str(root), filters={"label": "a"})
+
+
+class TestParquetDataset:
+    """分区 Parquet 数据集测试"""
+
+    @pytest.mark.parametrize("scheme", ["grid", "morton", "hilbert"])
+    def test_dataset_roundtrip(self, tmp_path, sample_cloud, scheme):
+        """测试分区写出与整体扫描"""
+        pc, xyz = sample_cloud
+        pc.set_crs("EPSG:32633")
+        root = tmp_path / scheme
+        pc.to_parquet_dataset(str(root), 10.0, scheme=scheme)
+        index = json.loads((root / "_dataset.json").read_text())
+        assert index["scheme"] == scheme
+        assert len(index["tiles"]) == 9
+        assert sum(tile["points"] for tile in index["tiles"]) == len(xyz)
+        loaded = PointCloud.scan_parquet(str(root), all_attributes=True)
+        assert loaded.point_count() == len(xyz)
+        assert sorted(loaded.attribute_names()) == ["label", "normal"]
+        assert loaded.crs() == "EPSG:32633"
+        np.testing.assert_allclose(
+            np.sort(loaded.get_xyz()[:, 0]), np.sort(xyz[:, 0])
+        )
+
+    def test_grid_partitions_and_pruning(self, tmp_path, sample_cloud):
+        """测试Hive目录与按包围盒裁剪瓦片"""
+        pc, xyz = sample_cloud
+        pc.set_crs("EPSG:32633")
+        root = tmp_path / "grid"
+        pc.to_parquet_dataset(str(root), 10.0, row_group_size=16)
+        tile = root / "tile_x=1" / "tile_y=2" / "part-0.parquet"
+        assert tile.exists()
+        part = PointCloud.from_parquet(str(tile))
+        assert np.all((part.get_xyz()[:, 0] >= 10) & (part.get_xyz()[:, 0] < 20))
+        # 单个瓦片文件也带有坐标系
+        assert part.crs() == "EPSG:32633"
+        (root / "tile_x=0" / "tile_y=0" / "part-0.parquet").unlink()
+        # 被裁剪的瓦片不会被读取
+        loaded = PointCloud.scan_parquet(str(root), bbox=(12, 22, 18, 28))
+        x, y = xyz[:, 0], xyz[:, 1]
+        mask = (x >= 12) & (x <= 18) & (y >= 22) & (y <= 28)
+        assert loaded.point_count() == int(mask.sum())
+
+    def test_overwrite(self, tmp_path, sample_cloud):
+        """测试非空目录默认报错，覆盖时删除旧瓦片"""
+        pc, xyz = sample_cloud
+        root = tmp_path / "lake"
+        pc.to_parquet_dataset(str(root), 10.0)
+        (root / "notes.txt").write_text("keep")
+        with pytest.raises(ValueError):
+            pc.to_parquet_dataset(str(root), 10.0)
+        small = PointCloud.from_xyz(xyz[xyz[:, 0] < 10])
+        small.to_parquet_dataset(str(root), 10.0, overwrite=True)
+        assert not (root / "tile_x=1").exists()
+        assert (root / "notes.txt").exists()
+        loaded = PointCloud.scan_parquet(str(root))
+        assert loaded.point_count() == small.point_count()
+
+    def test_invalid_dataset_arguments(self, tmp_path, sample_cloud):
+        """测试非法瓦片参数"""
+        pc, _ = sample_cloud
+        with pytest.raises(ValueError):
+            pc.to_parquet_dataset(str(tmp_path / "a"), 0.0)
+        with pytest.raises(ValueError):
+            pc.to_parquet_dataset(str(tmp_path / "b"), 10.0, scheme="quadkey")
+
+
+class TestArrowInterop:
+    """Arrow IPC 文件与 Arrow C 数据接口互通测试"""
+
+    @pytest.mark.parametrize("suffix", [".arrow", ".feather"])
+    def test_ipc_roundtrip(self, tmp_path, sample_cloud, suffix):
+        """测试IPC文件往返、坐标系与扩展名自动识别"""
+        pc, xyz = sample_cloud
+        pc.set_crs("EPSG:32633")
+        path = str(tmp_path / f"cloud{suffix}")
+        pc.save_to_file(path)
+        loaded = PointCloud.from_ipc(path, all_attributes=True)
+        np.testing.assert_allclose(loaded.get_xyz(), xyz)
+        assert loaded.has_intensity()
+        assert loaded.crs() == "EPSG:32633"
+        assert loaded.get_attribute("label").dtype == np.uint16
+        assert loaded.get_attribute("normal").shape == (len(xyz), 3)
+        auto = PointCloud.load_from_file(path, attributes=["label"])
+        assert auto.attribute_names() == ["label"]
+
+    def test_to_arrow(self, sample_cloud):
+        """测试导出为pyarrow.Table"""
+        pa = pytest.importorskip("pyarrow")
+        pc, xyz = sample_cloud
+        table = pc.to_arrow(exclude_attributes=["normal"])
+        assert isinstance(table, pa.Table)
+        assert table.num_rows == len(xyz)
+        assert table.column_names == [
+            "x", "y", "z", "intensity", "r", "g", "b", "label"
+        ]
+        assert table.schema.field("label").type == pa.uint16()
+        np.testing.assert_allclose(table.column("x").to_numpy(), xyz[:, 0])
+
+    def test_arrow_roundtrip(self, sample_cloud):
+        """测试经PyCapsule接口往返，定长列表读为多维属性"""
+        pa = pytest.importorskip("pyarrow")
+        pc, xyz = sample_cloud
+        table = pa.table(pc)
+        assert table.schema.field("normal").type == pa.list_(pa.float32())
+        loaded = PointCloud.from_arrow(table, all_attributes=True)
+        np.testing.assert_allclose(loaded.get_xyz(), xyz)
+        np.testing.assert_allclose(
+            loaded.get_attribute("normal"), pc.get_attribute("normal")
+        )
+
+        n = len(xyz)
+        normals = pa.FixedSizeListArray.from_arrays(
+            pa.array(np.ones(3 * n, dtype=np.float32)), 3
+        )
+        batch = pa.record_batch(
+            {
+                "px": xyz[:, 0],
+                "py": xyz[:, 1],
+                "pz": xyz[:, 2],
+                "name": ["p"] * n,
+                "normal": normals,
+            }
+        )
+        loaded = PointCloud.from_arrow(
+            batch, x="px", y="py", z="pz", all_attributes=True
+        )
+        assert loaded.attribute_names() == ["normal"]
+        assert loaded.get_attribute("normal").shape == (n, 3)
+
+    def test_from_arrow_multiple_batches(self, sample_cloud):
+        """测试多个RecordBatch的流按顺序合并"""
+        pa = pytest.importorskip("pyarrow")
+        pc, xyz = sample_cloud
+        batches = pa.table(pc).to_batches(max_chunksize=128)
+        assert len(batches) > 1
+        reader = pa.RecordBatchReader.from_batches(batches[0].schema, batches)
+        loaded = PointCloud.from_arrow(reader, attributes=["label"])
+        np.testing.assert_allclose(loaded.get_xyz(), xyz)
+        np.testing.assert_array_equal(
+            loaded.get_attribute("label"), pc.get_attribute("label")
+        )
+
+    def test_from_arrow_rejects_nulls(self):
+        """测试坐标或属性列含空值时报错，未使用的列不受影响"""
+        pa = pytest.importorskip("pyarrow")
+        table = pa.table(
+            {
+                "x": [0.0, 1.0],
+                "y": [0.0, 1.0],
+                "z": [0.0, None],
+                "label": pa.array([1, None], type=pa.int32()),
+            }
+        )
+        with pytest.raises(ValueError):
+            PointCloud.from_arrow(table)
+        table = table.set_column(2, "z", pa.array([0.0, 1.0]))
+        assert PointCloud.from_arrow(table).point_count() == 2
+        with pytest.raises(ValueError):
+            PointCloud.from_arrow(table, attributes=["label"])
+
+    def test_from_arrow_rejects_other_objects(self):
+        """测试不支持Arrow接口的对象与非结构体数组"""
+        with pytest.raises(TypeError):
+            PointCloud.from_arrow({"x": [1.0]})
+        pa = pytest.importorskip("pyarrow")
+
+        class PlainArray:
+            def __arrow_c_array__(self, requested_schema=None):
+                return pa.array([1.0, 2.0]).__arrow_c_array__()
+
+        with pytest.raises(TypeError):
+            PointCloud.from_arrow(PlainArray())
+
+
+class TestDataFrameInterop:
+    """polars/pandas DataFrame 互转测试"""
+
+    def test_polars_roundtrip(self, sample_cloud):
+        """测试polars往返与列名映射"""
+        pl = pytest.importorskip("polars")
+        pc, _ = sample_cloud
+        df = pc.to_polars(x="lon", y="lat", z="h")
+        assert isinstance(df, pl.DataFrame)
+        assert df.columns == [
+            "lon", "lat", "h", "intensity", "r", "g", "b", "label", "normal"
+        ]
+        assert df["label"].dtype == pl.UInt16
+        filtered = df.filter(df["label"] == 1)
+        loaded = PointCloud.from_polars(
+            filtered, x="lon", y="lat", z="h", all_attributes=True
+        )
+        assert loaded.point_count() == filtered.height
+        assert loaded.has_rgb()
+        assert sorted(loaded.attribute_names()) == ["label", "normal"]
+        assert np.all(loaded.get_attribute("label") == 1)
+        assert loaded.get_attribute("normal").shape == (filtered.height, 3)
+
+    def test_pandas_roundtrip(self, sample_cloud):
+        """测试pandas往返，忽略索引"""
+        pd = pytest.importorskip("pandas")
+        pytest.importorskip("pyarrow")
+        pc, xyz = sample_cloud
+        pdf = pc.to_pandas(exclude_attributes=["normal"])
+        assert isinstance(pdf, pd.DataFrame)
+        assert list(pdf.columns) == ["x", "y", "z", "intensity", "r", "g", "b", "label"]
+        np.testing.assert_allclose(pdf["x"].to_numpy(), xyz[:, 0])
+        pdf.index = pdf.index + 100
+        loaded = PointCloud.from_pandas(pdf, attributes=["label"])
+        np.testing.assert_allclose(loaded.get_xyz(), xyz)
+        np.testing.assert_array_equal(
+            loaded.get_attribute("label"), pc.get_attribute("label")
+        )
+
+
+class TestDlpack:
+    """NumPy 数组接口与 DLPack 互通测试"""
+
+    def test_array_interface(self, sample_cloud):
+        """测试np.asarray得到共享缓冲区的只读视图"""
+        pc, xyz = sample_cloud
+        tensor = pc.host_tensor()
+        assert tensor.shape == [len(xyz), 3]
+        view = np.asarray(tensor)
+        assert view.dtype == np.float64
+        assert not view.flags.writeable
+        np.testing.assert_allclose(view, xyz)
+        assert np.shares_memory(view, np.asarray(tensor))
+
+    def test_from_dlpack_export(self, sample_cloud):
+        """测试np.from_dlpack导出坐标与属性，属性保持dtype"""
+        pc, xyz = sample_cloud
+        np.testing.assert_allclose(np.from_dlpack(pc), xyz)
+        tensor = pc.host_tensor("label")
+        label = np.from_dlpack(tensor)
+        assert label.dtype == np.uint16
+        np.testing.assert_array_equal(label, pc.get_attribute("label"))
+        assert np.shares_memory(label, np.asarray(tensor))
+        copied = np.from_dlpack(tensor, copy=True)
+        assert not np.shares_memory(copied, np.asarray(tensor))
+
+    def test_dlpack_copy_false(self, sample_cloud):
+        """测试点云导出必然复制，copy=False时报BufferError"""
+        pc, _ = sample_cloud
+        with pytest.raises(BufferError):
+            pc.__dlpack__(copy=False)
+        tensor = pc.host_tensor("normal")
+        assert np.from_dlpack(tensor, copy=False).shape == (tensor.shape[0], 3)
+
+    def test_host_tensor_shares_storage(self, sample_cloud):
+        """测试float32张量共享点云存储，float64全局坐标与主机端属性需要复制"""
+        pc, xyz = sample_cloud
+        local = pc.host_tensor("local_xyz")
+        assert np.asarray(local).dtype == np.float32
+        np.testing.assert_array_equal(np.asarray(local), xyz)
+        assert not pc.host_tensor("xyz").shared
+        with pytest.raises(BufferError):
+            pc.host_tensor("xyz", copy=False)
+        with pytest.raises(BufferError):
+            pc.host_tensor("label", copy=False)
+        if not local.shared:
+            pytest.skip("点云不在 NdArray 后端上")
+        for name in ["local_xyz", "intensity", "normal"]:
+            view = np.asarray(pc.host_tensor(name, copy=False))
+            assert np.shares_memory(view, np.from_dlpack(pc.host_tensor(name)))
+        copied = pc.host_tensor("intensity", copy=True)
+        assert not copied.shared
+        view = np.asarray(pc.host_tensor("intensity"))
+        assert not np.shares_memory(np.asarray(copied), view)
+
+    def test_from_dlpack_import(self, sample_cloud):
+        """测试从连续与带步长的数组导入坐标"""
+        _, xyz = sample_cloud
+        loaded = PointCloud.from_dlpack(np.ascontiguousarray(xyz))
+        np.testing.assert_allclose(loaded.get_xyz(), xyz)
+        strided = np.asfortranarray(xyz)[::2]
+        loaded = PointCloud.from_dlpack(strided)
+        np.testing.assert_allclose(loaded.get_xyz(), xyz[::2])
+
+    def test_from_dlpack_float64_origin(self):
+        """测试float64输入按全局坐标处理并设置原点"""
+        xyz = np.array([[512345.678, 4123456.789, 12.3]], dtype=np.float64)
+        pc = PointCloud.from_dlpack(xyz)
+        assert pc.origin() == (512345.0, 4123456.0, 0.0)
+        np.testing.assert_allclose(pc.get_xyz(), xyz, atol=1e-3)
+
+    def test_invalid(self, sample_cloud):
+        """测试未知名称与错误形状"""
+        pc, _ = sample_cloud
+        with pytest.raises(ValueError):
+            pc.host_tensor("missing")
+        with pytest.raises(ValueError):
+            PointCloud.from_dlpack(np.zeros((4, 2), dtype=np.float32))
+
+
+class TestLasStreaming:
+    """LAS 分块读取与流式写入测试"""
+
+    def test_chunked_roundtrip(self, tmp_path):
+        """测试分块写入后分块读取，点数与坐标一致"""
+        path = str(tmp_path / "stream.las")
+        xyz = np.random.rand(2500, 3).astype(np.float32) * 100
+
+        with LasWriter(path) as writer:
+            for start in range(0, 2500, 1000):
+                writer.write(PointCloud.from_xyz(xyz[start : start + 1000]))
+            assert writer.point_count == 2500
+
+        chunks = list(PointCloud.iter_las(path, chunk_size=1000))
+        assert [c.point_count() for c in chunks] == [1000, 1000, 500]
+        merged = np.concatenate([c.get_xyz() for c in chunks])
+        np.testing.assert_allclose(merged, xyz, atol=1e-2)
+
+    def test_las_fields_roundtrip(self, tmp_path):
+        """测试分类、回波与 GPS 时间写出后可完整读回"""
+        path = str(tmp_path / "fields.las")
+        pc = PointCloud.from_xyz(np.random.rand(4, 3).astype(np.float32))
+        pc.add_attribute(
+            "classification", np.array([2, 6, 40, 9], dtype=np.float32)
+        )
+        pc.add_attribute("return_number", np.array([1, 2, 1, 3], dtype=np.float32))
+        gps_time = np.array([1.3e9, 1.3e9 + 0.25, 1.3e9 + 0.5, 1.3e9 + 1.0])
+        pc.set_gps_time(gps_time)
+        pc.to_las(path, False)
+
+        loaded = PointCloud.from_las(path)
+        np.testing.assert_array_equal(
+            loaded.get_attribute("classification"), [2, 6, 40, 9]
+        )
+        np.testing.assert_array_equal(
+            loaded.get_attribute("return_number"), [1, 2, 1, 3]
+        )
+        # 分类超过 31 需要扩展格式，扩展格式带扫描通道
+        assert "scanner_channel" in loaded.attribute_names()
+        np.testing.assert_array_equal(loaded.get_gps_time(), gps_time)
+
+    def test_extra_bytes_roundtrip(self, tmp_path):
+        """测试自定义属性通过 Extra Bytes 保留类型、缩放与无效值"""
+        path = str(tmp_path / "extra.las")
+        pc = PointCloud.from_xyz(np.random.rand(3, 3).astype(np.float32))
+        pc.add_attribute("score", np.array([0.25, 0.5, 0.75], dtype=np.float32))
+        pc.add_attribute("hag", np.array([1.5, np.nan, 3.25], dtype=np.float32))
+        pc.set_las_extra_bytes("hag", "uint16", scale=0.01, no_data=65535)
+        pc.to_las(path, False)
+
+        loaded = PointCloud.from_las(path)
+        np.testing.assert_allclose(loaded.get_attribute("score"), [0.25, 0.5, 0.75])
+        hag = loaded.get_attribute("hag")
+        np.testing.assert_allclose(hag[[0, 2]], [1.5, 3.25], atol=1e-4)
+        assert np.isnan(hag[1])
+        descriptors = {d["name"]: d for d in loaded.las_extra_bytes()}
+        assert descriptors["hag"]["data_type"] == "uint16"
+        assert descriptors["hag"]["no_data"] == 65535
+        assert descriptors["score"]["data_type"] == "float32"
+
+    def test_voxel_mean_keeps_las_fields(self, tmp_path):
+        """测试 LAS 点云经 MEAN 体素聚合后保留 GPS 时间与 Extra Bytes"""
+        path = str(tmp_path / "voxel.las")
+        xyz = np.array([[0.1, 0.1, 0.1], [0.2, 0.2, 0.2], [2.5, 2.5, 2.5]])
+        pc = PointCloud.from_xyz(xyz.astype(np.float32))
+        pc.set_gps_time(np.array([1.3e9, 1.3e9 + 0.5, 1.3e9 + 2.0]))
+        pc.add_attribute("hag", np.array([1.0, 2.0, 3.0], dtype=np.float32))
+        pc.set_las_extra_bytes("hag", "uint16", scale=0.01, no_data=65535)
+        pc.to_las(path, False)
+
+        loaded = PointCloud.from_las(path)
+        result = loaded.voxel_downsample(1.0, DownsampleStrategy.MEAN)
+        gps_time = result.get_gps_time()
+        np.testing.assert_array_equal(gps_time, [1.3e9 + 0.25, 1.3e9 + 2.0])
+        np.testing.assert_allclose(result.get_attribute("hag"), [1.5, 3.0], atol=1e-4)
+        descriptors = {d["name"]: d for d in result.las_extra_bytes()}
+        assert descriptors["hag"]["data_type"] == "uint16"
+
+    def test_extra_bytes_long_name_rejected(self, tmp_path):
+        """测试超过32字节的属性名写出时报错而不截断"""
+        pc = PointCloud.from_xyz(np.random.rand(3, 3).astype(np.float32))
+        pc.add_attribute("高程" * 6, np.zeros(3, dtype=np.float32))
+        with pytest.raises(ValueError, match="32"):
+            pc.to_las(str(tmp_path / "long.las"))
+
+    def test_header_preserved(self, tmp_path):
+        """测试指定的版本、缩放与偏移写出后保留，再次写出时沿用"""
+        path = str(tmp_path / "header.las")
+        copy_path = str(tmp_path / "header_copy.las")
+        pc = PointCloud.from_xyz(np.random.rand(5, 3).astype(np.float32) + 10)
+        assert pc.las_header() is None
+        pc.to_las(path, version=(1, 2), scale=(0.01, 0.01, 0.001), offset=(10, 10, 0))
+
+        header = PointCloud.from_las(path).las_header()
+        assert header["version"] == (1, 2)
+        np.testing.assert_allclose(header["scale"], [0.01, 0.01, 0.001])
+        np.testing.assert_allclose(header["offset"], [10, 10, 0])
+
+        PointCloud.from_las(path).to_las(copy_path)
+        copied = PointCloud.from_las(copy_path).las_header()
+        assert copied["version"] == (1, 2)
+        np.testing.assert_allclose(copied["scale"], header["scale"])
+        np.testing.assert_allclose(copied["offset"], header["offset"])
+
+    def test_invalid_scale_rejected(self, tmp_path):
+        """测试非正的缩放比例被拒绝"""
+        pc = PointCloud.from_xyz(np.random.rand(3, 3).astype(np.float32))
+        with pytest.raises(ValueError):
+            pc.to_las(str(tmp_path / "bad.las"), scale=(0.0, 0.01, 0.01))
+
+    def test_mismatched_rgb_rejected(self, tmp_path):
+        """测试后续块 RGB 与首块不一致时报错"""
+        xyz = np.random.rand(10, 3).astype(np.float32)
+        colored = PointCloud.from_xyz(xyz)
+        colored.set_rgb(
+            np.zeros(10, dtype=np.uint8),
+            np.zeros(10, dtype=np.uint8),
+            np.zeros(10, dtype=np.uint8),
+        )
+        writer = LasWriter(str(tmp_path / "mixed.las"))
+        writer.write(colored)
+        with pytest.raises(Exception):
+            writer.write(PointCloud.from_xyz(xyz))
+        writer.close()
+
+    def test_explicit_point_format(self, tmp_path):
+        """测试预先指定点格式后，后续块可带首块没有的GPS时间"""
+        xyz = np.random.rand(10, 3).astype(np.float32)
+        timed = PointCloud.from_xyz(xyz)
+        timed.set_gps_time(np.arange(10, dtype=np.float64))
+
+        path = str(tmp_path / "auto.las")
+        with LasWriter(path) as writer:
+            writer.write(PointCloud.from_xyz(xyz))
+            with pytest.raises(ValueError, match="GPS"):
+                writer.write(timed)
+        assert PointCloud.from_las(path).point_count() == 10
+
+        path = str(tmp_path / "format1.las")
+        with LasWriter(path, point_format=1) as writer:
+            writer.write(PointCloud.from_xyz(xyz))
+            writer.write(timed)
+        loaded = PointCloud.from_las(path)
+        assert loaded.point_count() == 20
+        np.testing.assert_array_equal(loaded.get_gps_time()[10:], np.arange(10))
+
+        with pytest.raises(ValueError):
+            LasWriter(str(tmp_path / "bad.las"), point_format=4)
+
+
+class TestCopc:
+    """COPC 读取测试"""
+
+    def test_plain_las_is_not_copc(self, tmp_path):
+        """测试普通 LAS 文件不被识别为 COPC"""
+        path = str(tmp_path / "plain.las")
+        PointCloud.from_xyz(np.random.rand(100, 3).astype(np.float32)).to_las(
+            path, False
+        )
+
+        assert not PointCloud.is_copc(path)
+        assert PointCloud.copc_info(path) is None
+        with pytest.raises(Exception):
+            PointCloud.from_copc(path)
+
+
+class TestPotreeExport:
+    """Potree 导出测试"""
+
+    def test_potree_layout(self, tmp_path):
+        """测试导出目录结构与记录大小"""
+        xyz = np.random.rand(5000, 3).astype(np.float32) * 10
+        pc = PointCloud.from_xyz(xyz)
+        pc.set_intensity(np.full(5000, 0.5, dtype=np.float32))
+        pc.set_rgb(
+            np.zeros(5000, dtype=np.uint8),
+            np.zeros(5000, dtype=np.uint8),
+            np.zeros(5000, dtype=np.uint8),
+        )
+        pc.add_attribute("score", np.ones(5000, dtype=np.float32))
+
+        pc.to_potree(str(tmp_path), name="demo", leaf_size=500)
+
+        metadata = json.loads((tmp_path / "metadata.json").read_text())
+        names = [a["name"] for a in metadata["attributes"]]
+        assert names == ["position", "intensity", "rgb", "score"]
+        assert metadata["points"] == 5000
+        assert metadata["name"] == "demo"
+
+        # position(12) + intensity(2) + rgb(6) + score(4)
+        assert (tmp_path / "octree.bin").stat().st_size == 5000 * 24
+        hierarchy_size = (tmp_path / "hierarchy.bin").stat().st_size
+        assert hierarchy_size == metadata["hierarchy"]["firstChunkSize"]
+        assert hierarchy_size % 22 == 0
+
+
 class TestMemoryAndRepr:
     """内存和表示测试"""
 